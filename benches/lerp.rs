@@ -0,0 +1,66 @@
+use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::*;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use shape_lerping::path_lerping::Lerp;
+use std::f32::consts::TAU;
+use tess::path::Path;
+
+// Typical per-frame parameters, small enough that nothing snaps mid-benchmark
+const LERP_T: f32 = 0.025;
+const MARGIN_OF_ERROR: f32 = 1.0;
+
+fn polygon(sides: usize, radius: f32) -> Path {
+    ShapePath::build_as(&shapes::RegularPolygon {
+        sides,
+        feature: shapes::RegularPolygonFeature::Radius(radius),
+        ..Default::default()
+    })
+    .0
+}
+
+// A closed wavy ring with `segments` line events, going through the SVG import path
+fn svg_ring(segments: usize, waves: f32) -> Path {
+    let mut data = String::new();
+    for i in 0..segments {
+        let angle = i as f32 / segments as f32 * TAU;
+        let radius = 200.0 + 20.0 * (angle * waves).sin();
+        let command = if i == 0 { 'M' } else { 'L' };
+        data.push_str(&format!(
+            "{}{} {} ",
+            command,
+            500.0 + radius * angle.cos(),
+            500.0 + radius * angle.sin()
+        ));
+    }
+    data.push('Z');
+    ShapePath::build_as(&shapes::SvgPathShape {
+        svg_path_string: data,
+        svg_doc_size_in_px: Vec2::new(1000.0, 1000.0),
+    })
+    .0
+}
+
+fn bench_pair(c: &mut Criterion, name: &str, from: &Path, to: &Path) {
+    c.bench_function(name, |b| {
+        b.iter(|| black_box(from).lerped(black_box(to), Vec2::splat(LERP_T), MARGIN_OF_ERROR))
+    });
+}
+
+fn lerp_benchmarks(c: &mut Criterion) {
+    let triangle = polygon(3, 200.0);
+    let octagon = polygon(8, 200.0);
+    let big_octagon = polygon(8, 300.0);
+    let ring = svg_ring(1000, 7.0);
+    let other_ring = svg_ring(1000, 11.0);
+    let small_ring = svg_ring(600, 5.0);
+
+    bench_pair(c, "triangle to octagon", &triangle, &octagon);
+    bench_pair(c, "octagon to triangle", &octagon, &triangle);
+    bench_pair(c, "octagon to octagon", &octagon, &big_octagon);
+    bench_pair(c, "1k svg to 1k svg", &ring, &other_ring);
+    bench_pair(c, "600 svg to 1k svg", &small_ring, &ring);
+    bench_pair(c, "1k svg to 600 svg", &ring, &small_ring);
+}
+
+criterion_group!(benches, lerp_benchmarks);
+criterion_main!(benches);
@@ -0,0 +1,51 @@
+// Components to insert on or remove from a shape once its morph finishes, for the usual
+// spawning/despawning state machines without a LerpBatchFinished handler for each:
+//   AfterLerp::default().insert(Clickable).remove::<Spawning>()
+// The AfterLerp itself is removed first, so one it inserts waits for the next morph to finish.
+use crate::LerpBatchFinished;
+use bevy::ecs::system::EntityCommands;
+use bevy::prelude::*;
+use std::mem;
+
+type Change = Box<dyn FnOnce(&mut EntityCommands) + Send + Sync>;
+
+#[derive(Component, Default)]
+pub struct AfterLerp {
+    changes: Vec<Change>,
+}
+
+impl AfterLerp {
+    pub fn insert<C: Component>(mut self, component: C) -> Self {
+        self.changes.push(Box::new(move |entity| {
+            entity.insert(component);
+        }));
+        self
+    }
+
+    pub fn remove<C: Component>(mut self) -> Self {
+        self.changes.push(Box::new(|entity| {
+            entity.remove::<C>();
+        }));
+        self
+    }
+}
+
+pub fn apply_after_lerp(
+    mut commands: Commands,
+    mut lerp_events: EventReader<LerpBatchFinished>,
+    mut shapes: Query<&mut AfterLerp>,
+) {
+    for LerpBatchFinished(entities) in lerp_events.iter() {
+        for &entity in entities {
+            let changes = match shapes.get_mut(entity) {
+                Ok(mut after) => mem::take(&mut after.changes),
+                Err(_) => continue,
+            };
+            let mut entity = commands.entity(entity);
+            entity.remove::<AfterLerp>();
+            for change in changes {
+                change(&mut entity);
+            }
+        }
+    }
+}
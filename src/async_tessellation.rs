@@ -0,0 +1,341 @@
+use crate::LerpingShape;
+use bevy::prelude::*;
+use bevy::render::{mesh::Indices, pipeline::PrimitiveTopology};
+use bevy::tasks::{AsyncComputeTaskPool, Task};
+use bevy_prototype_lyon::entity::Path as PathComponent;
+use bevy_prototype_lyon::prelude::*;
+use futures_lite::future;
+use std::mem::{self, Discriminant};
+use tess::{
+    geom::LineSegment,
+    math::Point,
+    path::{EndpointId, Event, Path, PathEvent},
+    BuffersBuilder, FillTessellator, FillVertex, StrokeTessellator, StrokeVertex, VertexBuffers,
+    VertexSource,
+};
+
+// Tessellates a shape's path on the async compute pool instead of in ShapePlugin's stage.
+// Holds the shape's DrawMode in place of the component, which is what keeps ShapePlugin away
+// from it. The last finished mesh stays on screen until the next one is ready.
+#[derive(Component)]
+pub struct AsyncTessellation {
+    mode: DrawMode,
+    task: Option<Task<(Mesh, Option<FillLayout>)>>,
+    // The path or mode has changed since the task in flight (if any) was started
+    dirty: bool,
+    // From the last full tessellation, if the current mesh can be moved along with the path
+    layout: Option<FillLayout>,
+}
+
+impl AsyncTessellation {
+    fn new(mode: DrawMode) -> Self {
+        Self {
+            mode,
+            task: None,
+            dirty: true,
+            layout: None,
+        }
+    }
+
+    pub fn mode(&self) -> &DrawMode {
+        &self.mode
+    }
+
+    pub fn set_mode(&mut self, mode: DrawMode) {
+        self.mode = mode;
+        self.dirty = true;
+        self.layout = None;
+    }
+}
+
+// The shape's DrawMode, wherever it is
+pub fn set_mode(
+    mode: Option<Mut<DrawMode>>,
+    tessellation: Option<Mut<AsyncTessellation>>,
+    new: DrawMode,
+) {
+    if let Some(mut mode) = mode {
+        *mode = new;
+    } else if let Some(mut tessellation) = tessellation {
+        tessellation.set_mode(new);
+    }
+}
+
+// Takes over each shape as it's spawned, whenever that is
+pub fn start_async_tessellation(
+    mut commands: Commands,
+    shapes: Query<
+        (Entity, &DrawMode),
+        (
+            With<LerpingShape>,
+            Or<(Added<LerpingShape>, Added<PathComponent>)>,
+        ),
+    >,
+) {
+    for (entity, mode) in shapes.iter() {
+        commands
+            .entity(entity)
+            .remove::<DrawMode>()
+            .insert(AsyncTessellation::new(*mode));
+    }
+}
+
+// At most one task per shape is in flight, a path that changes while it runs is picked up
+// once it's done, so slow tessellations skip frames rather than queueing up.
+// Where the last mesh's layout still fits the path, it's moved in place without a task at all.
+pub fn tessellate_async(
+    pool: Res<AsyncComputeTaskPool>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut shapes: Query<(
+        &PathComponent,
+        ChangeTrackers<PathComponent>,
+        &mut AsyncTessellation,
+        &mut Handle<Mesh>,
+    )>,
+) {
+    for (path, path_tracker, mut tessellation, mut mesh) in shapes.iter_mut() {
+        let tessellation = &mut *tessellation;
+        tessellation.dirty |= path_tracker.is_changed();
+        if let Some(task) = &mut tessellation.task {
+            if let Some((finished, layout)) = future::block_on(future::poll_once(task)) {
+                *mesh = meshes.add(finished);
+                tessellation.task = None;
+                tessellation.layout = layout;
+            }
+        }
+        if tessellation.task.is_some() || !tessellation.dirty {
+            continue;
+        }
+        let moved = match (&tessellation.layout, meshes.get_mut(&*mesh)) {
+            (Some(layout), Some(mesh)) => layout.move_mesh(&path.0, &tessellation.mode, mesh),
+            _ => false,
+        };
+        if moved {
+            tessellation.dirty = false;
+        } else {
+            let (path, mode) = (path.0.clone(), tessellation.mode);
+            tessellation.task = Some(pool.spawn(async move { tessellate(&path, &mode) }));
+            tessellation.dirty = false;
+            tessellation.layout = None;
+        }
+    }
+}
+
+// Where a fill vertex came from, so it can follow the path as it moves
+#[derive(Clone, Copy)]
+enum Source {
+    Endpoint(EndpointId),
+    Edge {
+        from: EndpointId,
+        to: EndpointId,
+        t: f32,
+    },
+}
+
+impl Source {
+    fn position(self, path: &Path) -> Point {
+        match self {
+            Self::Endpoint(id) => path[id],
+            Self::Edge { from, to, t } => LineSegment {
+                from: path[from],
+                to: path[to],
+            }
+            .sample(t),
+        }
+    }
+}
+
+// How a fill of a path made of straight lines was triangulated. Tessellating depends on the
+// geometry, not just the event kinds, so the layout only holds while every triangle keeps
+// its winding; once one flips (or the events change) it takes a full tessellation again.
+// Strokes and curves always take the full tessellation, their vertices don't map back this simply,
+// though an outline over a fill is stroked again on its own while the fill moves in place.
+pub struct FillLayout {
+    verbs: Vec<Discriminant<PathEvent>>,
+    sources: Vec<Source>,
+    indices: Vec<u32>,
+    clockwise: Vec<bool>,
+}
+
+impl FillLayout {
+    fn new(path: &Path, buffers: &VertexBuffers<Vertex, u32>) -> Option<Self> {
+        let curved = path
+            .iter()
+            .any(|event| matches!(event, Event::Quadratic { .. } | Event::Cubic { .. }));
+        if curved {
+            return None;
+        }
+        let sources = buffers
+            .vertices
+            .iter()
+            .map(|vertex| vertex.source)
+            .collect::<Option<Vec<_>>>()?;
+        let positions = buffers
+            .vertices
+            .iter()
+            .map(|vertex| vertex.position.into())
+            .collect::<Vec<Point>>();
+        Some(Self {
+            verbs: path.iter().map(|event| mem::discriminant(&event)).collect(),
+            sources,
+            clockwise: windings(&buffers.indices, &positions)?,
+            indices: buffers.indices.clone(),
+        })
+    }
+
+    // False, leaving the mesh alone, if the layout no longer fits the path
+    pub fn move_mesh(&self, path: &Path, mode: &DrawMode, mesh: &mut Mesh) -> bool {
+        if !path
+            .iter()
+            .map(|event| mem::discriminant(&event))
+            .eq(self.verbs.iter().copied())
+        {
+            return false;
+        }
+        let positions = self
+            .sources
+            .iter()
+            .map(|source| source.position(path))
+            .collect::<Vec<_>>();
+        if windings(&self.indices, &positions).as_ref() != Some(&self.clockwise) {
+            return false;
+        }
+        if let DrawMode::Outlined {
+            fill_mode,
+            outline_mode,
+        } = mode
+        {
+            let color = fill_mode.color.as_linear_rgba_f32();
+            let mut buffers = VertexBuffers {
+                vertices: positions
+                    .iter()
+                    .map(|p| Vertex {
+                        position: p.to_array(),
+                        color,
+                        source: None,
+                    })
+                    .collect(),
+                indices: self.indices.clone(),
+            };
+            stroke(path, outline_mode, &mut buffers);
+            *mesh = build_mesh(&buffers);
+            return true;
+        }
+        mesh.set_attribute(
+            Mesh::ATTRIBUTE_POSITION,
+            positions
+                .iter()
+                .map(|p| [p.x, p.y, 0.0])
+                .collect::<Vec<[f32; 3]>>(),
+        );
+        true
+    }
+}
+
+// Which way round each triangle goes, or None if any has collapsed to nothing
+fn windings(indices: &[u32], positions: &[Point]) -> Option<Vec<bool>> {
+    indices
+        .chunks(3)
+        .map(|triangle| {
+            let corner = |i: usize| positions[triangle[i] as usize];
+            let (a, b, c) = (corner(0), corner(1), corner(2));
+            let area = (b - a).cross(c - a);
+            if area == 0.0 {
+                None
+            } else {
+                Some(area < 0.0)
+            }
+        })
+        .collect()
+}
+
+#[derive(Clone, Copy)]
+struct Vertex {
+    position: [f32; 2],
+    color: [f32; 4],
+    // Only known for fill vertices
+    source: Option<Source>,
+}
+
+fn fill_source(vertex: &FillVertex) -> Option<Source> {
+    match vertex.sources().next()? {
+        VertexSource::Endpoint { id } => Some(Source::Endpoint(id)),
+        VertexSource::Edge { from, to, t } => Some(Source::Edge { from, to, t }),
+    }
+}
+
+// Produces the same mesh ShapePlugin would for this path and mode, and the fill layout if it has one
+pub fn tessellate(path: &Path, mode: &DrawMode) -> (Mesh, Option<FillLayout>) {
+    let mut buffers = VertexBuffers::new();
+    let (fill_mode, outline_mode) = match mode {
+        DrawMode::Fill(fill_mode) => (Some(fill_mode), None),
+        DrawMode::Stroke(stroke_mode) => (None, Some(stroke_mode)),
+        DrawMode::Outlined {
+            fill_mode,
+            outline_mode,
+        } => (Some(fill_mode), Some(outline_mode)),
+    };
+    // Laid out before the outline goes on, which comes after the fill in the mesh
+    let layout = fill_mode.and_then(|fill_mode| {
+        fill(path, fill_mode, &mut buffers);
+        FillLayout::new(path, &buffers)
+    });
+    if let Some(outline_mode) = outline_mode {
+        stroke(path, outline_mode, &mut buffers);
+    }
+    (build_mesh(&buffers), layout)
+}
+
+fn fill(path: &Path, mode: &FillMode, buffers: &mut VertexBuffers<Vertex, u32>) {
+    let color = mode.color.as_linear_rgba_f32();
+    let result = FillTessellator::new().tessellate_path(
+        path,
+        &mode.options,
+        &mut BuffersBuilder::new(buffers, |vertex: FillVertex| Vertex {
+            position: vertex.position().to_array(),
+            color,
+            source: fill_source(&vertex),
+        }),
+    );
+    if let Err(e) = result {
+        warn!("Failed to fill a shape: {:?}", e);
+    }
+}
+
+fn stroke(path: &Path, mode: &StrokeMode, buffers: &mut VertexBuffers<Vertex, u32>) {
+    let color = mode.color.as_linear_rgba_f32();
+    let result = StrokeTessellator::new().tessellate_path(
+        path,
+        &mode.options,
+        &mut BuffersBuilder::new(buffers, |vertex: StrokeVertex| Vertex {
+            position: vertex.position().to_array(),
+            color,
+            source: None,
+        }),
+    );
+    if let Err(e) = result {
+        warn!("Failed to stroke a shape: {:?}", e);
+    }
+}
+
+fn build_mesh(buffers: &VertexBuffers<Vertex, u32>) -> Mesh {
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+    mesh.set_indices(Some(Indices::U32(buffers.indices.clone())));
+    mesh.set_attribute(
+        Mesh::ATTRIBUTE_POSITION,
+        buffers
+            .vertices
+            .iter()
+            .map(|v| [v.position[0], v.position[1], 0.0])
+            .collect::<Vec<[f32; 3]>>(),
+    );
+    mesh.set_attribute(
+        Mesh::ATTRIBUTE_COLOR,
+        buffers
+            .vertices
+            .iter()
+            .map(|v| v.color)
+            .collect::<Vec<[f32; 4]>>(),
+    );
+    mesh
+}
@@ -0,0 +1,240 @@
+// Listens to the default input device and reacts to it: each beat steps every shape to its next
+// side count, and the louder it gets the faster shapes morph. In ambient mode the loudness
+// instead blends each shape between two targets.
+use crate::config::Config;
+use crate::control::MorphCommand;
+use crate::path_lerping::{sample_morph, SampleOptions};
+use crate::target::TargetShape;
+use crate::{LerpingShape, SidesChangingShape};
+use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::*;
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use rustfft::{num_complex::Complex, Fft, FftPlanner};
+use std::{
+    collections::VecDeque,
+    f32::consts::PI,
+    ops::RangeInclusive,
+    sync::{Arc, Mutex},
+};
+use tess::path::Path;
+
+const FFT_SIZE: usize = 1024;
+pub const BANDS: usize = 8;
+// Roughly a second of low-band energy at 60 fps, to compare each frame against
+const BEAT_HISTORY: usize = 60;
+// How much louder than the recent average the bass has to be to count as a beat
+const BEAT_THRESHOLD: f32 = 1.5;
+const BEAT_COOLDOWN: f32 = 0.2;
+// At full loudness shapes morph this many times faster than the configured lerp_t
+const LOUDNESS_SPEEDUP: f32 = 4.0;
+
+// Mono samples from the input stream, newest last, never more than FFT_SIZE of them
+#[derive(Clone, Default)]
+pub struct AudioSamples(Arc<Mutex<VecDeque<f32>>>);
+
+impl AudioSamples {
+    fn push(&self, samples: impl Iterator<Item = f32>) {
+        let mut buffer = self.0.lock().unwrap();
+        buffer.extend(samples);
+        let excess = buffer.len().saturating_sub(FFT_SIZE);
+        buffer.drain(..excess);
+    }
+}
+
+// Dropping the stream stops recording, and it isn't Send on every platform
+pub struct AudioStream(cpal::Stream);
+
+pub fn record_default_input() -> Result<(AudioStream, AudioSamples), String> {
+    let device = cpal::default_host()
+        .default_input_device()
+        .ok_or_else(|| "No audio input device".to_owned())?;
+    let config = device.default_input_config().map_err(|e| e.to_string())?;
+    let samples = AudioSamples::default();
+    let stream = match config.sample_format() {
+        cpal::SampleFormat::F32 => build_stream::<f32>(&device, &config.into(), samples.clone()),
+        cpal::SampleFormat::I16 => build_stream::<i16>(&device, &config.into(), samples.clone()),
+        cpal::SampleFormat::U16 => build_stream::<u16>(&device, &config.into(), samples.clone()),
+    }?;
+    stream.play().map_err(|e| e.to_string())?;
+    Ok((AudioStream(stream), samples))
+}
+
+fn build_stream<T: cpal::Sample>(
+    device: &cpal::Device,
+    config: &cpal::StreamConfig,
+    samples: AudioSamples,
+) -> Result<cpal::Stream, String> {
+    let channels = config.channels as usize;
+    device
+        .build_input_stream(
+            config,
+            move |data: &[T], _: &cpal::InputCallbackInfo| {
+                samples.push(
+                    data.chunks(channels).map(|frame| {
+                        frame.iter().map(|s| s.to_f32()).sum::<f32>() / channels as f32
+                    }),
+                );
+            },
+            |e| error!("Audio input failed: {}", e),
+        )
+        .map_err(|e| e.to_string())
+}
+
+pub struct AudioAnalysis {
+    // Normalized magnitude of logarithmically spaced frequency bands, lowest first
+    pub bands: [f32; BANDS],
+    // RMS of the latest window, 0 is silence and 1 is full scale
+    pub loudness: f32,
+    pub beat: bool,
+    fft: Arc<dyn Fft<f32>>,
+    bass_history: VecDeque<f32>,
+    since_beat: f32,
+}
+
+impl Default for AudioAnalysis {
+    fn default() -> Self {
+        Self {
+            bands: [0.0; BANDS],
+            loudness: 0.0,
+            beat: false,
+            fft: FftPlanner::new().plan_fft_forward(FFT_SIZE),
+            bass_history: VecDeque::with_capacity(BEAT_HISTORY),
+            since_beat: BEAT_COOLDOWN,
+        }
+    }
+}
+
+pub fn analyze_audio(
+    time: Res<Time>,
+    samples: Res<AudioSamples>,
+    mut analysis: ResMut<AudioAnalysis>,
+) {
+    let mut buffer = {
+        let samples = samples.0.lock().unwrap();
+        if samples.len() < FFT_SIZE {
+            return;
+        }
+        // Hann window, to keep the edges of the window from smearing into every band
+        samples
+            .iter()
+            .enumerate()
+            .map(|(i, s)| {
+                let window = 0.5 - 0.5 * (2.0 * PI * i as f32 / (FFT_SIZE - 1) as f32).cos();
+                Complex::new(s * window, 0.0)
+            })
+            .collect::<Vec<_>>()
+    };
+    analysis.loudness = (buffer.iter().map(|c| c.re * c.re).sum::<f32>() / FFT_SIZE as f32)
+        .sqrt()
+        .min(1.0);
+    analysis.fft.process(&mut buffer);
+
+    // Band i covers bins 2^i..2^(i+1), skipping the DC bin
+    let half = &buffer[..FFT_SIZE / 2];
+    for (i, band) in analysis.bands.iter_mut().enumerate() {
+        let (start, end) = (1 << i, (2 << i).min(half.len()));
+        *band = half[start..end].iter().map(|c| c.norm()).sum::<f32>()
+            / ((end - start) as f32 * FFT_SIZE as f32).sqrt();
+    }
+
+    let bass = analysis.bands[0] + analysis.bands[1];
+    let average =
+        analysis.bass_history.iter().sum::<f32>() / analysis.bass_history.len().max(1) as f32;
+    analysis.since_beat += time.delta_seconds();
+    analysis.beat = analysis.bass_history.len() == BEAT_HISTORY
+        && bass > average * BEAT_THRESHOLD
+        && analysis.since_beat >= BEAT_COOLDOWN;
+    if analysis.beat {
+        analysis.since_beat = 0.0;
+    }
+    if analysis.bass_history.len() == BEAT_HISTORY {
+        analysis.bass_history.pop_front();
+    }
+    analysis.bass_history.push_back(bass);
+}
+
+pub fn react_to_audio(
+    analysis: Res<AudioAnalysis>,
+    config: Res<Config>,
+    mut commands: EventWriter<MorphCommand>,
+) {
+    if analysis.beat {
+        commands.send(MorphCommand::StepSides { entity: None });
+    }
+    commands.send(MorphCommand::SetLerpT {
+        entity: None,
+        lerp_t: config.lerp_t * (1.0 + analysis.loudness * LOUDNESS_SPEEDUP),
+    });
+}
+
+// Typical microphone levels are far below full scale, so they're boosted before blending
+const AMPLITUDE_GAIN: f32 = 4.0;
+// Fraction of the way the blend moves towards the current level each frame
+const AMPLITUDE_SMOOTHING: f32 = 0.1;
+// How far the blend has to move before the target's rebuilt. Every new target restarts the
+// morph toward it, so one a frame for the slightest flicker never lets the shape settle.
+const AMPLITUDE_EPSILON: f32 = 0.01;
+
+// Retargets the shape every frame to a blend between two shapes, weighted by how loud the input is
+#[derive(Component)]
+pub struct AmplitudeBlend {
+    pub quiet: Path,
+    pub loud: Path,
+    level: f32,
+    // What `level` was when the target was last blended, None until the first time
+    blended: Option<f32>,
+}
+
+impl AmplitudeBlend {
+    pub fn new(quiet: Path, loud: Path) -> Self {
+        Self {
+            quiet,
+            loud,
+            level: 0.0,
+            blended: None,
+        }
+    }
+}
+
+// Ambient mode swaps side stepping for blending between the configured bounds' smallest and largest shapes,
+// for each shape as it comes out
+pub fn start_ambient_mode(
+    mut commands: Commands,
+    shapes: Query<
+        (Entity, &SidesChangingShape<RangeInclusive<u8>>),
+        (
+            With<LerpingShape>,
+            Added<SidesChangingShape<RangeInclusive<u8>>>,
+        ),
+    >,
+) {
+    for (entity, sides) in shapes.iter() {
+        let (min, max) = sides.bounds.clone().into_inner();
+        commands
+            .entity(entity)
+            .remove::<SidesChangingShape<RangeInclusive<u8>>>()
+            .insert(AmplitudeBlend::new(
+                TargetShape::for_sides(min, sides.radius).build(),
+                TargetShape::for_sides(max, sides.radius).build(),
+            ));
+    }
+}
+
+pub fn blend_by_amplitude(
+    analysis: Res<AudioAnalysis>,
+    mut shapes: Query<(&mut AmplitudeBlend, &mut LerpingShape)>,
+) {
+    let level = (analysis.loudness * AMPLITUDE_GAIN).min(1.0);
+    for (mut blend, mut shape) in shapes.iter_mut() {
+        blend.level += (level - blend.level) * AMPLITUDE_SMOOTHING;
+        let smoothed = blend.level;
+        if let Some(blended) = blend.blended {
+            if (smoothed - blended).abs() <= AMPLITUDE_EPSILON {
+                continue;
+            }
+        }
+        blend.blended = Some(smoothed);
+        let options = SampleOptions::default();
+        shape.target = sample_morph(&blend.quiet, &blend.loud, smoothed, options);
+    }
+}
@@ -0,0 +1,263 @@
+// Union, intersection and difference of the areas closed paths enclose, for building targets like
+// a polygon with a circle cut out of it. Curves are flattened to within `tolerance` first, so the
+// results are polygons, and open subpaths count as if they were closed.
+use crate::metrics::edges_area;
+use bevy_prototype_lyon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use tess::{
+    math::Point,
+    path::{iterator::PathIterator, Event, Path},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BooleanOp {
+    Union,
+    Intersection,
+    // What's left of the first path with the second cut out of it
+    Difference,
+}
+
+// Points closer than this count as the same point
+const EPSILON: f32 = 1e-3;
+
+type Edge = (Point, Point);
+
+// Each subpath's points, without the closing point repeating the first. Subpaths enclosing
+// nothing are left out.
+fn rings(path: &Path, tolerance: f32) -> Vec<Vec<Point>> {
+    let mut rings = Vec::new();
+    let mut ring = Vec::new();
+    for event in path.iter().flattened(tolerance) {
+        match event {
+            Event::Begin { at } => ring.push(at),
+            // Repeated points would make for edges with no direction
+            Event::Line { to, .. } => {
+                if ring
+                    .last()
+                    .map_or(true, |last: &Point| last.distance_to(to) > EPSILON)
+                {
+                    ring.push(to);
+                }
+            }
+            Event::End { first, .. } => {
+                if ring.len() > 1 && ring[ring.len() - 1].distance_to(first) <= EPSILON {
+                    ring.pop();
+                }
+                if ring.len() >= 3 {
+                    rings.push(std::mem::take(&mut ring));
+                }
+                ring.clear();
+            }
+            _ => {}
+        }
+    }
+    rings
+}
+
+fn ring_edges(ring: &[Point]) -> impl Iterator<Item = Edge> + '_ {
+    ring.iter()
+        .zip(ring.iter().cycle().skip(1))
+        .map(|(&from, &to)| (from, to))
+}
+
+// Even-odd, so holes count as outside
+fn contains(edges: impl IntoIterator<Item = Edge>, p: Point) -> bool {
+    edges
+        .into_iter()
+        .filter(|&(from, to)| {
+            (from.y > p.y) != (to.y > p.y)
+                && p.x < from.x + (p.y - from.y) / (to.y - from.y) * (to.x - from.x)
+        })
+        .count()
+        % 2
+        == 1
+}
+
+// Outlines anticlockwise and the holes in them clockwise, however they were drawn, so that the
+// edges kept from each path below line up head to tail
+fn normalized(mut rings: Vec<Vec<Point>>) -> Vec<Vec<Point>> {
+    let depths = rings
+        .iter()
+        .enumerate()
+        .map(|(i, ring)| {
+            rings
+                .iter()
+                .enumerate()
+                .filter(|&(j, other)| j != i && contains(ring_edges(other), ring[0]))
+                .count()
+        })
+        .collect::<Vec<_>>();
+    for (ring, depth) in rings.iter_mut().zip(depths) {
+        if (edges_area(ring_edges(ring)) > 0.0) != (depth % 2 == 0) {
+            ring.reverse();
+        }
+    }
+    rings
+}
+
+// Each subpath of the flattened path as a ring of points, outlines anticlockwise and the holes in
+// them clockwise
+pub fn outlines(path: &Path, tolerance: f32) -> Vec<Vec<Point>> {
+    normalized(rings(path, tolerance))
+}
+
+// The points along each edge where the other path's edges cross or touch it, as a fraction of
+// the way along and the point itself. Both sides of a crossing get the very same point, and
+// crossings at an end are snapped to it, so the pieces meet exactly.
+fn splits(a: &[Edge], b: &[Edge]) -> (Vec<Vec<(f32, Point)>>, Vec<Vec<(f32, Point)>>) {
+    let mut a_splits = vec![Vec::new(); a.len()];
+    let mut b_splits = vec![Vec::new(); b.len()];
+    for (i, &(p, q)) in a.iter().enumerate() {
+        let r = q - p;
+        let r_epsilon = EPSILON / r.length();
+        for (j, &(c, d)) in b.iter().enumerate() {
+            let s = d - c;
+            let s_epsilon = EPSILON / s.length();
+            let denominator = r.cross(s);
+            let interior = |t: f32, epsilon: f32| t > epsilon && t < 1.0 - epsilon;
+            if denominator.abs() <= 1e-6 * r.length() * s.length() {
+                // Parallel, so they only touch if they overlap
+                if r.cross(c - p).abs() > EPSILON * r.length() {
+                    continue;
+                }
+                for &x in &[c, d] {
+                    let t = (x - p).dot(r) / r.square_length();
+                    if interior(t, r_epsilon) {
+                        a_splits[i].push((t, x));
+                    }
+                }
+                for &x in &[p, q] {
+                    let u = (x - c).dot(s) / s.square_length();
+                    if interior(u, s_epsilon) {
+                        b_splits[j].push((u, x));
+                    }
+                }
+                continue;
+            }
+            let t = (c - p).cross(s) / denominator;
+            let u = (c - p).cross(r) / denominator;
+            if t < -r_epsilon || t > 1.0 + r_epsilon || u < -s_epsilon || u > 1.0 + s_epsilon {
+                continue;
+            }
+            let at = if t <= r_epsilon {
+                p
+            } else if t >= 1.0 - r_epsilon {
+                q
+            } else if u <= s_epsilon {
+                c
+            } else if u >= 1.0 - s_epsilon {
+                d
+            } else {
+                p + r * t
+            };
+            if interior(t, r_epsilon) {
+                a_splits[i].push((t, at));
+            }
+            if interior(u, s_epsilon) {
+                b_splits[j].push((u, at));
+            }
+        }
+    }
+    (a_splits, b_splits)
+}
+
+// The edges cut up at their splits
+fn pieces(edges: &[Edge], splits: Vec<Vec<(f32, Point)>>) -> Vec<Edge> {
+    let mut pieces = Vec::new();
+    for (&(from, to), mut splits) in edges.iter().zip(splits) {
+        splits.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+        let mut last = from;
+        for (_, at) in splits.into_iter().chain(std::iter::once((1.0, to))) {
+            if at.distance_to(last) > EPSILON {
+                pieces.push((last, at));
+                last = at;
+            }
+        }
+    }
+    pieces
+}
+
+// Whether the piece runs along one of the edges, and if so whether it runs the same way
+fn along(edges: &[Edge], (from, to): Edge) -> Option<bool> {
+    let direction = to - from;
+    let middle = from.lerp(to, 0.5);
+    edges.iter().find_map(|&(c, d)| {
+        let s = d - c;
+        let length = s.length();
+        let t = (middle - c).dot(s) / s.square_length();
+        let parallel = direction.cross(s).abs() <= EPSILON * direction.length() * length;
+        let on_edge = (middle - c).cross(s).abs() <= EPSILON * length;
+        (length > 0.0 && parallel && on_edge && t > 0.0 && t < 1.0).then(|| direction.dot(s) > 0.0)
+    })
+}
+
+// Follows the pieces head to tail back round to where each ring started
+pub fn chained(mut pieces: Vec<Edge>) -> Vec<Vec<Point>> {
+    let mut rings = Vec::new();
+    while let Some((start, mut end)) = pieces.pop() {
+        let mut ring = vec![start];
+        while end.distance_to(start) > EPSILON {
+            let next = pieces
+                .iter()
+                .position(|&(from, _)| from.distance_to(end) <= EPSILON);
+            let next = match next {
+                Some(next) => next,
+                None => break,
+            };
+            ring.push(end);
+            end = pieces.swap_remove(next).1;
+        }
+        if ring.len() >= 3 {
+            rings.push(ring);
+        }
+    }
+    rings
+}
+
+pub fn combined(a: &Path, b: &Path, op: BooleanOp, tolerance: f32) -> Path {
+    let edges = |path: &Path| {
+        outlines(path, tolerance)
+            .iter()
+            .flat_map(|ring| ring_edges(ring).collect::<Vec<_>>())
+            .collect::<Vec<_>>()
+    };
+    let (a, b) = (edges(a), edges(b));
+    let (a_splits, b_splits) = splits(&a, &b);
+
+    let mut kept = Vec::new();
+    // Where the outlines coincide the edge is kept once, from the first path
+    for piece in pieces(&a, a_splits) {
+        let keep = match along(&b, piece) {
+            Some(same_way) => same_way == (op != BooleanOp::Difference),
+            None => {
+                contains(b.iter().copied(), piece.0.lerp(piece.1, 0.5))
+                    == (op == BooleanOp::Intersection)
+            }
+        };
+        if keep {
+            kept.push(piece);
+        }
+    }
+    for piece in pieces(&b, b_splits) {
+        if along(&a, piece).is_some() {
+            continue;
+        }
+        match (op, contains(a.iter().copied(), piece.0.lerp(piece.1, 0.5))) {
+            (BooleanOp::Union, false) | (BooleanOp::Intersection, true) => kept.push(piece),
+            // Turned round, as the inside of the cut is the outside of what's left
+            (BooleanOp::Difference, true) => kept.push((piece.1, piece.0)),
+            _ => {}
+        }
+    }
+
+    let mut builder = Path::builder();
+    for ring in chained(kept) {
+        builder.begin(ring[0]);
+        for &point in &ring[1..] {
+            builder.line_to(point);
+        }
+        builder.end(true);
+    }
+    builder.build()
+}
@@ -0,0 +1,61 @@
+// One-off callbacks for when a particular shape's morph finishes, instead of watching every
+// LerpBatchFinished for it:
+//   callbacks.on_complete(shape, |world, shape| {
+//       world.entity_mut(shape).insert(Highlighted);
+//   });
+// Each callback runs once, with the world, at the end of the frame the shape snaps on.
+// Callbacks for shapes that are despawned first are dropped.
+use crate::LerpBatchFinished;
+use bevy::app::{Events, ManualEventReader};
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+
+type Callback = Box<dyn FnOnce(&mut World, Entity) + Send + Sync>;
+
+#[derive(Default)]
+pub struct MorphCallbacks {
+    callbacks: HashMap<Entity, Vec<Callback>>,
+    reader: ManualEventReader<LerpBatchFinished>,
+}
+
+impl MorphCallbacks {
+    pub fn on_complete(
+        &mut self,
+        entity: Entity,
+        callback: impl FnOnce(&mut World, Entity) + Send + Sync + 'static,
+    ) {
+        self.callbacks
+            .entry(entity)
+            .or_default()
+            .push(Box::new(callback));
+    }
+}
+
+pub fn run_completion_callbacks(world: &mut World) {
+    let due = world.resource_scope(|world, mut callbacks: Mut<MorphCallbacks>| {
+        let events = world.get_resource::<Events<LerpBatchFinished>>().unwrap();
+        let finished = callbacks
+            .reader
+            .iter(events)
+            .flat_map(|LerpBatchFinished(entities)| entities.clone())
+            .collect::<Vec<_>>();
+        let mut due = Vec::new();
+        for entity in finished {
+            if let Some(entity_callbacks) = callbacks.callbacks.remove(&entity) {
+                due.extend(
+                    entity_callbacks
+                        .into_iter()
+                        .map(|callback| (entity, callback)),
+                );
+            }
+        }
+        callbacks
+            .callbacks
+            .retain(|&entity, _| world.get_entity(entity).is_some());
+        due
+    });
+    // Run outside the scope, so callbacks can register more callbacks
+    for (entity, callback) in due {
+        callback(world, entity);
+    }
+}
@@ -0,0 +1,141 @@
+use crate::demo_config::DemoConfig;
+use clap::Parser;
+use shape_lerping::config::{Config, CONFIG_PATH};
+use shape_lerping::margin::MarginSpace;
+use shape_lerping::visibility::OffscreenPolicy;
+#[cfg(any(feature = "osc", feature = "net", feature = "websocket"))]
+use std::net::SocketAddr;
+use std::path::PathBuf;
+
+#[derive(Parser)]
+#[clap(about = "Morphs shapes into each other by lerping their paths")]
+pub struct Args {
+    /// Scene config to load, missing fields fall back to defaults
+    #[clap(long, value_name = "FILE", default_value = CONFIG_PATH)]
+    pub config: PathBuf,
+    #[clap(long)]
+    pub sides_min: Option<u8>,
+    #[clap(long)]
+    pub sides_max: Option<u8>,
+    #[clap(long)]
+    pub lerp_t: Option<f32>,
+    /// Take this many seconds over each morph instead of going by lerp_t each frame
+    #[clap(long, value_name = "SECONDS")]
+    pub morph_seconds: Option<f32>,
+    #[clap(long)]
+    pub radius: Option<f32>,
+    /// Run without a window or renderer
+    #[clap(long)]
+    pub headless: bool,
+    /// Tessellate changed shapes in the background, showing the previous mesh until each is ready
+    #[clap(long)]
+    pub async_tessellation: bool,
+    /// Also spawn the shapes of this library (a .shapes.ron file under assets/) once it's loaded
+    #[clap(long, value_name = "FILE")]
+    pub library: Option<PathBuf>,
+    /// Play the morphs planned in this recipe file (RON, or JSON if it ends in .json) instead of
+    /// spawning the config's shapes; its slideshow, if any, steps with the arrow keys
+    #[clap(long, value_name = "FILE")]
+    pub recipe: Option<PathBuf>,
+    /// Morph two shapes side by side in step, one lerping the paths' events and the other
+    /// points resampled evenly along them, instead of spawning the config's shapes
+    #[clap(long, conflicts_with = "recipe")]
+    pub compare: bool,
+    /// Write every frame into this directory as an SVG
+    #[clap(long, value_name = "DIR")]
+    pub export: Option<PathBuf>,
+    /// Rhai script to drive the morphs, see `scripting.rs` for the functions it can call
+    #[cfg(feature = "scripting")]
+    #[clap(long, value_name = "FILE")]
+    pub script: Option<PathBuf>,
+    /// Listen for OSC remote control messages on this address, e.g. 0.0.0.0:9000
+    #[cfg(feature = "osc")]
+    #[clap(long, value_name = "ADDR")]
+    pub osc: Option<SocketAddr>,
+    /// Play the shapes from a MIDI input
+    #[cfg(feature = "midi")]
+    #[clap(long)]
+    pub midi: bool,
+    /// Use the first MIDI input whose name contains this, instead of the first one found
+    #[cfg(feature = "midi")]
+    #[clap(long, value_name = "NAME", requires = "midi")]
+    pub midi_port: Option<String>,
+    /// Morph to the beat of the default audio input
+    #[cfg(feature = "audio")]
+    #[clap(long, conflicts_with = "deterministic")]
+    pub audio: bool,
+    /// Blend each shape between its smallest and largest side count by input loudness, instead of following beats
+    #[cfg(feature = "audio")]
+    #[clap(long, requires = "audio")]
+    pub ambient: bool,
+    /// Own every shape's targets and send them to clients connecting on this address
+    #[cfg(feature = "net")]
+    #[clap(long, value_name = "ADDR")]
+    pub serve: Option<SocketAddr>,
+    /// Follow the shapes of the server on this address instead of changing them locally
+    #[cfg(feature = "net")]
+    #[clap(long, value_name = "ADDR", conflicts_with = "serve")]
+    pub connect: Option<SocketAddr>,
+    /// Accept JSON remote control commands over WebSocket on this address
+    #[cfg(feature = "websocket")]
+    #[clap(long, value_name = "ADDR")]
+    pub websocket: Option<SocketAddr>,
+    /// Record every target and lerp parameter change into this file
+    #[clap(long, value_name = "FILE")]
+    pub record: Option<PathBuf>,
+    /// Play back a recording instead of changing shapes locally
+    #[clap(long, value_name = "FILE", conflicts_with = "record")]
+    pub replay: Option<PathBuf>,
+    /// Spread the shapes' lerping over several frames once their paths add up to more events than this
+    #[clap(long, value_name = "EVENTS")]
+    pub max_events_per_frame: Option<usize>,
+    /// Morph identically on every run, turning off config reloading, levels of detail, offscreen
+    /// policies, on-screen margins and the screensaver, stepping anything that goes by time a
+    /// 60th of a second each frame, and seeding from 0 if the config doesn't have a seed; with
+    /// --frames, prints a checksum of the paths on exit
+    // The lerping only uses exactly rounded arithmetic, so it's the same across platforms too.
+    // Targets built with trigonometry (polygons, ellipses, SVG arcs) use the platform's maths
+    // library, but recordings and servers send targets as data. When a library finishes loading
+    // is up to the asset server, so it can't be used alongside.
+    #[clap(long, conflicts_with = "library")]
+    pub deterministic: bool,
+    /// Open a second window showing the shapes' targets
+    #[clap(long)]
+    pub preview: bool,
+    /// Label every point with its t and distance to go, and step single frames with . while paused
+    #[clap(long)]
+    pub teach: bool,
+    /// Show a button whose morphing background changes shape as it's hovered and pressed
+    #[clap(long)]
+    pub ui: bool,
+    /// Log frame times and morph diagnostics to the console every second
+    #[clap(long)]
+    pub diagnostics: bool,
+    /// Exit after this many frames
+    #[clap(long)]
+    pub frames: Option<u64>,
+}
+
+impl Args {
+    // Flags take precedence over the config file
+    pub fn apply(&self, config: &mut Config, demo: &mut DemoConfig) {
+        let (min, max) = config.bounds.clone().into_inner();
+        config.bounds = self.sides_min.unwrap_or(min)..=self.sides_max.unwrap_or(max);
+        if let Some(lerp_t) = self.lerp_t {
+            config.lerp_t = lerp_t;
+        }
+        if let Some(radius) = self.radius {
+            config.radius = radius;
+        }
+        if let Some(morph_seconds) = self.morph_seconds {
+            config.morph_seconds = morph_seconds;
+        }
+        if self.deterministic {
+            config.lod_threshold = 0.0;
+            config.offscreen = OffscreenPolicy::Morph;
+            config.margin_space = MarginSpace::World;
+            demo.idle_seconds = 0.0;
+            config.seed.get_or_insert(0);
+        }
+    }
+}
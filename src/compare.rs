@@ -0,0 +1,100 @@
+// Morphs the same shapes side by side with two interpolation strategies, both sampled at the
+// same t each frame, so changes to how paths are lerped can be judged by eye. On the left the
+// paths' events are lined up and lerped pairwise, as `lerp_shape` does; on the right both paths
+// are resampled into evenly spaced points along their outlines first.
+use crate::demo_config::DemoConfig;
+use bevy::prelude::*;
+use bevy_prototype_lyon::entity::Path as PathComponent;
+use bevy_prototype_lyon::prelude::*;
+use shape_lerping::path_lerping::{sample_morph, sample_resampled, SampleOptions};
+use shape_lerping::target::TargetShape;
+use shape_lerping::{config::Config, frame_delta, shape_bundle, FixedDelta, Paused, ShapeId};
+use tess::path::Path;
+
+// How long each morph takes
+const MORPH_SECONDS: f32 = 2.0;
+const RESAMPLED_POINTS: usize = 64;
+const TOLERANCE: f32 = 0.1;
+
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Strategy {
+    Events,
+    Resampled,
+}
+
+// The morph both sides are partway through
+pub struct Comparison {
+    sides: u8,
+    from: Path,
+    to: Path,
+    elapsed: f32,
+}
+
+// Steps through the config's bounds, wrapping around at the top
+fn next_sides(sides: u8, config: &Config) -> u8 {
+    let (min, max) = (*config.bounds.start(), *config.bounds.end());
+    if sides >= max || sides < min {
+        min
+    } else {
+        sides + 1
+    }
+}
+
+pub fn spawn_comparison(mut commands: Commands, config: Res<Config>, demo: Res<DemoConfig>) {
+    let from = TargetShape::for_sides(config.sides, config.radius).build();
+    let sides = next_sides(config.sides, &config);
+    let to = TargetShape::for_sides(sides, config.radius).build();
+    let strategies = [Strategy::Events, Strategy::Resampled];
+    for (i, &strategy) in strategies.iter().enumerate() {
+        let x = (i as f32 - 0.5) * demo.spacing;
+        commands
+            .spawn_bundle(shape_bundle(&config, Vec3::new(x, 0.0, 0.0), &from))
+            .insert(ShapeId(i as u32))
+            .insert(strategy);
+    }
+    commands.insert_resource(Comparison {
+        sides,
+        from,
+        to,
+        elapsed: 0.0,
+    });
+}
+
+pub fn compare_strategies(
+    time: Res<Time>,
+    fixed_delta: Option<Res<FixedDelta>>,
+    config: Res<Config>,
+    paused: Res<Paused>,
+    mut comparison: ResMut<Comparison>,
+    mut shapes: Query<(&mut PathComponent, &Strategy)>,
+) {
+    if paused.0 {
+        return;
+    }
+    comparison.elapsed += frame_delta(&time, fixed_delta.as_deref()).as_secs_f32();
+    let t = (comparison.elapsed / MORPH_SECONDS).min(1.0);
+    for (mut path, strategy) in shapes.iter_mut() {
+        path.0 = match strategy {
+            Strategy::Events => sample_morph(
+                &comparison.from,
+                &comparison.to,
+                t,
+                SampleOptions::default(),
+            ),
+            Strategy::Resampled => sample_resampled(
+                &comparison.from,
+                &comparison.to,
+                t,
+                RESAMPLED_POINTS,
+                TOLERANCE,
+            ),
+        };
+    }
+    if t >= 1.0 {
+        let sides = next_sides(comparison.sides, &config);
+        let to = TargetShape::for_sides(sides, config.radius).build();
+        comparison.from = std::mem::replace(&mut comparison.to, to);
+        comparison.sides = sides;
+        comparison.elapsed = 0.0;
+    }
+}
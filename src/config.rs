@@ -0,0 +1,196 @@
+use crate::easing::Easing;
+use crate::fit::TargetFit;
+use crate::margin::MarginSpace;
+use crate::origin::{LerpMode, RetargetPolicy};
+use crate::spring::SpringLerp;
+use crate::validation::{NonFinitePolicy, SidesPolicy};
+use crate::visibility::OffscreenPolicy;
+use crate::{LerpingShape, SameTargetPolicy, SidesChangingShape};
+use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::{FillOptions, StrokeOptions};
+use serde::{de::DeserializeOwned, Deserialize};
+use std::{fs, io, ops::RangeInclusive, path::Path, time::Duration};
+
+pub const CONFIG_PATH: &str = "config.ron";
+
+const MAX_MORPH_SECONDS: f32 = 24.0 * 60.0 * 60.0;
+
+// Startup scene parameters, any field missing from the file falls back to its default, and any
+// the file has that aren't here are skipped, so an app can keep its own settings alongside
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub sides: u8,
+    pub bounds: RangeInclusive<u8>,
+    pub degenerate_sides: SidesPolicy,
+    pub radius: f32,
+    pub fill_color: Color,
+    pub outline_color: Color,
+    pub outline_width: f32,
+    pub fill_tolerance: f32,
+    pub stroke_tolerance: f32,
+    // Used for both while a shape morphs, until it snaps, 0 turns it off
+    pub morphing_tolerance: f32,
+    pub lerp_t: f32,
+    // lerp_t is scaled by this along x and y, for morphs that converge faster one way than the other
+    pub lerp_axes: Vec2,
+    // Only applied at startup, to the shapes from the config
+    pub lerp_mode: LerpMode,
+    // Seconds each morph takes by the frame time, the same at any frame rate, in place of
+    // lerp_t; 0 goes by lerp_t each frame instead
+    pub morph_seconds: f32,
+    // Anything but Linear gives the shapes an origin to ease from, see `LerpingShape::eased`
+    pub easing: Easing,
+    // How shapes spring toward their targets with the Spring lerp mode
+    pub spring: SpringLerp,
+    pub margin_of_error: f32,
+    pub margin_space: MarginSpace,
+    // Added to the margin in the shape's local space, so a margin of 0 still snaps once float
+    // error is all that's left rather than creeping toward the target forever
+    pub snap_epsilon: f32,
+    pub same_target: SameTargetPolicy,
+    // What a new target does to the progress of a morph from an origin
+    pub retarget: RetargetPolicy,
+    // How new targets are placed relative to the shape's current path, None leaves them as they are
+    pub target_fit: Option<TargetFit>,
+    // Moves each point of the targets built from side counts up to this far at random
+    pub target_noise: f32,
+    // For the shapes' random numbers, None seeds them from the time
+    pub seed: Option<u64>,
+    // Shapes smaller than this many pixels on screen morph toward simplified targets, 0 turns it off
+    pub lod_threshold: f32,
+    pub offscreen: OffscreenPolicy,
+    pub non_finite: NonFinitePolicy,
+    // Also look for NaNs in the shapes' paths as they morph, not just in their targets
+    pub check_live_paths: bool,
+    // Frames each shape's echo trails behind it by, 0 for no echoes
+    pub echo_delay: usize,
+    // Concentric outlines around each shape, each offset `ripple_spacing` further out than the
+    // last and following its morphs `ripple_delay` frames later, 0 for no ripples
+    pub ripple_rings: usize,
+    // Negative to ripple inward
+    pub ripple_spacing: f32,
+    pub ripple_delay: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            sides: 5,
+            bounds: 3..=8,
+            degenerate_sides: SidesPolicy::Clamp,
+            radius: 200.0,
+            fill_color: Color::ORANGE,
+            outline_color: Color::ORANGE_RED,
+            outline_width: 8.0,
+            fill_tolerance: FillOptions::DEFAULT_TOLERANCE,
+            stroke_tolerance: StrokeOptions::DEFAULT_TOLERANCE,
+            morphing_tolerance: 0.5,
+            lerp_t: 0.025,
+            lerp_axes: Vec2::ONE,
+            lerp_mode: LerpMode::Feedback,
+            morph_seconds: 0.0,
+            easing: Easing::Linear,
+            spring: SpringLerp::default(),
+            margin_of_error: 1.0,
+            margin_space: MarginSpace::World,
+            snap_epsilon: 1e-4,
+            same_target: SameTargetPolicy::Finish,
+            retarget: RetargetPolicy::Restart,
+            target_fit: None,
+            target_noise: 0.0,
+            seed: None,
+            lod_threshold: 32.0,
+            offscreen: OffscreenPolicy::Morph,
+            non_finite: NonFinitePolicy::Clamp,
+            check_live_paths: false,
+            echo_delay: 0,
+            ripple_rings: 0,
+            ripple_spacing: 24.0,
+            ripple_delay: 8,
+        }
+    }
+}
+
+impl Config {
+    // What each shape's lerp_t starts from
+    pub fn axis_lerp_t(&self) -> Vec2 {
+        self.lerp_axes * self.lerp_t
+    }
+
+    // None for a morph_seconds of 0, or anything that isn't a positive number of seconds.
+    // Capped at a day, which is as good as forever and well clear of what a Duration can hold.
+    pub fn morph_duration(&self) -> Option<Duration> {
+        (self.morph_seconds > 0.0)
+            .then(|| Duration::from_secs_f32(self.morph_seconds.min(MAX_MORPH_SECONDS)))
+    }
+
+    pub fn read(path: impl AsRef<Path>) -> Result<Self, String> {
+        read(path)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Self {
+        load(path)
+    }
+}
+
+// A missing file isn't an error, it just means the defaults are used
+pub fn read<T: DeserializeOwned + Default>(path: impl AsRef<Path>) -> Result<T, String> {
+    let path = path.as_ref();
+    match fs::read_to_string(path) {
+        Ok(contents) => ron::from_str(&contents)
+            .map_err(|e| format!("Failed to parse {}: {}", path.display(), e)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(T::default()),
+        Err(e) => Err(format!("Failed to read {}: {}", path.display(), e)),
+    }
+}
+
+// Runs before the app (and its logger) exists, so problems are reported on stderr
+pub fn load<T: DeserializeOwned + Default>(path: impl AsRef<Path>) -> T {
+    read(path).unwrap_or_else(|e| {
+        eprintln!("{}, using defaults", e);
+        T::default()
+    })
+}
+
+// Brings a shape in line with a config that's changed from `old` to `new`, as on a reload.
+// Only what changed in the config is touched, so unchanged shapes don't get retargeted, and
+// anything set on a shape since that the config doesn't change is left as it is.
+pub fn reconfigure(
+    old: &Config,
+    new: &Config,
+    sides: &mut Mut<SidesChangingShape<RangeInclusive<u8>>>,
+    shape: &mut Mut<LerpingShape>,
+) {
+    if new.sides != old.sides {
+        sides.sides = new.sides;
+    }
+    if new.bounds != old.bounds {
+        sides.bounds = new.bounds.clone();
+    }
+    // New sides or new bounds alike can leave the sides outside them, with nowhere to step
+    if !sides.bounds.contains(&sides.sides) && !sides.bounds.is_empty() {
+        sides.sides = sides.sides.clamp(*new.bounds.start(), *new.bounds.end());
+    }
+    if new.radius != old.radius {
+        sides.radius = new.radius;
+    }
+    if new.axis_lerp_t() != old.axis_lerp_t() {
+        shape.lerp_t = new.axis_lerp_t();
+    }
+    if new.margin_of_error != old.margin_of_error {
+        shape.margin_of_error = new.margin_of_error;
+    }
+    if new.offscreen != old.offscreen {
+        shape.offscreen = new.offscreen;
+    }
+    if new.morph_duration() != old.morph_duration() {
+        shape.duration = new.morph_duration();
+    }
+    if new.easing != old.easing {
+        shape.easing = new.easing;
+    }
+    if new.retarget != old.retarget {
+        shape.retarget = new.retarget;
+    }
+}
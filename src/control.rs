@@ -0,0 +1,161 @@
+use crate::config::Config;
+use crate::target::TargetShape;
+use crate::{LerpingShape, SidesChangingShape};
+use bevy::prelude::*;
+use std::{
+    ops::RangeInclusive,
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Mutex,
+    },
+};
+
+// Requests from outside the scene (scripts, remote control, ...) to change shapes.
+// A `None` entity applies the command to every morphing shape.
+#[derive(Clone, Debug)]
+pub enum MorphCommand {
+    SetTarget {
+        entity: Option<Entity>,
+        target: TargetShape,
+    },
+    SetSides {
+        entity: Option<Entity>,
+        sides: u8,
+    },
+    // Advance to the next side count, as if the shape had just finished lerping
+    StepSides {
+        entity: Option<Entity>,
+    },
+    SetRadius {
+        entity: Option<Entity>,
+        radius: f32,
+    },
+    SetLerpT {
+        entity: Option<Entity>,
+        lerp_t: f32,
+    },
+    SetMarginOfError {
+        entity: Option<Entity>,
+        margin_of_error: f32,
+    },
+}
+
+impl MorphCommand {
+    pub fn entity(&self) -> Option<Entity> {
+        match *self {
+            MorphCommand::SetTarget { entity, .. }
+            | MorphCommand::SetSides { entity, .. }
+            | MorphCommand::StepSides { entity }
+            | MorphCommand::SetRadius { entity, .. }
+            | MorphCommand::SetLerpT { entity, .. }
+            | MorphCommand::SetMarginOfError { entity, .. } => entity,
+        }
+    }
+}
+
+// A channel for sending MorphCommands from outside of Bevy, such as audio callbacks or network
+// threads. Clone as many senders as needed out of the resource, commands sent through any of
+// them are applied at the start of the next frame.
+pub struct MorphCommands {
+    // Senders can't be shared between threads, only cloned across them
+    sender: Mutex<Sender<MorphCommand>>,
+    receiver: Mutex<Receiver<MorphCommand>>,
+}
+
+impl Default for MorphCommands {
+    fn default() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self {
+            sender: Mutex::new(sender),
+            receiver: Mutex::new(receiver),
+        }
+    }
+}
+
+impl MorphCommands {
+    pub fn sender(&self) -> Sender<MorphCommand> {
+        self.sender.lock().unwrap().clone()
+    }
+}
+
+pub fn drain_morph_commands(channel: Res<MorphCommands>, mut commands: EventWriter<MorphCommand>) {
+    for command in channel.receiver.lock().unwrap().try_iter() {
+        commands.send(command);
+    }
+}
+
+// Side and radius changes go through `SidesChangingShape` so they're retargeted like any other step,
+// explicit targets are written straight to the lerp target and hold until the next side change
+pub fn apply_morph_commands(
+    config: Res<Config>,
+    mut events: EventReader<MorphCommand>,
+    mut shapes: Query<(
+        Entity,
+        &mut LerpingShape,
+        Option<&mut SidesChangingShape<RangeInclusive<u8>>>,
+    )>,
+) {
+    for command in events.iter() {
+        for (entity, mut shape, sides) in shapes.iter_mut() {
+            if command.entity().map_or(false, |e| e != entity) {
+                continue;
+            }
+            match command {
+                MorphCommand::SetTarget { target, .. } => shape.target = target.build(),
+                // Kept in the bounds, outside them stepping would have nowhere to go
+                MorphCommand::SetSides {
+                    sides: new_sides, ..
+                } => {
+                    if let Some(mut sides) = sides {
+                        let new_sides = if sides.bounds.is_empty() {
+                            *new_sides
+                        } else {
+                            (*new_sides).clamp(*sides.bounds.start(), *sides.bounds.end())
+                        };
+                        if sides.sides != new_sides {
+                            sides.sides = new_sides;
+                        }
+                    }
+                }
+                MorphCommand::StepSides { .. } => {
+                    if let Some(mut sides) = sides {
+                        sides.increment_sides();
+                    }
+                }
+                // Repeating the current value shouldn't wake a snapped shape back up
+                MorphCommand::SetRadius { radius, .. } => {
+                    if let Some(mut sides) = sides.filter(|s| s.radius != *radius) {
+                        sides.radius = *radius;
+                    }
+                }
+                // Scaled along each axis the same way the config's lerp_t is
+                MorphCommand::SetLerpT { lerp_t, .. } => {
+                    let lerp_t = config.lerp_axes * *lerp_t;
+                    if shape.lerp_t != lerp_t {
+                        shape.lerp_t = lerp_t;
+                    }
+                }
+                MorphCommand::SetMarginOfError {
+                    margin_of_error, ..
+                } => {
+                    if shape.margin_of_error != *margin_of_error {
+                        shape.margin_of_error = *margin_of_error;
+                    }
+                }
+            }
+        }
+    }
+}
+
+// For modes where targets come from somewhere else (a server, a recording) rather than local decisions.
+// Runs every frame, shapes come out whenever they're loaded.
+pub fn stop_changing_sides(
+    mut commands: Commands,
+    shapes: Query<Entity, Added<SidesChangingShape<RangeInclusive<u8>>>>,
+) {
+    for entity in shapes.iter() {
+        commands
+            .entity(entity)
+            .remove::<SidesChangingShape<RangeInclusive<u8>>>();
+    }
+}
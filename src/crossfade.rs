@@ -0,0 +1,120 @@
+// Morphs a shape's DrawMode along with its path, between any of Fill, Stroke and Outlined.
+// A fill or outline that's going away fades out as the shape closes in on its target, one
+// that's coming in fades in, and colours and widths in both blend across. Drawn Outlined until
+// the shape snaps, then it's exactly the new mode.
+use crate::async_tessellation::{set_mode, AsyncTessellation};
+use crate::{AlignedTarget, Snapped};
+use bevy::prelude::*;
+use bevy_prototype_lyon::entity::Path as PathComponent;
+use bevy_prototype_lyon::prelude::*;
+
+#[derive(Component)]
+pub struct ModeTransition {
+    pub to: DrawMode,
+    // Taken from the shape on the transition's first frame
+    from: Option<DrawMode>,
+    // How far the shape had to go when the transition started
+    initial: Option<f32>,
+}
+
+impl ModeTransition {
+    pub fn new(to: DrawMode) -> Self {
+        Self {
+            to,
+            from: None,
+            initial: None,
+        }
+    }
+}
+
+fn parts(mode: &DrawMode) -> (Option<FillMode>, Option<StrokeMode>) {
+    match *mode {
+        DrawMode::Fill(fill) => (Some(fill), None),
+        DrawMode::Stroke(stroke) => (None, Some(stroke)),
+        DrawMode::Outlined {
+            fill_mode,
+            outline_mode,
+        } => (Some(fill_mode), Some(outline_mode)),
+    }
+}
+
+fn mix(a: Color, b: Color, t: f32) -> Color {
+    let (a, b) = (a.as_rgba_f32(), b.as_rgba_f32());
+    let channel = |i: usize| a[i] + (b[i] - a[i]) * t;
+    Color::rgba(channel(0), channel(1), channel(2), channel(3))
+}
+
+fn faded(mut color: Color, opacity: f32) -> Color {
+    let alpha = color.a();
+    color.set_a(alpha * opacity);
+    color
+}
+
+// A side that's missing from one mode takes the other mode's colour, at no opacity
+fn blend_colors(from: Option<Color>, to: Option<Color>, t: f32) -> Color {
+    match (from, to) {
+        (Some(from), Some(to)) => mix(from, to, t),
+        (Some(from), None) => faded(from, 1.0 - t),
+        (None, Some(to)) => faded(to, t),
+        (None, None) => Color::NONE,
+    }
+}
+
+// The mode drawn a fraction t of the way from one mode to another
+pub fn blended(from: &DrawMode, to: &DrawMode, t: f32) -> DrawMode {
+    let ((from_fill, from_stroke), (to_fill, to_stroke)) = (parts(from), parts(to));
+    let mut fill_mode = to_fill
+        .or(from_fill)
+        .unwrap_or_else(|| FillMode::color(Color::NONE));
+    fill_mode.color = blend_colors(from_fill.map(|f| f.color), to_fill.map(|f| f.color), t);
+    let mut outline_mode = to_stroke
+        .or(from_stroke)
+        .unwrap_or_else(|| StrokeMode::color(Color::NONE));
+    outline_mode.color = blend_colors(from_stroke.map(|s| s.color), to_stroke.map(|s| s.color), t);
+    if let (Some(from), Some(to)) = (from_stroke, to_stroke) {
+        let (from, to) = (from.options.line_width, to.options.line_width);
+        outline_mode.options.line_width = from + (to - from) * t;
+    }
+    DrawMode::Outlined {
+        fill_mode,
+        outline_mode,
+    }
+}
+
+pub fn cross_fade_modes(
+    mut commands: Commands,
+    mut shapes: Query<(
+        Entity,
+        &PathComponent,
+        &AlignedTarget,
+        &mut ModeTransition,
+        Option<&Snapped>,
+        Option<&mut DrawMode>,
+        Option<&mut AsyncTessellation>,
+    )>,
+) {
+    for (entity, path, aligned, mut transition, snapped, mode, tessellation) in shapes.iter_mut() {
+        let current = match (&mode, &tessellation) {
+            (Some(mode), _) => **mode,
+            (None, Some(tessellation)) => *tessellation.mode(),
+            (None, None) => continue,
+        };
+        let from = *transition.from.get_or_insert(current);
+        if snapped.is_some() {
+            set_mode(mode, tessellation, transition.to);
+            commands.entity(entity).remove::<ModeTransition>();
+            continue;
+        }
+        let remaining = match aligned.remaining(&path.0) {
+            Some(remaining) => remaining,
+            None => continue,
+        };
+        let initial = *transition.initial.get_or_insert(remaining);
+        let t = if initial > 0.0 {
+            (1.0 - remaining / initial).clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+        set_mode(mode, tessellation, blended(&from, &transition.to, t));
+    }
+}
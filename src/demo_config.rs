@@ -0,0 +1,114 @@
+// The demo's own settings, kept in the same file as the library's Config, which skips over them.
+// Like the Config, any field missing from the file falls back to its default.
+use crate::cli::Args;
+use crate::overlay::WindowConfig;
+use crate::palette::Palette;
+use bevy::prelude::*;
+use serde::Deserialize;
+use shape_lerping::config::{self, Config};
+use shape_lerping::{LerpingShape, SidesChangingShape};
+use std::{
+    fs,
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+// How often the config file's modification time is checked
+const POLL_INTERVAL: f32 = 0.5;
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct DemoConfig {
+    // Normal uses the config's fill and outline colours
+    pub palette: Palette,
+    // For --teach, relative to the assets directory
+    pub label_font: PathBuf,
+    // Seconds without input before the screensaver starts, 0 turns it off
+    pub idle_seconds: f32,
+    // Only applied at startup, reloading doesn't spawn, despawn or rearrange shapes
+    pub entity_count: usize,
+    pub spacing: f32,
+    // Puts every shape in one MorphGroup, so they all snap together
+    pub grouped: bool,
+    // Also only applied at startup
+    pub window: WindowConfig,
+}
+
+impl Default for DemoConfig {
+    fn default() -> Self {
+        Self {
+            palette: Palette::Normal,
+            label_font: PathBuf::from("fonts/DejaVuSansMono.ttf"),
+            idle_seconds: 0.0,
+            entity_count: 1,
+            spacing: 500.0,
+            grouped: false,
+            window: WindowConfig::default(),
+        }
+    }
+}
+
+pub struct ConfigWatcher {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    timer: Timer,
+}
+
+impl ConfigWatcher {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            modified: modified_time(&path),
+            path,
+            timer: Timer::from_seconds(POLL_INTERVAL, true),
+        }
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+pub fn reload_config(
+    time: Res<Time>,
+    args: Res<Args>,
+    mut watcher: ResMut<ConfigWatcher>,
+    mut config: ResMut<Config>,
+    mut demo: ResMut<DemoConfig>,
+    mut shapes: Query<(
+        &mut SidesChangingShape<RangeInclusive<u8>>,
+        &mut LerpingShape,
+    )>,
+) {
+    // When the edit lands depends on the wall clock
+    if args.deterministic || !watcher.timer.tick(time.delta()).just_finished() {
+        return;
+    }
+    let modified = modified_time(&watcher.path);
+    if modified == watcher.modified {
+        return;
+    }
+    watcher.modified = modified;
+
+    // Keep the current scene if the file is mid-edit and doesn't parse
+    let read = (
+        Config::read(&watcher.path),
+        config::read::<DemoConfig>(&watcher.path),
+    );
+    let (mut new, mut new_demo) = match read {
+        (Ok(new), Ok(new_demo)) => (new, new_demo),
+        (Err(e), _) | (_, Err(e)) => {
+            warn!("{}, keeping the current config", e);
+            return;
+        }
+    };
+    args.apply(&mut new, &mut new_demo);
+    info!("Reloaded {}", watcher.path.display());
+
+    for (mut sides, mut shape) in shapes.iter_mut() {
+        config::reconfigure(&config, &new, &mut sides, &mut shape);
+    }
+
+    *config = new;
+    *demo = new_demo;
+}
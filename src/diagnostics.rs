@@ -0,0 +1,58 @@
+use bevy::diagnostic::{Diagnostic, DiagnosticId, Diagnostics};
+use bevy::prelude::*;
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+pub const MORPHED_SHAPES: DiagnosticId =
+    DiagnosticId::from_u128(0x3c5f_61d2_8a4e_4b7f_9d1e_0c27_b6a8_5e14);
+pub const EVENTS_PER_PATH: DiagnosticId =
+    DiagnosticId::from_u128(0x7a0e_94b3_2f6c_4d81_a5e9_13d7_c04b_f862);
+pub const LERP_MICROS: DiagnosticId =
+    DiagnosticId::from_u128(0xe18b_2d47_c963_4a05_8f3a_6b9e_51c2_d7f0);
+
+// Enough frames to smooth over the odd slow one without hiding a trend
+const MAX_HISTORY: usize = 20;
+
+// Filled in by `lerp_shape` as it goes, then reported and reset once per frame.
+// Atomic so the task pool's threads can all add to it.
+#[derive(Default)]
+pub struct LerpStats {
+    pub shapes: AtomicUsize,
+    pub events: AtomicUsize,
+    pub elapsed: Duration,
+}
+
+impl LerpStats {
+    pub fn add(&self, events: usize) {
+        self.shapes.fetch_add(1, Ordering::Relaxed);
+        self.events.fetch_add(events, Ordering::Relaxed);
+    }
+}
+
+pub fn register_diagnostics(mut diagnostics: ResMut<Diagnostics>) {
+    diagnostics.add(Diagnostic::new(
+        MORPHED_SHAPES,
+        "morphed_shapes",
+        MAX_HISTORY,
+    ));
+    diagnostics.add(Diagnostic::new(
+        EVENTS_PER_PATH,
+        "events_per_path",
+        MAX_HISTORY,
+    ));
+    diagnostics.add(Diagnostic::new(LERP_MICROS, "lerp_shape_us", MAX_HISTORY));
+}
+
+// Frames where nothing needed lerping are reported as zeroes, not skipped
+pub fn report_lerp_stats(mut stats: ResMut<LerpStats>, mut diagnostics: ResMut<Diagnostics>) {
+    let shapes = *stats.shapes.get_mut();
+    let events = *stats.events.get_mut();
+    diagnostics.add_measurement(MORPHED_SHAPES, shapes as f64);
+    if shapes > 0 {
+        diagnostics.add_measurement(EVENTS_PER_PATH, events as f64 / shapes as f64);
+    }
+    diagnostics.add_measurement(LERP_MICROS, stats.elapsed.as_secs_f64() * 1e6);
+    *stats = LerpStats::default();
+}
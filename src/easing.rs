@@ -0,0 +1,117 @@
+use serde::{Deserialize, Serialize};
+use std::f32::consts::PI;
+
+// How progress through a timed morph maps onto how far along the morph is
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Easing {
+    Linear,
+    QuadIn,
+    QuadOut,
+    QuadInOut,
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+    SineIn,
+    SineOut,
+    SineInOut,
+    // Overshoot either end and spring back
+    ElasticIn,
+    ElasticOut,
+    BounceIn,
+    BounceOut,
+    // CSS's cubic-bezier(x1, y1, x2, y2), so easings from design tools can be used as they are.
+    // The xs are clamped between 0 and 1 like CSS does, the ys can overshoot either end.
+    CubicBezier(f32, f32, f32, f32),
+}
+
+impl Default for Easing {
+    fn default() -> Self {
+        Self::Linear
+    }
+}
+
+// Bounces off 1 three times after landing on it, each bounce smaller
+fn bounce_out(t: f32) -> f32 {
+    const N: f32 = 7.5625;
+    const D: f32 = 2.75;
+    if t < 1.0 / D {
+        N * t * t
+    } else if t < 2.0 / D {
+        let t = t - 1.5 / D;
+        N * t * t + 0.75
+    } else if t < 2.5 / D {
+        let t = t - 2.25 / D;
+        N * t * t + 0.9375
+    } else {
+        let t = t - 2.625 / D;
+        N * t * t + 0.984375
+    }
+}
+
+// One coordinate of a bezier from 0 to 1 with the control points `a` and `b`, `s` along it
+fn bezier(a: f32, b: f32, s: f32) -> f32 {
+    3.0 * a * s * (1.0 - s) * (1.0 - s) + 3.0 * b * s * s * (1.0 - s) + s * s * s
+}
+
+// The curve's y where its x is `t`. With the xs between 0 and 1 x only ever increases along it,
+// so halving is sure to find the point, and is as close as an f32 gets after 24 halvings.
+fn cubic_bezier(x1: f32, y1: f32, x2: f32, y2: f32, t: f32) -> f32 {
+    let (x1, x2) = (x1.clamp(0.0, 1.0), x2.clamp(0.0, 1.0));
+    let (mut low, mut high) = (0.0, 1.0);
+    for _ in 0..24 {
+        let middle = (low + high) / 2.0;
+        if bezier(x1, x2, middle) < t {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+    bezier(y1, y2, (low + high) / 2.0)
+}
+
+// Three wobbles' worth over the morph
+const ELASTIC_PERIOD: f32 = 2.0 * PI / 3.0;
+
+impl Easing {
+    // `t` runs from 0 to 1, and so does the result, only leaving that range in between for the
+    // elastic easings and beziers with ys outside it
+    pub fn apply(self, t: f32) -> f32 {
+        match self {
+            Self::Linear => t,
+            Self::QuadIn => t * t,
+            Self::QuadOut => t * (2.0 - t),
+            Self::QuadInOut => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    1.0 - 2.0 * (1.0 - t) * (1.0 - t)
+                }
+            }
+            Self::CubicIn => t * t * t,
+            Self::CubicOut => 1.0 - (1.0 - t).powi(3),
+            Self::CubicInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - 4.0 * (1.0 - t).powi(3)
+                }
+            }
+            Self::SineIn => 1.0 - (t * PI / 2.0).cos(),
+            Self::SineOut => (t * PI / 2.0).sin(),
+            Self::SineInOut => (1.0 - (t * PI).cos()) / 2.0,
+            // Exactly at the ends, which the formulas only get to approximately
+            Self::ElasticIn | Self::ElasticOut | Self::CubicBezier(..) if t <= 0.0 || t >= 1.0 => {
+                t.clamp(0.0, 1.0)
+            }
+            Self::ElasticIn => {
+                -(2.0f32.powf(10.0 * t - 10.0)) * ((t * 10.0 - 10.75) * ELASTIC_PERIOD).sin()
+            }
+            Self::ElasticOut => {
+                2.0f32.powf(-10.0 * t) * ((t * 10.0 - 0.75) * ELASTIC_PERIOD).sin() + 1.0
+            }
+            Self::BounceIn => 1.0 - bounce_out(1.0 - t),
+            Self::BounceOut => bounce_out(t),
+            Self::CubicBezier(x1, y1, x2, y2) => cubic_bezier(x1, y1, x2, y2, t),
+        }
+    }
+}
@@ -0,0 +1,64 @@
+// Gives shapes an echo: a faint outline of the shape as it was `delay_frames` frames ago,
+// trailing behind it as it morphs. The echo is a child entity, so it moves with the shape.
+use crate::config::Config;
+use crate::shape_bundle;
+use bevy::prelude::*;
+use bevy_prototype_lyon::entity::Path as PathComponent;
+use bevy_prototype_lyon::prelude::*;
+use std::collections::VecDeque;
+use tess::path::Path;
+
+// How opaque the echo's outline is next to the shape's
+const ECHO_ALPHA: f32 = 0.4;
+// Behind the shape
+const ECHO_DEPTH: f32 = -0.5;
+
+#[derive(Component)]
+pub struct MorphEcho {
+    pub delay_frames: usize,
+}
+
+// The shape's last `delay_frames` paths, oldest first
+#[derive(Component)]
+pub struct Echo(VecDeque<Path>);
+
+pub fn spawn_echoes(
+    mut commands: Commands,
+    config: Res<Config>,
+    shapes: Query<(Entity, &PathComponent), Added<MorphEcho>>,
+) {
+    for (entity, path) in shapes.iter() {
+        let mut bundle = shape_bundle(&config, Vec3::new(0.0, 0.0, ECHO_DEPTH), &path.0);
+        let mut color = config.outline_color;
+        color.set_a(ECHO_ALPHA);
+        bundle.mode = DrawMode::Stroke(StrokeMode::new(color, config.outline_width));
+        let echo = commands
+            .spawn_bundle(bundle)
+            .insert(Echo(VecDeque::new()))
+            .id();
+        commands.entity(entity).push_children(&[echo]);
+    }
+}
+
+pub fn follow_with_delay(
+    shapes: Query<(&PathComponent, &MorphEcho, &Children), Without<Echo>>,
+    mut echoes: Query<(&mut Echo, &mut PathComponent)>,
+) {
+    for (path, echo, children) in shapes.iter() {
+        for &child in children.iter() {
+            let (mut history, mut echo_path) = match echoes.get_mut(child) {
+                Ok(child) => child,
+                Err(_) => continue,
+            };
+            history.0.push_back(path.0.clone());
+            // Shortening the delay drops the extra frames all at once
+            while history.0.len() > echo.delay_frames {
+                let delayed = history.0.pop_front().unwrap();
+                // Writing has it re-tessellated, which is wasted on a shape that's holding still
+                if !delayed.iter().eq(echo_path.0.iter()) {
+                    echo_path.0 = delayed;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use crate::async_tessellation::AsyncTessellation;
+use crate::FrameCount;
+use bevy::prelude::*;
+use bevy_prototype_lyon::entity::Path as PathComponent;
+use bevy_prototype_lyon::prelude::*;
+use std::{fs, path::PathBuf};
+use tess::path::{Event, Path};
+
+// Headless runs have no window to size the SVGs by, so every export uses the default window size
+const VIEW_SIZE: (f32, f32) = (1280.0, 720.0);
+
+pub struct Export {
+    pub dir: PathBuf,
+}
+
+pub fn export_frame(
+    export: Res<Export>,
+    frame: Res<FrameCount>,
+    shapes: Query<(
+        &PathComponent,
+        Option<&DrawMode>,
+        Option<&AsyncTessellation>,
+        &GlobalTransform,
+    )>,
+) {
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\">\n<g transform=\"scale(1 -1)\">\n",
+        -VIEW_SIZE.0 / 2.0,
+        -VIEW_SIZE.1 / 2.0,
+        VIEW_SIZE.0,
+        VIEW_SIZE.1
+    );
+    for (path, mode, tessellation, transform) in shapes.iter() {
+        let mode = match mode.or_else(|| tessellation.map(|t| t.mode())) {
+            Some(mode) => mode,
+            None => continue,
+        };
+        let t = transform.translation;
+        svg.push_str(&format!(
+            "<path transform=\"translate({} {})\" {} d=\"{}\"/>\n",
+            t.x,
+            t.y,
+            style(mode),
+            svg_path_data(&path.0)
+        ));
+    }
+    svg.push_str("</g>\n</svg>\n");
+
+    let file = export.dir.join(format!("frame_{:05}.svg", frame.0));
+    if let Err(e) = fs::write(&file, svg) {
+        error!("Failed to export {}: {}", file.display(), e);
+    }
+}
+
+pub fn svg_path_data(path: &Path) -> String {
+    let mut d = String::new();
+    for event in path.iter() {
+        match event {
+            Event::Begin { at } => d.push_str(&format!("M{} {} ", at.x, at.y)),
+            Event::Line { to, .. } => d.push_str(&format!("L{} {} ", to.x, to.y)),
+            Event::Quadratic { ctrl, to, .. } => {
+                d.push_str(&format!("Q{} {} {} {} ", ctrl.x, ctrl.y, to.x, to.y))
+            }
+            Event::Cubic {
+                ctrl1, ctrl2, to, ..
+            } => d.push_str(&format!(
+                "C{} {} {} {} {} {} ",
+                ctrl1.x, ctrl1.y, ctrl2.x, ctrl2.y, to.x, to.y
+            )),
+            Event::End { close: true, .. } => d.push_str("Z "),
+            Event::End { close: false, .. } => {}
+        }
+    }
+    d.truncate(d.trim_end().len());
+    d
+}
+
+fn style(mode: &DrawMode) -> String {
+    match mode {
+        DrawMode::Fill(fill) => format!("{} stroke=\"none\"", paint("fill", fill.color)),
+        DrawMode::Stroke(stroke) => format!(
+            "fill=\"none\" {} stroke-width=\"{}\"",
+            paint("stroke", stroke.color),
+            stroke.options.line_width
+        ),
+        DrawMode::Outlined {
+            fill_mode,
+            outline_mode,
+        } => format!(
+            "{} {} stroke-width=\"{}\"",
+            paint("fill", fill_mode.color),
+            paint("stroke", outline_mode.color),
+            outline_mode.options.line_width
+        ),
+    }
+}
+
+fn paint(attribute: &str, color: Color) -> String {
+    let [r, g, b, a] = color.as_rgba_f32();
+    let channel = |c: f32| (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+    format!(
+        "{0}=\"rgb({1},{2},{3})\" {0}-opacity=\"{4}\"",
+        attribute,
+        channel(r),
+        channel(g),
+        channel(b),
+        a
+    )
+}
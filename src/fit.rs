@@ -0,0 +1,110 @@
+// Placing new targets relative to the shape's current path, for mixing targets authored at
+// different scales or around different origins. Each target is placed once, as it's given.
+use crate::metrics::{bounding_box, centroid};
+use crate::LerpingShape;
+use bevy::prelude::*;
+use bevy_prototype_lyon::entity::Path as PathComponent;
+use bevy_prototype_lyon::prelude::*;
+use serde::Deserialize;
+use tess::{
+    math::{point, Point, Transform},
+    path::Path,
+};
+
+// For flattening curves to measure both paths
+const FIT_TOLERANCE: f32 = 0.1;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Deserialize)]
+pub enum Corner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl Corner {
+    // Of a (min, max) bounding box, with y up
+    fn of(self, (min, max): (Point, Point)) -> Point {
+        match self {
+            Self::TopLeft => point(min.x, max.y),
+            Self::TopRight => max,
+            Self::BottomLeft => min,
+            Self::BottomRight => point(max.x, min.y),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Deserialize)]
+pub enum TargetFit {
+    // Scaled evenly to fit inside the current path's bounding box, and centred in it
+    Fit,
+    // Moved so its centroid is on the current path's
+    AlignCentroids,
+    // Moved so this corner of its bounding box is on the same corner of the current path's
+    Anchor(Corner),
+}
+
+#[derive(Component)]
+pub struct FitTarget {
+    pub fit: TargetFit,
+    // The target as it was last placed, so it isn't placed again
+    placed: Option<Path>,
+}
+
+impl FitTarget {
+    pub fn new(fit: TargetFit) -> Self {
+        Self { fit, placed: None }
+    }
+}
+
+// `target` placed relative to `current`, or as it is if `current` is empty
+pub fn fitted(target: &Path, current: &Path, fit: TargetFit) -> Path {
+    if current.iter().next().is_none() {
+        return target.clone();
+    }
+    let transform = match fit {
+        TargetFit::Fit => {
+            let (min, max) = bounding_box(target, FIT_TOLERANCE);
+            let (current_min, current_max) = bounding_box(current, FIT_TOLERANCE);
+            let (size, current_size) = (max - min, current_max - current_min);
+            // An axis the target doesn't extend along, like a line's, doesn't limit the scale
+            let scale = [(size.x, current_size.x), (size.y, current_size.y)]
+                .iter()
+                .filter(|(size, _)| *size > 0.0)
+                .map(|(size, current_size)| current_size / size)
+                .fold(f32::INFINITY, f32::min);
+            let scale = if scale.is_finite() { scale } else { 1.0 };
+            let center = min.lerp(max, 0.5);
+            let current_center = current_min.lerp(current_max, 0.5);
+            Transform::translation(-center.x, -center.y)
+                .then_scale(scale, scale)
+                .then_translate(current_center.to_vector())
+        }
+        TargetFit::AlignCentroids => {
+            let offset = centroid(current, FIT_TOLERANCE) - centroid(target, FIT_TOLERANCE);
+            Transform::translation(offset.x, offset.y)
+        }
+        TargetFit::Anchor(corner) => {
+            let offset = corner.of(bounding_box(current, FIT_TOLERANCE))
+                - corner.of(bounding_box(target, FIT_TOLERANCE));
+            Transform::translation(offset.x, offset.y)
+        }
+    };
+    target.clone().transformed(&transform)
+}
+
+// Runs once everything else has had its chance to retarget, and before the target is checked
+pub fn fit_targets(
+    mut shapes: Query<(&PathComponent, &mut LerpingShape, &mut FitTarget), Changed<LerpingShape>>,
+) {
+    for (path, mut shape, mut fit) in shapes.iter_mut() {
+        // Changing the speed or margin changes the LerpingShape too, without a new target
+        let placed = fit.placed.as_ref();
+        if placed.map_or(false, |placed| placed.iter().eq(shape.target.iter())) {
+            continue;
+        }
+        let target = fitted(&shape.target, &path.0, fit.fit);
+        fit.placed = Some(target.clone());
+        shape.target = target;
+    }
+}
@@ -0,0 +1,105 @@
+// Morphs some parts of a shape faster than others, by scaling lerp_t along the outline, so e.g.
+// the top of a shape settles before the bottom does and it seems to melt into its target.
+// Positions go by length along the target, from the start of its first subpath to the end of its
+// last, so each part keeps the same speed the whole way through the morph. Only for morphs going
+// the feedback way, from an origin there's just the one progress for the whole path.
+use crate::path_lerping::{build_path, Lerp};
+use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::*;
+use serde::Deserialize;
+use tess::path::{Event, Path, PathEvent};
+
+#[derive(Component, Clone, Debug, Default, PartialEq, Deserialize)]
+pub struct LerpGradient {
+    // How far along the outline, 0 to 1, and what lerp_t is scaled by there, in order along it.
+    // Scales in between are interpolated, and held flat past the first and last.
+    pub stops: Vec<(f32, f32)>,
+}
+
+impl LerpGradient {
+    pub fn new(stops: Vec<(f32, f32)>) -> Self {
+        Self { stops }
+    }
+
+    // 1 without any stops, which lerps like there's no gradient at all
+    pub fn scale_at(&self, along: f32) -> f32 {
+        let after = self.stops.iter().position(|&(at, _)| at > along);
+        match after {
+            None => self.stops.last().map_or(1.0, |&(_, scale)| scale),
+            Some(0) => self.stops[0].1,
+            Some(i) => {
+                let ((from_at, from), (to_at, to)) = (self.stops[i - 1], self.stops[i]);
+                from + (to - from) * (along - from_at) / (to_at - from_at)
+            }
+        }
+    }
+}
+
+// How far along the outline each event ends, 0 to 1. Curves are measured along their control
+// points, which is near enough for picking speeds.
+pub fn event_positions(events: &[PathEvent]) -> Vec<f32> {
+    let mut along = 0.0;
+    let mut positions = events
+        .iter()
+        .map(|event| {
+            along += match *event {
+                Event::Begin { .. } => 0.0,
+                Event::Line { from, to }
+                | Event::End {
+                    last: from,
+                    first: to,
+                    close: true,
+                } => from.distance_to(to),
+                Event::End { .. } => 0.0,
+                Event::Quadratic { from, ctrl, to } => {
+                    from.distance_to(ctrl) + ctrl.distance_to(to)
+                }
+                Event::Cubic {
+                    from,
+                    ctrl1,
+                    ctrl2,
+                    to,
+                } => from.distance_to(ctrl1) + ctrl1.distance_to(ctrl2) + ctrl2.distance_to(to),
+            };
+            along
+        })
+        .collect::<Vec<_>>();
+    if along > 0.0 {
+        for position in &mut positions {
+            *position /= along;
+        }
+    }
+    positions
+}
+
+// Lerps each event of `path` toward the one of `aligned` it lines up with, at `t` scaled by the
+// gradient where it is along them. Returns `target` itself once every event's snapped, like
+// `lerped_aligned` does. Paths that don't line up with `aligned` lerp at `t` all over instead.
+pub fn gradient_lerped(
+    path: &Path,
+    target: &Path,
+    aligned: &[PathEvent],
+    t: Vec2,
+    gradient: &LerpGradient,
+    p: f32,
+) -> (bool, Path) {
+    if path.iter().count() != aligned.len() {
+        return path.lerped(target, t, p);
+    }
+    let mut all_snapped = true;
+    let events = path
+        .iter()
+        .zip(aligned)
+        .zip(event_positions(aligned))
+        .map(|((event, &target_event), along)| {
+            let t = (t * gradient.scale_at(along)).clamp(Vec2::ZERO, Vec2::ONE);
+            let (snapped, event) = event.lerped(target_event, t, p);
+            all_snapped &= snapped;
+            event
+        })
+        .collect::<Vec<_>>();
+    if all_snapped {
+        return (true, target.clone());
+    }
+    (false, build_path(&events))
+}
@@ -0,0 +1,122 @@
+// Shapes in the same MorphGroup reach their targets on the same frame. Each frame, every
+// member's lerp_t is rescaled so it has as many frames left to go as the slowest member has
+// at its own lerp_t, then a single GroupLerpFinished is sent once all of them have snapped.
+// Shapes held offscreen or left out by a lerp budget lose frames, and finish later. A member
+// lerps at its own pace on the frame it joins or is given a new target, while its pace is added.
+use crate::margin::MarginScale;
+use crate::{config::Config, AlignedTarget, LerpingShape, Snapped};
+use bevy::prelude::*;
+use bevy::utils::{HashMap, HashSet};
+use bevy_prototype_lyon::entity::Path as PathComponent;
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct GroupId(pub u32);
+
+#[derive(Component)]
+pub struct MorphGroup(pub GroupId);
+
+// Used by `lerp_shape` in place of the LerpingShape's lerp_t. Kept apart from the LerpingShape,
+// as changing that would have its target aligned again, and written in place so it's this frame's.
+#[derive(Component)]
+pub struct GroupPace(pub Vec2);
+
+pub struct GroupLerpFinished(pub GroupId);
+
+// Aiming for half the margin makes up for rounding in the number of frames
+const MARGIN_FRACTION: f32 = 0.5;
+
+// Each frame a point closes (1 - t) of its distance, so it takes log(margin / distance) /
+// log(1 - t) frames to get within a margin
+fn frames_left(distance: f32, margin: f32, t: f32) -> f32 {
+    if distance <= margin || t >= 1.0 {
+        0.0
+    } else if t <= 0.0 {
+        f32::INFINITY
+    } else {
+        ((margin / distance).ln() / (1.0 - t).ln()).ceil()
+    }
+}
+
+pub fn pace_groups(
+    mut commands: Commands,
+    config: Res<Config>,
+    shapes: Query<
+        (
+            Entity,
+            &MorphGroup,
+            &PathComponent,
+            &LerpingShape,
+            &AlignedTarget,
+            Option<&MarginScale>,
+        ),
+        Without<Snapped>,
+    >,
+    mut paces: Query<&mut GroupPace>,
+    done: Query<Entity, (With<GroupPace>, Or<(With<Snapped>, Without<MorphGroup>)>)>,
+) {
+    for entity in done.iter() {
+        commands.entity(entity).remove::<GroupPace>();
+    }
+    let mut members = HashMap::<GroupId, Vec<(Entity, f32, Vec2)>>::default();
+    for (entity, group, path, shape, aligned, scale) in shapes.iter() {
+        let margin = shape.margin(scale, config.snap_epsilon) * MARGIN_FRACTION;
+        match aligned.remaining(&path.0) {
+            Some(distance) => {
+                let member = (entity, distance / margin, shape.lerp_t);
+                members.entry(group.0).or_default().push(member);
+            }
+            // Until its path lines up with its target, at its own pace
+            None => {
+                if let Ok(mut pace) = paces.get_mut(entity) {
+                    pace.0 = shape.lerp_t;
+                }
+            }
+        }
+    }
+    for members in members.values() {
+        // A shape's slower axis is the one that decides when it gets there
+        let frames = members
+            .iter()
+            .map(|&(_, ratio, t)| frames_left(ratio, 1.0, t.min_element()))
+            .fold(0.0, f32::max);
+        for &(entity, ratio, t) in members {
+            // The same pace along both axes, in place of the shape's own lerp_t for each. The
+            // ratio's for the furthest point's distance as the crow flies, which only closes by
+            // the pace each frame if both axes close by it.
+            let pace = if frames > 0.0 && frames.is_finite() && ratio > 1.0 {
+                Vec2::splat(1.0 - ratio.recip().powf(frames.recip()))
+            } else {
+                t
+            };
+            match paces.get_mut(entity) {
+                Ok(mut group_pace) => group_pace.0 = pace,
+                Err(_) => {
+                    commands.entity(entity).insert(GroupPace(pace));
+                }
+            }
+        }
+    }
+}
+
+// Groups count as finished from the frame their last member snaps until one is given a new target
+pub fn finish_groups(
+    mut finished: Local<HashSet<GroupId>>,
+    mut events: EventWriter<GroupLerpFinished>,
+    shapes: Query<(&MorphGroup, Option<&Snapped>)>,
+) {
+    let mut all_snapped = HashMap::<GroupId, bool>::default();
+    for (group, snapped) in shapes.iter() {
+        *all_snapped.entry(group.0).or_insert(true) &= snapped.is_some();
+    }
+    finished.retain(|group| all_snapped.get(group) == Some(&true));
+    let mut newly_finished = all_snapped
+        .into_iter()
+        .filter(|&(group, snapped)| snapped && finished.insert(group))
+        .map(|(group, _)| group)
+        .collect::<Vec<_>>();
+    // In the same order from run to run
+    newly_finished.sort();
+    for group in newly_finished {
+        events.send(GroupLerpFinished(group));
+    }
+}
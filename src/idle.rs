@@ -0,0 +1,119 @@
+// Screensaver mode. After `idle_seconds` without any input, shapes that finish a morph step to a
+// random side count in their bounds, in a random colour (from the palette, unless it's Normal)
+// at a random speed. Any input hands control back, restoring the palette's colours and the
+// config's speed.
+use crate::demo_config::DemoConfig;
+use crate::palette::{recolor, ActivePalette, Palette};
+use bevy::input::mouse::{MouseMotion, MouseWheel};
+use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::*;
+use shape_lerping::async_tessellation::AsyncTessellation;
+use shape_lerping::rng::ShapeRng;
+use shape_lerping::{config::Config, LerpBatchFinished, LerpingShape, ShapeId, SidesChangingShape};
+use std::ops::RangeInclusive;
+
+// How much slower or faster than the config's lerp_t a random speed can be
+const SPEED_RANGE: (f32, f32) = (0.5, 2.0);
+
+#[derive(Default)]
+pub struct Idle {
+    elapsed: f32,
+    active: bool,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn detect_idle(
+    time: Res<Time>,
+    config: Res<Config>,
+    demo: Res<DemoConfig>,
+    keys: Res<Input<KeyCode>>,
+    buttons: Res<Input<MouseButton>>,
+    mut motion: EventReader<MouseMotion>,
+    mut wheel: EventReader<MouseWheel>,
+    palette: Res<ActivePalette>,
+    mut idle: ResMut<Idle>,
+    mut shapes: Query<(
+        &ShapeId,
+        &mut LerpingShape,
+        Option<&mut DrawMode>,
+        Option<&mut AsyncTessellation>,
+    )>,
+) {
+    // Both readers have to be drained every frame, or old events would count once it's on
+    let moved = motion.iter().count() > 0;
+    let scrolled = wheel.iter().count() > 0;
+    if demo.idle_seconds <= 0.0 {
+        return;
+    }
+    let input = moved
+        || scrolled
+        || keys.get_just_pressed().next().is_some()
+        || buttons.get_just_pressed().next().is_some();
+    if !input {
+        idle.elapsed += time.delta_seconds();
+        if !idle.active && idle.elapsed >= demo.idle_seconds {
+            info!(
+                "Idle for {} seconds, starting the screensaver",
+                demo.idle_seconds
+            );
+            idle.active = true;
+        }
+        return;
+    }
+    idle.elapsed = 0.0;
+    if !idle.active {
+        return;
+    }
+    idle.active = false;
+    let colors = palette.0.colors(&config);
+    for (id, mut shape, mode, tessellation) in shapes.iter_mut() {
+        if shape.lerp_t() != config.axis_lerp_t() {
+            shape.set_lerp_t(config.axis_lerp_t());
+        }
+        let pair = colors[id.0 as usize % colors.len()];
+        recolor(mode, tessellation, pair, &config);
+    }
+}
+
+// Runs after the finished shapes have stepped their sides, and overrides the step
+pub fn randomize_finished(
+    config: Res<Config>,
+    palette: Res<ActivePalette>,
+    idle: Res<Idle>,
+    mut lerp_events: EventReader<LerpBatchFinished>,
+    mut shapes: Query<(
+        &mut SidesChangingShape<RangeInclusive<u8>>,
+        &mut LerpingShape,
+        &mut ShapeRng,
+        Option<&mut DrawMode>,
+        Option<&mut AsyncTessellation>,
+    )>,
+) {
+    if !idle.active {
+        // Otherwise the last frame's events would be acted on once it starts
+        lerp_events.iter().for_each(drop);
+        return;
+    }
+    let colors = palette.0.colors(&config);
+    for LerpBatchFinished(entities) in lerp_events.iter() {
+        for &entity in entities {
+            let (mut sides, mut shape, mut rng, mode, tessellation) = match shapes.get_mut(entity) {
+                Ok(shape) => shape,
+                Err(_) => continue,
+            };
+            let (min, max) = (*sides.bounds().start(), *sides.bounds().end());
+            if min <= max {
+                sides.set_sides(min + rng.index((max - min) as usize + 1) as u8);
+            }
+            let speed = rng.range(SPEED_RANGE.0, SPEED_RANGE.1);
+            shape.set_lerp_t((config.axis_lerp_t() * speed).min(Vec2::ONE));
+            let pair = if palette.0 == Palette::Normal {
+                let hue = rng.unit() * 360.0;
+                (Color::hsl(hue, 0.8, 0.55), Color::hsl(hue, 0.8, 0.35))
+            } else {
+                colors[rng.index(colors.len())]
+            };
+            recolor(mode, tessellation, pair, &config);
+        }
+    }
+}
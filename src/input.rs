@@ -0,0 +1,145 @@
+use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
+use bevy::prelude::*;
+use bevy_prototype_lyon::entity::Path as PathComponent;
+use bevy_prototype_lyon::prelude::*;
+use shape_lerping::{LerpingShape, MainCamera, Paused, SidesChangingShape};
+use std::{cmp::Ordering, ops::RangeBounds};
+use tess::{
+    math::{point, Point},
+    path::{iterator::PathIterator, Event, Path},
+};
+
+// World-space position of the cursor, or None if it isn't over the primary window
+#[derive(Default)]
+pub struct CursorPosition(pub Option<Vec2>);
+
+// The shape currently being dragged, and its offset from the cursor when it was grabbed
+#[derive(Default)]
+pub struct Dragging(Option<(Entity, Vec2)>);
+
+pub fn update_cursor_position(
+    windows: Res<Windows>,
+    cameras: Query<(&GlobalTransform, &OrthographicProjection), With<MainCamera>>,
+    mut cursor: ResMut<CursorPosition>,
+) {
+    cursor.0 = windows.get_primary().and_then(|window| {
+        let screen = window.cursor_position()?;
+        let (transform, projection) = cameras.iter().next()?;
+        let size = Vec2::new(window.width(), window.height());
+        let offset = (screen - size / 2.0) * projection.scale;
+        Some(transform.mul_vec3(offset.extend(0.0)).truncate())
+    });
+}
+
+// Finds the topmost morphing shape whose current outline contains the cursor
+pub fn shape_under_cursor<'a>(
+    cursor: Vec2,
+    shapes: impl IntoIterator<Item = (Entity, &'a PathComponent, &'a GlobalTransform)>,
+) -> Option<Entity> {
+    shapes
+        .into_iter()
+        .filter(|(_, path, transform)| {
+            let local = transform
+                .compute_matrix()
+                .inverse()
+                .transform_point3(cursor.extend(0.0));
+            contains(&path.0, point(local.x, local.y))
+        })
+        .max_by(|(_, _, a), (_, _, b)| {
+            a.translation
+                .z
+                .partial_cmp(&b.translation.z)
+                .unwrap_or(Ordering::Equal)
+        })
+        .map(|(entity, _, _)| entity)
+}
+
+// Even-odd test against the flattened outline, treating every subpath as closed like the fill does
+fn contains(path: &Path, at: Point) -> bool {
+    let mut inside = false;
+    for event in path.iter().flattened(0.5) {
+        let (from, to) = match event {
+            Event::Line { from, to } => (from, to),
+            Event::End { last, first, .. } => (last, first),
+            _ => continue,
+        };
+        if (from.y > at.y) != (to.y > at.y)
+            && at.x < (to.x - from.x) * (at.y - from.y) / (to.y - from.y) + from.x
+        {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+pub fn grab_shape(
+    buttons: Res<Input<MouseButton>>,
+    cursor: Res<CursorPosition>,
+    shapes: Query<(Entity, &PathComponent, &GlobalTransform), With<LerpingShape>>,
+    transforms: Query<&Transform>,
+    mut dragging: ResMut<Dragging>,
+) {
+    if buttons.just_released(MouseButton::Left) {
+        dragging.0 = None;
+    }
+    if !buttons.just_pressed(MouseButton::Left) {
+        return;
+    }
+    if let Some(cursor) = cursor.0 {
+        dragging.0 = shape_under_cursor(cursor, shapes.iter())
+            .and_then(|entity| Some((entity, transforms.get(entity).ok()?)))
+            .map(|(entity, transform)| (entity, transform.translation.truncate() - cursor));
+    }
+}
+
+// Only the Transform moves, the path and its target stay in local space so the morph is unaffected
+pub fn drag_shape(
+    cursor: Res<CursorPosition>,
+    dragging: Res<Dragging>,
+    mut transforms: Query<&mut Transform>,
+) {
+    if let (Some((entity, offset)), Some(cursor)) = (dragging.0, cursor.0) {
+        if let Ok(mut transform) = transforms.get_mut(entity) {
+            let z = transform.translation.z;
+            transform.translation = (cursor + offset).extend(z);
+        }
+    }
+}
+
+pub fn toggle_pause(keys: Res<Input<KeyCode>>, mut paused: ResMut<Paused>) {
+    if keys.just_pressed(KeyCode::Space) {
+        paused.0 = !paused.0;
+    }
+}
+
+// Each line scrolled scales the hovered shape's target radius by this factor (or its inverse)
+const SCROLL_SCALE: f32 = 1.1;
+const PIXELS_PER_LINE: f32 = 100.0;
+const MIN_RADIUS: f32 = 1.0;
+
+pub fn scroll_radius<T: RangeBounds<u8> + 'static + Send + Sync>(
+    mut wheel: EventReader<MouseWheel>,
+    cursor: Res<CursorPosition>,
+    shapes: Query<(Entity, &PathComponent, &GlobalTransform), With<LerpingShape>>,
+    mut sides: Query<&mut SidesChangingShape<T>>,
+) {
+    let lines = wheel
+        .iter()
+        .map(|event| match event.unit {
+            MouseScrollUnit::Line => event.y,
+            MouseScrollUnit::Pixel => event.y / PIXELS_PER_LINE,
+        })
+        .sum::<f32>();
+    if lines == 0.0 {
+        return;
+    }
+    if let Some(entity) = cursor
+        .0
+        .and_then(|cursor| shape_under_cursor(cursor, shapes.iter()))
+    {
+        if let Ok(mut sides) = sides.get_mut(entity) {
+            let radius = sides.radius() * SCROLL_SCALE.powf(lines);
+            sides.set_radius(radius.max(MIN_RADIUS));
+        }
+    }
+}
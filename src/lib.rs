@@ -0,0 +1,934 @@
+pub mod after_lerp;
+pub mod async_tessellation;
+#[cfg(feature = "audio")]
+pub mod audio;
+pub mod boolean;
+pub mod callbacks;
+pub mod config;
+pub mod control;
+pub mod crossfade;
+pub mod diagnostics;
+pub mod easing;
+pub mod echo;
+pub mod export;
+pub mod fit;
+pub mod gradient;
+pub mod group;
+pub mod lock;
+pub mod lod;
+pub mod margin;
+pub mod metadata;
+pub mod metrics;
+#[cfg(feature = "midi")]
+pub mod midi;
+#[cfg(feature = "net")]
+pub mod net;
+pub mod offset;
+pub mod origin;
+#[cfg(feature = "osc")]
+pub mod osc;
+pub mod path_lerping;
+pub mod pulse;
+pub mod recipe;
+pub mod replay;
+pub mod ripple;
+pub mod rng;
+#[cfg(feature = "scripting")]
+pub mod scripting;
+pub mod spring;
+pub mod stroke;
+pub mod svg;
+pub mod target;
+pub mod timeline;
+pub mod tolerance;
+pub mod trim;
+pub mod ui;
+pub mod validation;
+pub mod visibility;
+#[cfg(feature = "websocket")]
+pub mod websocket;
+
+use crate::config::Config;
+use crate::control::{MorphCommand, MorphCommands};
+use crate::diagnostics::LerpStats;
+use crate::easing::Easing;
+use crate::gradient::{gradient_lerped, LerpGradient};
+use crate::group::{GroupLerpFinished, GroupPace};
+use crate::lod::Lod;
+use crate::margin::MarginScale;
+use crate::origin::{MorphOrigin, RetargetPolicy};
+use crate::path_lerping::{aligned_events, lerped_aligned, with_scratch_events};
+use crate::rng::{perturbed, ShapeRng};
+use crate::spring::{sprung, SpringLerp};
+use crate::target::TargetShape;
+use crate::timeline::{KeyframeReached, LerpTimeline};
+use crate::validation::InvalidTarget;
+use crate::visibility::{OffscreenPolicy, OnScreen};
+use bevy::{
+    ecs::schedule::ShouldRun,
+    prelude::*,
+    tasks::ComputeTaskPool,
+    utils::{HashMap, HashSet},
+};
+use bevy_prototype_lyon::entity::Path as PathComponent;
+use bevy_prototype_lyon::prelude::*;
+use serde::Deserialize;
+use std::{
+    mem,
+    ops::{Bound, RangeBounds, RangeFrom, RangeFull, RangeInclusive, RangeTo},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+use tess::path::{Path, PathEvent};
+
+enum Direction {
+    Increasing,
+    Decreasing,
+}
+
+impl Direction {
+    fn inverted(&self) -> Self {
+        match self {
+            Direction::Increasing => Direction::Decreasing,
+            Direction::Decreasing => Direction::Increasing,
+        }
+    }
+
+    fn invert(&mut self) {
+        *self = self.inverted();
+    }
+}
+
+#[derive(Component)]
+pub struct SidesChangingShape<T: RangeBounds<u8>> {
+    sides: u8,
+    radius: f32,
+    bounds: T,
+    direction: Direction,
+}
+
+impl<T: RangeBounds<u8>> SidesChangingShape<T> {
+    // Starting out at `sides`, stepping up first
+    pub fn new(sides: u8, radius: f32, bounds: T) -> Self {
+        Self {
+            sides,
+            radius,
+            bounds,
+            direction: Direction::Increasing,
+        }
+    }
+
+    pub fn sides(&self) -> u8 {
+        self.sides
+    }
+
+    // Counts outside the bounds head back into them on the next step
+    pub fn set_sides(&mut self, sides: u8) {
+        self.sides = sides;
+    }
+
+    pub fn radius(&self) -> f32 {
+        self.radius
+    }
+
+    pub fn set_radius(&mut self, radius: f32) {
+        self.radius = radius;
+    }
+
+    pub fn bounds(&self) -> &T {
+        &self.bounds
+    }
+
+    fn step(&self, direction: &Direction) -> Option<u8> {
+        // Stepping past either end of a u8 counts as leaving the bounds
+        match direction {
+            Direction::Increasing => self.sides.checked_add(1),
+            Direction::Decreasing => self.sides.checked_sub(1),
+        }
+        .filter(|new_sides| self.bounds.contains(new_sides))
+    }
+
+    // Which way the bounds are from sides that have ended up outside them, if they're either way
+    fn toward_bounds(&self) -> Option<Direction> {
+        let below = match self.bounds.start_bound() {
+            Bound::Included(&start) => self.sides < start,
+            Bound::Excluded(&start) => self.sides <= start,
+            Bound::Unbounded => false,
+        };
+        let above = match self.bounds.end_bound() {
+            Bound::Included(&end) => self.sides > end,
+            Bound::Excluded(&end) => self.sides >= end,
+            Bound::Unbounded => false,
+        };
+        match (below, above) {
+            (true, false) => Some(Direction::Increasing),
+            (false, true) => Some(Direction::Decreasing),
+            _ => None,
+        }
+    }
+
+    // Bounces off the ends of the bounds, or heads back into them from outside. False, leaving
+    // the sides as they are, if there's no step to take either way, as with bounds of a single
+    // count or none at all.
+    fn increment_sides(&mut self) -> bool {
+        if !self.bounds.contains(&self.sides) {
+            let toward = match self.toward_bounds() {
+                Some(toward) => toward,
+                None => return false,
+            };
+            // Outside the bounds there's always room to step toward them
+            self.sides = match toward {
+                Direction::Increasing => self.sides + 1,
+                Direction::Decreasing => self.sides - 1,
+            };
+            self.direction = toward;
+            return true;
+        }
+        if let Some(new_sides) = self.step(&self.direction) {
+            self.sides = new_sides;
+            return true;
+        }
+        match self.step(&self.direction.inverted()) {
+            Some(new_sides) => {
+                self.direction.invert();
+                self.sides = new_sides;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct LerpingShape {
+    // In the entity's local space, like the path itself, so moving its Transform doesn't disturb the morph
+    target: Path,
+    // Per axis
+    lerp_t: Vec2,
+    margin_of_error: f32,
+    offscreen: OffscreenPolicy,
+    // Set for morphs that take a fixed time, in place of lerp_t
+    duration: Option<Duration>,
+    // Applied to the progress from the shape's origin
+    easing: Easing,
+    retarget: RetargetPolicy,
+}
+
+impl LerpingShape {
+    // Morphing toward `target` the way the config says
+    pub fn new(target: Path, config: &Config) -> Self {
+        Self {
+            offscreen: config.offscreen,
+            duration: config.morph_duration(),
+            easing: config.easing,
+            retarget: config.retarget,
+            ..Self::lerping(target, config.axis_lerp_t(), config.margin_of_error)
+        }
+    }
+
+    // Closing `lerp_t` of the distance to `target` each frame until it's within `margin_of_error`,
+    // with the defaults for everything else, for apps without a Config
+    pub fn lerping(target: Path, lerp_t: Vec2, margin_of_error: f32) -> Self {
+        Self {
+            target,
+            lerp_t,
+            margin_of_error,
+            offscreen: OffscreenPolicy::default(),
+            duration: None,
+            easing: Easing::Linear,
+            retarget: RetargetPolicy::default(),
+        }
+    }
+
+    pub fn target(&self) -> &Path {
+        &self.target
+    }
+
+    // The morph carries on from wherever the path is, toward the new target
+    pub fn set_target(&mut self, target: Path) {
+        self.target = target;
+    }
+
+    pub fn lerp_t(&self) -> Vec2 {
+        self.lerp_t
+    }
+
+    pub fn set_lerp_t(&mut self, lerp_t: Vec2) {
+        self.lerp_t = lerp_t;
+    }
+
+    // Taking exactly `duration` to get to each target instead, from a snapshot of the path as
+    // the target's given, and finishing on the frame the time's up
+    pub fn over(self, duration: Duration) -> Self {
+        Self {
+            duration: Some(duration),
+            ..self
+        }
+    }
+
+    // Eased morphs are sampled from an origin like timed ones. Untimed, they snap as soon as they
+    // first reach the target, so bounces and wobbles only play out in full over a duration.
+    pub fn eased(self, easing: Easing) -> Self {
+        Self { easing, ..self }
+    }
+
+    // What a new target does to the progress of a morph from the shape's origin
+    pub fn retargeting(self, retarget: RetargetPolicy) -> Self {
+        Self { retarget, ..self }
+    }
+
+    // Either way round it's a snapshot of the path being morphed from
+    fn needs_origin(&self) -> bool {
+        self.duration.is_some() || self.easing != Easing::Linear
+    }
+
+    fn eased_progress(&self, progress: Vec2) -> Vec2 {
+        Vec2::new(self.easing.apply(progress.x), self.easing.apply(progress.y))
+    }
+
+    // What `lerp_shape` snaps within
+    pub fn margin(&self, scale: Option<&MarginScale>, snap_epsilon: f32) -> f32 {
+        self.margin_of_error * scale.map_or(1.0, |scale| scale.0) + snap_epsilon
+    }
+}
+
+// The side count and radius the shape's target was last built from. Kept apart from
+// SidesChangingShape, so noting them doesn't count as changing the sides again.
+#[derive(Component)]
+pub struct BuiltSides {
+    sides: u8,
+    radius: f32,
+}
+
+impl BuiltSides {
+    // Spawned alongside a SidesChangingShape whose target is already built from the same values
+    pub fn new(sides: u8, radius: f32) -> Self {
+        Self { sides, radius }
+    }
+}
+
+// Marks shapes that have reached their target, so they're skipped until they're given a new one
+#[derive(Component)]
+pub struct Snapped;
+
+// The target's events lined up with the path's,
+// recomputed whenever the LerpingShape or its Lod changes, see `align_targets`
+#[derive(Component, Default)]
+pub struct AlignedTarget {
+    events: Vec<PathEvent>,
+    padding: usize,
+    // Local-space bounding box of the full target, for picking a level of detail and culling
+    bounds: (Vec2, Vec2),
+    level: u8,
+    // Simplified targets by level, kept until the target changes so zooming back and forth is free
+    simplified: HashMap<u8, Path>,
+}
+
+impl AlignedTarget {
+    pub fn events(&self) -> &[PathEvent] {
+        &self.events
+    }
+
+    // The furthest any point of `path` still has to go, if it's lined up with the target yet
+    fn remaining(&self, path: &Path) -> Option<f32> {
+        if path.iter().count() != self.events.len() {
+            return None;
+        }
+        let distances = path
+            .iter()
+            .zip(&self.events)
+            .map(|(event, target)| event.to().distance_to(target.to()));
+        Some(distances.fold(0.0, f32::max))
+    }
+
+    fn size(&self) -> f32 {
+        (self.bounds.1 - self.bounds.0).max_element()
+    }
+
+    // The target at the current level of detail
+    fn target<'a>(&'a self, full: &'a Path) -> &'a Path {
+        self.simplified.get(&self.level).unwrap_or(full)
+    }
+}
+
+#[derive(Component)]
+pub struct MainCamera;
+
+// Identifies a shape across instances of the app, entities themselves differ between runs
+#[derive(Component, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct ShapeId(pub u32);
+
+// Number of frames that have completed since startup
+#[derive(Default)]
+pub struct FrameCount(pub u64);
+
+// Event for when all points of LerpingShapes are within the margin-of-error of their target paths.
+// Sent once a frame for all the shapes that snapped on it, in entity order,
+// as synchronised shapes tend to snap together by the hundred.
+pub struct LerpBatchFinished(pub Vec<Entity>);
+
+// What happens when a shape is given a target it's already at
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Deserialize)]
+pub enum SameTargetPolicy {
+    // It finishes straight away, sending LerpBatchFinished once for the new target. A shape
+    // that's already snapped there stays snapped instead, unless a timeline's starting a keyframe.
+    Finish,
+    // It's marked Snapped without a LerpBatchFinished, as there was nothing to morph.
+    // Shapes spawned at their target then wait for something else to give them a new one.
+    // Timelines, queues among them, go on to their next keyframe on the event, so it's still
+    // sent for them.
+    Ignore,
+}
+
+impl Default for SameTargetPolicy {
+    fn default() -> Self {
+        Self::Finish
+    }
+}
+
+// Event for when a shape finished lerping but its bounds left it no side count to step to
+pub struct SidesStuck(pub Entity);
+
+// The stages of the morph pipeline, for ordering other systems against it
+#[derive(Clone, Copy, Hash, PartialEq, Eq, Debug, SystemLabel)]
+pub enum ShapeLerpSet {
+    // Works out what each shape is morphing toward this frame
+    UpdateTarget,
+    // Moves each unsnapped shape a step toward its target, sending LerpBatchFinished as they snap
+    Lerp,
+    // Reacts to what the lerp did
+    HandleEvents,
+}
+
+// While true, shapes hold still wherever they are. Targets can still be changed in the meantime.
+#[derive(Default)]
+pub struct Paused(pub bool);
+
+// Finer ordering within and around the pipeline
+#[derive(Clone, Copy, Hash, PartialEq, Eq, Debug, SystemLabel)]
+pub enum System {
+    ReloadConfig,
+    ApplyPalette,
+    UpdateCursor,
+    Drag,
+    ScrollRadius,
+    ChangeSides,
+    CheckSides,
+    UpdateLerpTarget,
+    ApplyCommands,
+    FitTargets,
+    CheckTargets,
+    LockEvents,
+    AlignTargets,
+    Cull,
+    #[cfg(feature = "scripting")]
+    Script,
+    #[cfg(feature = "audio")]
+    AnalyzeAudio,
+    Export,
+    PlaySlides,
+    CountFrames,
+}
+
+// The morph pipeline, for adding to any app with shapes to morph. Uses the app's Config if it
+// has one by the time the plugin's added, and the defaults otherwise.
+pub struct ShapeLerpPlugin;
+
+impl Plugin for ShapeLerpPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Config>()
+            .init_resource::<FrameCount>()
+            .add_event::<LerpBatchFinished>()
+            .add_event::<SidesStuck>()
+            .add_event::<MorphCommand>()
+            .init_resource::<MorphCommands>()
+            .add_event::<InvalidTarget>()
+            .add_event::<GroupLerpFinished>()
+            .add_event::<KeyframeReached>()
+            .init_resource::<Paused>()
+            .init_resource::<LerpStats>()
+            .add_system_set(side_stepping::<RangeInclusive<u8>>())
+            .add_system_set(side_stepping::<RangeFrom<u8>>())
+            .add_system_set(side_stepping::<RangeTo<u8>>())
+            .add_system_set(side_stepping::<RangeFull>())
+            .add_system_set(
+                SystemSet::new()
+                    .label(ShapeLerpSet::UpdateTarget)
+                    .with_system(
+                        control::apply_morph_commands
+                            .label(System::ApplyCommands)
+                            .after(System::UpdateLerpTarget),
+                    )
+                    .with_system(
+                        fit::fit_targets
+                            .label(System::FitTargets)
+                            .after(System::ApplyCommands),
+                    )
+                    .with_system(
+                        validation::check_targets
+                            .label(System::CheckTargets)
+                            .after(System::FitTargets),
+                    )
+                    .with_system(
+                        align_targets
+                            .label(System::AlignTargets)
+                            .after(System::CheckTargets),
+                    ),
+            )
+            .add_system_set(
+                SystemSet::new()
+                    .label(ShapeLerpSet::Lerp)
+                    .after(ShapeLerpSet::UpdateTarget)
+                    .with_run_criteria(anything_to_lerp)
+                    .with_system(lerp_shape),
+            )
+            .add_system_set(
+                SystemSet::new()
+                    .label(ShapeLerpSet::HandleEvents)
+                    .after(ShapeLerpSet::Lerp)
+                    .with_system(validation::check_live_paths),
+            )
+            .add_system_to_stage(CoreStage::PreUpdate, add_aligned_targets)
+            .add_system_to_stage(CoreStage::PreUpdate, control::drain_morph_commands)
+            .add_system_to_stage(CoreStage::Last, count_frames.label(System::CountFrames))
+            .add_system(
+                pulse::pulse_shapes
+                    .after(System::UpdateLerpTarget)
+                    .before(System::ApplyCommands),
+            )
+            .add_system(
+                timeline::advance_timelines
+                    .after(System::UpdateLerpTarget)
+                    .before(System::ApplyCommands),
+            )
+            .add_system(
+                origin::snapshot_origins
+                    .after(System::LockEvents)
+                    .before(ShapeLerpSet::Lerp),
+            )
+            .add_system(
+                group::pace_groups
+                    .after(System::AlignTargets)
+                    .before(ShapeLerpSet::Lerp),
+            )
+            .add_system(group::finish_groups.after(ShapeLerpSet::Lerp))
+            .add_system(after_lerp::apply_after_lerp.after(ShapeLerpSet::Lerp))
+            .init_resource::<callbacks::MorphCallbacks>()
+            .add_system(
+                callbacks::run_completion_callbacks
+                    .exclusive_system()
+                    .at_end(),
+            )
+            .add_system(crossfade::cross_fade_modes.after(ShapeLerpSet::Lerp))
+            .add_system(echo::spawn_echoes)
+            .add_system(echo::follow_with_delay.after(ShapeLerpSet::Lerp))
+            .add_system(
+                lock::lock_events
+                    .label(System::LockEvents)
+                    .after(System::CheckTargets)
+                    .before(System::AlignTargets),
+            )
+            .add_system(trim::spawn_trimmed_outlines)
+            .add_system(trim::trim_outlines.after(ShapeLerpSet::Lerp))
+            .add_system(trim::untrim)
+            .add_system(ripple::spawn_ripples)
+            .add_system(
+                ripple::follow_outward
+                    .after(System::CheckTargets)
+                    .before(System::AlignTargets),
+            );
+
+        #[cfg(debug_assertions)]
+        app.add_system_to_stage(CoreStage::PostUpdate, validation::assert_well_formed);
+    }
+}
+
+// Steps and retargets the SidesChangingShapes bounded by a T, as part of the UpdateTarget set.
+// The config only makes inclusive ranges, the others are for shapes spawned in code; stepping
+// treats the ends of a u8 as the ends of any range that doesn't stop it sooner.
+fn side_stepping<T: RangeBounds<u8> + 'static + Send + Sync>() -> SystemSet {
+    SystemSet::new()
+        .label(ShapeLerpSet::UpdateTarget)
+        .with_system(change_sides::<T>.label(System::ChangeSides))
+        .with_system(
+            validation::check_sides::<T>
+                .label(System::CheckSides)
+                .after(System::ChangeSides),
+        )
+        .with_system(
+            update_lerp_target::<T>
+                .label(System::UpdateLerpTarget)
+                .after(System::CheckSides),
+        )
+}
+
+// A shape drawn the way the config says, starting out as `path` and morphing toward `target`
+pub fn spawn_shape(
+    commands: &mut Commands,
+    config: &Config,
+    id: ShapeId,
+    translation: Vec3,
+    path: &Path,
+    target: Path,
+) -> Entity {
+    commands
+        .spawn_bundle(shape_bundle(config, translation, path))
+        .insert(id)
+        .insert(ShapeRng::for_shape(config.seed, id))
+        .insert(Lod::default())
+        .insert(MarginScale::default())
+        .insert(LerpingShape::new(target, config))
+        .insert(OnScreen::default())
+        .id()
+}
+
+// Drawn the way the config says, as `path`
+pub fn shape_bundle(config: &Config, translation: Vec3, path: &Path) -> ShapeBundle {
+    // The placeholder geometry is swapped for the prebuilt path below
+    let mut bundle = GeometryBuilder::build_as(
+        &shapes::Circle::default(),
+        DrawMode::Outlined {
+            fill_mode: FillMode::color(config.fill_color),
+            outline_mode: StrokeMode::new(config.outline_color, config.outline_width),
+        },
+        Transform::from_translation(translation),
+    );
+    bundle.path = PathComponent(path.clone());
+    bundle
+}
+
+// Looks up just the shapes that finished, rather than walking every SidesChangingShape
+fn change_sides<T: RangeBounds<u8> + 'static + Send + Sync>(
+    mut lerp_events: EventReader<LerpBatchFinished>,
+    mut stuck_events: EventWriter<SidesStuck>,
+    mut query: Query<&mut SidesChangingShape<T>, With<LerpingShape>>,
+) {
+    for LerpBatchFinished(entities) in lerp_events.iter() {
+        for &entity in entities {
+            if let Ok(mut sides) = query.get_mut(entity) {
+                if !sides.increment_sides() {
+                    stuck_events.send(SidesStuck(entity));
+                }
+            }
+        }
+    }
+}
+
+// Changes to the bounds or direction alone, or a step that bounced back to the same count,
+// leave the target as it is
+// Shapes held off screen are caught up when they come back into view
+fn update_lerp_target<T: RangeBounds<u8> + 'static + Send + Sync>(
+    config: Res<Config>,
+    mut query: Query<
+        (
+            &SidesChangingShape<T>,
+            &mut BuiltSides,
+            &mut LerpingShape,
+            Option<&OnScreen>,
+            Option<&mut ShapeRng>,
+        ),
+        Or<(Changed<SidesChangingShape<T>>, Changed<OnScreen>)>,
+    >,
+) {
+    for (sides, mut built, mut shape, on_screen, rng) in query.iter_mut() {
+        if built.sides == sides.sides && built.radius == sides.radius {
+            continue;
+        }
+        if on_screen.map_or(false, |on_screen| shape.offscreen.holds(on_screen)) {
+            continue;
+        }
+        let _span = info_span!("update_lerp_target", sides = sides.sides).entered();
+        built.sides = sides.sides;
+        built.radius = sides.radius;
+        let target = TargetShape::for_sides(sides.sides, sides.radius).build();
+        shape.target = match rng {
+            Some(mut rng) if config.target_noise > 0.0 => {
+                perturbed(&target, config.target_noise, &mut rng)
+            }
+            _ => target,
+        };
+    }
+}
+
+// Added to every LerpingShape automatically
+fn add_aligned_targets(
+    mut commands: Commands,
+    query: Query<Entity, (With<LerpingShape>, Without<AlignedTarget>)>,
+) {
+    for entity in query.iter() {
+        commands.entity(entity).insert(AlignedTarget::default());
+    }
+}
+
+// Done once per new target rather than every frame in `lerp_shape`
+// A snapped shape only morphs again once this lines up a different target for it than before, so
+// changing just its lerp params, or giving it the target it's at, doesn't finish it a second time.
+// A timeline starting a keyframe always gets it morphing, to finish the keyframe if it's already
+// there.
+fn align_targets(
+    mut commands: Commands,
+    config: Res<Config>,
+    mut previous: Local<Vec<PathEvent>>,
+    mut query: Query<
+        (
+            Entity,
+            &PathComponent,
+            &LerpingShape,
+            ChangeTrackers<LerpingShape>,
+            Option<&Lod>,
+            &mut AlignedTarget,
+            Option<&Snapped>,
+            Option<ChangeTrackers<LerpTimeline>>,
+        ),
+        Or<(Changed<LerpingShape>, Changed<Lod>)>,
+    >,
+) {
+    for (entity, path, shape, shape_tracker, lod, mut aligned, snapped, timeline) in
+        query.iter_mut()
+    {
+        let aligned = &mut *aligned;
+        if shape_tracker.is_changed() {
+            aligned.bounds = visibility::bounds(&shape.target);
+            aligned.simplified.clear();
+        }
+        aligned.level = lod.map_or(0, |lod| lod.level);
+        let target = if aligned.level > 0 {
+            let (size, level) = (aligned.size(), aligned.level);
+            &*aligned.simplified.entry(level).or_insert_with(|| {
+                lod::simplified(
+                    &shape.target,
+                    lod::tolerance(size, level, config.lod_threshold),
+                )
+            })
+        } else {
+            &shape.target
+        };
+        mem::swap(&mut *previous, &mut aligned.events);
+        aligned.padding = aligned_events(&path.0, target, &mut aligned.events);
+        let retargeted = *previous != aligned.events;
+        if snapped.is_some() && (retargeted || timeline.map_or(false, |t| t.is_changed())) {
+            commands.entity(entity).remove::<Snapped>();
+        }
+    }
+}
+
+// Caps how many path events `lerp_shape` works through in a frame. Over budget, shapes take
+// turns in entity order, so each morphs every few frames instead of the frame time spiking.
+pub struct LerpBudget {
+    pub max_events_per_frame: usize,
+}
+
+// Moves morphs that go by time (timed, eased and spring ones, timelines, pulses) on by this much
+// every frame instead of by how long the frame really took, so they play out the same every run
+pub struct FixedDelta(pub Duration);
+
+// How far time-based morphs move on this frame
+pub fn frame_delta(time: &Time, fixed: Option<&FixedDelta>) -> Duration {
+    fixed.map_or_else(|| time.delta(), |fixed| fixed.0)
+}
+
+// The shapes to lerp this frame given each one's size, or None for all of them.
+// `last` is the shape the previous over-budget frame ended on, and is moved on.
+fn budgeted_shapes(
+    max_events: usize,
+    last: &mut Option<Entity>,
+    mut sizes: Vec<(Entity, usize)>,
+) -> Option<HashSet<Entity>> {
+    if sizes.iter().map(|&(_, events)| events).sum::<usize>() <= max_events {
+        return None;
+    }
+    sizes.sort_unstable_by_key(|&(entity, _)| entity);
+    let start = last.map_or(0, |last| {
+        sizes.partition_point(|&(entity, _)| entity <= last)
+    });
+    let mut selected = HashSet::default();
+    let mut spent = 0;
+    for &(entity, events) in sizes.iter().cycle().skip(start).take(sizes.len()) {
+        // Always at least one, even if it's bigger than the whole budget
+        if !selected.is_empty() && spent + events > max_events {
+            break;
+        }
+        spent += events;
+        selected.insert(entity);
+        *last = Some(entity);
+    }
+    Some(selected)
+}
+
+// Big enough that a handful of shapes don't pay for splitting the work across threads
+const LERP_BATCH_SIZE: usize = 16;
+
+// Skips lerping entirely while paused, or when every shape has already snapped
+fn anything_to_lerp(
+    paused: Res<Paused>,
+    morphing: Query<(), (With<LerpingShape>, Without<Snapped>)>,
+) -> ShouldRun {
+    if !paused.0 && morphing.iter().next().is_some() {
+        ShouldRun::Yes
+    } else {
+        ShouldRun::No
+    }
+}
+
+fn lerp_shape(
+    mut commands: Commands,
+    time: Res<Time>,
+    fixed_delta: Option<Res<FixedDelta>>,
+    pool: Res<ComputeTaskPool>,
+    config: Res<Config>,
+    mut stats: ResMut<LerpStats>,
+    budget: Option<Res<LerpBudget>>,
+    mut last_budgeted: Local<Option<Entity>>,
+    mut lerp_events: EventWriter<LerpBatchFinished>,
+    mut query: Query<
+        (
+            Entity,
+            &mut PathComponent,
+            &LerpingShape,
+            &AlignedTarget,
+            Option<&OnScreen>,
+            Option<&MarginScale>,
+            Option<&GroupPace>,
+            Option<&mut MorphOrigin>,
+            Option<&LerpGradient>,
+            Option<&mut SpringLerp>,
+        ),
+        Without<Snapped>,
+    >,
+    sequenced: Query<Entity, With<LerpTimeline>>,
+) {
+    let start = Instant::now();
+    let selected = budget.and_then(|budget| {
+        let sizes = query
+            .iter_mut()
+            .map(|(entity, _, _, aligned, _, _, _, _, _, _)| (entity, aligned.events.len()))
+            .collect();
+        budgeted_shapes(budget.max_events_per_frame, &mut last_budgeted, sizes)
+    });
+    let counters = &*stats;
+    let finished = Mutex::new(Vec::new());
+    let already_there = Mutex::new(Vec::new());
+    let (same_target, snap_epsilon) = (config.same_target, config.snap_epsilon);
+    let delta = frame_delta(&time, fixed_delta.as_deref());
+    query.par_for_each_mut(
+        &pool,
+        LERP_BATCH_SIZE,
+        |(
+            entity,
+            mut from,
+            to,
+            aligned,
+            on_screen,
+            margin_scale,
+            pace,
+            mut origin,
+            gradient,
+            spring,
+        )| {
+            if on_screen.map_or(false, |on_screen| to.offscreen.holds(on_screen)) {
+                return;
+            }
+            if selected
+                .as_ref()
+                .map_or(false, |selected| !selected.contains(&entity))
+            {
+                return;
+            }
+            let _span = info_span!("lerp_shape", ?entity).entered();
+            counters.add(aligned.events.len());
+            let t = pace.map_or(to.lerp_t, |pace| pace.0);
+            let margin = to.margin(margin_scale, snap_epsilon);
+            let target = aligned.target(&to.target);
+            let (is_within_margin_of_error, new_path) = match spring {
+                // Springs go by the time passed, not by lerp_t, an origin or a gradient
+                Some(mut spring) => sprung(
+                    &from.0,
+                    target,
+                    &aligned.events,
+                    to.lerp_t,
+                    &mut spring,
+                    margin,
+                    delta.as_secs_f32(),
+                ),
+                None => {
+                    // From an origin there's one progress for the whole path, it can't go faster in parts
+                    let gradient = gradient.filter(|_| origin.is_none());
+                    // From the origin it's a sample of the morph at the progress made, not a step
+                    let (start, t, margin) = match (&mut origin, to.duration) {
+                        (Some(origin), Some(duration)) => {
+                            let progress = origin.advance_over(delta, duration);
+                            // Snapping exactly when the time's up, however close it gets before then
+                            let margin = if progress == Vec2::ONE {
+                                f32::INFINITY
+                            } else {
+                                f32::NEG_INFINITY
+                            };
+                            let eased = origin.eased_to(to.eased_progress(progress));
+                            (origin.path(), eased, margin)
+                        }
+                        (Some(origin), None) => {
+                            let progress = origin.advance(t);
+                            let eased = origin.eased_to(to.eased_progress(progress));
+                            (origin.path(), eased, margin)
+                        }
+                        // Its origin is snapshotted a frame late, it waits for that
+                        (None, _) if to.needs_origin() => return,
+                        (None, _) => (&from.0, t, margin),
+                    };
+                    match gradient {
+                        Some(gradient) => {
+                            gradient_lerped(start, target, &aligned.events, t, gradient, margin)
+                        }
+                        None => with_scratch_events(|buffer| {
+                            lerped_aligned(
+                                start,
+                                target,
+                                &aligned.events,
+                                aligned.padding,
+                                t,
+                                margin,
+                                buffer,
+                            )
+                        }),
+                    }
+                }
+            };
+            // Writing marks the path changed, which has it re-tessellated even if it's identical
+            let moved = !from.0.iter().eq(new_path.iter());
+            if moved {
+                from.0 = new_path;
+            }
+            if is_within_margin_of_error {
+                if moved || same_target == SameTargetPolicy::Finish {
+                    finished.lock().unwrap().push(entity);
+                } else {
+                    already_there.lock().unwrap().push(entity);
+                }
+            }
+        },
+    );
+    stats.elapsed = start.elapsed();
+    // Batches finish in any order, sorting keeps the events the same from run to run
+    let mut finished = finished.into_inner().unwrap();
+    let (sequenced, already_there): (Vec<_>, Vec<_>) = already_there
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .partition(|&entity| sequenced.get(entity).is_ok());
+    finished.extend(sequenced);
+    finished.sort();
+    for entity in already_there {
+        commands.entity(entity).insert(Snapped);
+    }
+    if finished.is_empty() {
+        return;
+    }
+    for &entity in &finished {
+        commands.entity(entity).insert(Snapped);
+    }
+    lerp_events.send(LerpBatchFinished(finished));
+}
+
+fn count_frames(mut frame: ResMut<FrameCount>) {
+    frame.0 += 1;
+}
@@ -0,0 +1,216 @@
+use crate::{cli::Args, demo_config::DemoConfig};
+use bevy::asset::{AssetLoader, LoadContext, LoadState, LoadedAsset};
+use bevy::prelude::*;
+use bevy::reflect::TypeUuid;
+use bevy::tasks::{AsyncComputeTaskPool, Task};
+use bevy::utils::BoxedFuture;
+use bevy_prototype_lyon::prelude::*;
+use futures_lite::future;
+use serde::Deserialize;
+use shape_lerping::echo::MorphEcho;
+use shape_lerping::fit::FitTarget;
+use shape_lerping::group::{GroupId, MorphGroup};
+use shape_lerping::origin::{LerpMode, MorphOrigin};
+use shape_lerping::ripple::MorphRipple;
+use shape_lerping::{
+    config::Config, spawn_shape, target::TargetShape, BuiltSides, ShapeId, SidesChangingShape,
+};
+use tess::path::Path;
+
+pub const EXTENSION: &str = "shapes.ron";
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum AppState {
+    // The config's shapes or the shape library are still loading, whichever's done is out
+    Loading,
+    Running,
+}
+
+#[derive(Deserialize)]
+struct ShapeDefinition {
+    target: TargetShape,
+    position: Vec2,
+}
+
+// More shapes to spawn alongside the config's, with their targets already built
+#[derive(TypeUuid)]
+#[uuid = "6d1c2f0e-93b4-4c57-a8e2-5b7f10c4d39a"]
+pub struct ShapeLibrary {
+    shapes: Vec<(Path, Vec2)>,
+}
+
+#[derive(Default)]
+pub struct ShapeLibraryLoader;
+
+// Building the targets here keeps big SVGs off the main thread, the asset server runs loaders
+// on its own task pool
+impl AssetLoader for ShapeLibraryLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+        Box::pin(async move {
+            let definitions: Vec<ShapeDefinition> = ron::de::from_bytes(bytes)?;
+            let shapes = definitions
+                .iter()
+                .map(|definition| (definition.target.build(), definition.position))
+                .collect();
+            load_context.set_default_asset(LoadedAsset::new(ShapeLibrary { shapes }));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &[EXTENSION]
+    }
+}
+
+// What's still to be built before every shape is out
+pub struct Loading {
+    // The target the config's shapes start out at, built on the async compute pool
+    config_target: Option<Task<Path>>,
+    library: Option<Handle<ShapeLibrary>>,
+}
+
+pub fn start_loading(
+    mut commands: Commands,
+    pool: Res<AsyncComputeTaskPool>,
+    asset_server: Option<Res<AssetServer>>,
+    config: Res<Config>,
+    args: Res<Args>,
+) {
+    // A recipe or comparison brings its own shapes
+    let config_target = if args.recipe.is_some() || args.compare {
+        None
+    } else {
+        let shape = TargetShape::for_sides(config.sides, config.radius);
+        Some(pool.spawn(async move { shape.build() }))
+    };
+    let library = match (&args.library, asset_server) {
+        (Some(path), Some(asset_server)) => Some(asset_server.load(path.as_path())),
+        _ => None,
+    };
+    commands.insert_resource(Loading {
+        config_target,
+        library,
+    });
+}
+
+// Spawns each set of shapes as soon as it's ready, and moves on once they all are
+pub fn spawn_when_loaded(
+    mut commands: Commands,
+    config: Res<Config>,
+    demo: Res<DemoConfig>,
+    args: Res<Args>,
+    asset_server: Option<Res<AssetServer>>,
+    libraries: Option<Res<Assets<ShapeLibrary>>>,
+    mut loading: ResMut<Loading>,
+    mut state: ResMut<State<AppState>>,
+) {
+    let loading = &mut *loading;
+    if let Some(task) = &mut loading.config_target {
+        // Deterministic runs wait, so the shapes come out on the same frame every run
+        let target = if args.deterministic {
+            Some(future::block_on(task))
+        } else {
+            future::block_on(future::poll_once(task))
+        };
+        if let Some(target) = target {
+            spawn_config_shapes(&mut commands, &config, &demo, &target);
+            loading.config_target = None;
+        }
+    }
+    if let (Some(handle), Some(asset_server)) = (&loading.library, asset_server) {
+        match asset_server.get_load_state(handle) {
+            LoadState::Loaded => {
+                if let Some(library) = libraries
+                    .as_ref()
+                    .and_then(|libraries| libraries.get(handle))
+                {
+                    spawn_library(&mut commands, &config, &demo, library);
+                }
+                loading.library = None;
+            }
+            LoadState::Failed => {
+                error!("Failed to load the shape library, running without it");
+                loading.library = None;
+            }
+            _ => {}
+        }
+    }
+    if loading.config_target.is_none() && loading.library.is_none() {
+        let _ = state.set(AppState::Running);
+    }
+}
+
+// A row of shapes that step through side counts, all starting out at `target`
+fn spawn_config_shapes(commands: &mut Commands, config: &Config, demo: &DemoConfig, target: &Path) {
+    let first_x = -(demo.entity_count.saturating_sub(1) as f32) * demo.spacing / 2.0;
+    for i in 0..demo.entity_count {
+        let translation = Vec3::new(first_x + i as f32 * demo.spacing, 0.0, 0.0);
+        let entity = spawn_shape(
+            commands,
+            config,
+            ShapeId(i as u32),
+            translation,
+            target,
+            target.clone(),
+        );
+        commands
+            .entity(entity)
+            .insert(SidesChangingShape::new(
+                config.sides,
+                config.radius,
+                config.bounds.clone(),
+            ))
+            .insert(BuiltSides::new(config.sides, config.radius));
+        if demo.grouped {
+            commands.entity(entity).insert(MorphGroup(GroupId(0)));
+        }
+        if config.echo_delay > 0 {
+            commands.entity(entity).insert(MorphEcho {
+                delay_frames: config.echo_delay,
+            });
+        }
+        if config.ripple_rings > 0 {
+            commands.entity(entity).insert(MorphRipple {
+                rings: config.ripple_rings,
+                spacing: config.ripple_spacing,
+                delay_frames: config.ripple_delay,
+            });
+        }
+        if let Some(fit) = config.target_fit {
+            commands.entity(entity).insert(FitTarget::new(fit));
+        }
+        match config.lerp_mode {
+            LerpMode::Feedback => {}
+            LerpMode::Origin => {
+                commands.entity(entity).insert(MorphOrigin::new());
+            }
+            LerpMode::Spring => {
+                commands.entity(entity).insert(config.spring.clone());
+            }
+        }
+    }
+}
+
+// Each library shape starts out as the config's first target and morphs into its own
+fn spawn_library(
+    commands: &mut Commands,
+    config: &Config,
+    demo: &DemoConfig,
+    library: &ShapeLibrary,
+) {
+    let start = TargetShape::for_sides(config.sides, config.radius).build();
+    for (i, (target, position)) in library.shapes.iter().enumerate() {
+        spawn_shape(
+            commands,
+            config,
+            ShapeId((demo.entity_count + i) as u32),
+            position.extend(0.0),
+            &start,
+            target.clone(),
+        );
+    }
+}
@@ -0,0 +1,87 @@
+// Holds some of a shape's events where they are while the rest morph, like a speech bubble's body
+// staying put as its tail wiggles. The ranges index the target's events, so they can be picked
+// out with the target's EventMetadata too. Each new target has its locked events moved to where
+// the events of the path they line up with already are, so lerping leaves them there and the
+// shape still snaps once the rest arrive.
+use crate::path_lerping::{build_path, subpath_lengths};
+use crate::LerpingShape;
+use bevy::prelude::*;
+use bevy_prototype_lyon::entity::Path as PathComponent;
+use bevy_prototype_lyon::prelude::*;
+use std::{mem, ops::Range};
+use tess::path::{Event, Path};
+
+#[derive(Component, Clone, Debug, Default, PartialEq, Eq)]
+pub struct LockedEvents(pub Vec<Range<usize>>);
+
+impl LockedEvents {
+    // The runs of events whose data `locked` picks out
+    pub fn matching<M>(data: &[M], locked: impl Fn(&M) -> bool) -> Self {
+        let mut ranges: Vec<Range<usize>> = Vec::new();
+        for (i, datum) in data.iter().enumerate() {
+            if !locked(datum) {
+                continue;
+            }
+            match ranges.last_mut() {
+                Some(range) if range.end == i => range.end += 1,
+                _ => ranges.push(i..i + 1),
+            }
+        }
+        Self(ranges)
+    }
+
+    fn contains(&self, i: usize) -> bool {
+        self.0.iter().any(|range| range.contains(&i))
+    }
+}
+
+// `target` with its locked events where `path` has them, lined up the way lerping pads them.
+// Paths with different numbers of subpaths don't line up event for event, so nothing's locked.
+pub fn locked_target(path: &Path, target: &Path, locked: &LockedEvents) -> Path {
+    let (path_subpaths, target_subpaths) = (subpath_lengths(path), subpath_lengths(target));
+    if path_subpaths.len() != target_subpaths.len() {
+        return target.clone();
+    }
+    let held = path.iter().collect::<Vec<_>>();
+    let (path_count, target_count) = (held.len(), target_subpaths.iter().sum::<usize>());
+    let events = target
+        .iter()
+        .enumerate()
+        .map(|(i, event)| {
+            // The padding goes after the first event when it's the target that's short, and
+            // before everything when it's the path
+            let lined_up = if path_count > target_count && i > 0 {
+                Some(i + path_count - target_count)
+            } else {
+                i.checked_sub(target_count.saturating_sub(path_count))
+            };
+            match lined_up.and_then(|i| held.get(i)) {
+                // Ends are left be, they only say whether the subpath's closed
+                Some(&held)
+                    if locked.contains(i)
+                        && !matches!(event, Event::End { .. })
+                        && mem::discriminant(&held) == mem::discriminant(&event) =>
+                {
+                    held
+                }
+                _ => event,
+            }
+        })
+        .collect::<Vec<_>>();
+    build_path(&events)
+}
+
+pub fn lock_events(
+    mut shapes: Query<
+        (&PathComponent, &LockedEvents, &mut LerpingShape),
+        Or<(Changed<LerpingShape>, Changed<LockedEvents>)>,
+    >,
+) {
+    for (path, locked, mut shape) in shapes.iter_mut() {
+        let target = locked_target(&path.0, &shape.target, locked);
+        // Once locked there's nothing to change, and the target's left unmarked
+        if !target.iter().eq(shape.target.iter()) {
+            shape.target = target;
+        }
+    }
+}
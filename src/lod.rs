@@ -0,0 +1,85 @@
+use crate::{config::Config, AlignedTarget, MainCamera};
+use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::*;
+use tess::{
+    math::Point,
+    path::{iterator::PathIterator, Event, Path},
+};
+
+// Each level halves the on-screen size the shape is treated as having, past the last the detail
+// lost is under a pixel anyway
+const MAX_LEVEL: u8 = 6;
+
+// How detailed a shape's target is. Level 0 is the full target, higher levels are coarser
+// simplifications of it, for shapes that are too small on screen for the detail to show.
+#[derive(Component, Default)]
+pub struct Lod {
+    pub level: u8,
+}
+
+// Picks each shape's level from how big its target appears through the main camera.
+// Only writes on an actual change of level, as that gets the shape realigned and morphing again.
+pub fn update_lod(
+    config: Res<Config>,
+    cameras: Query<&OrthographicProjection, With<MainCamera>>,
+    mut shapes: Query<(&GlobalTransform, &AlignedTarget, &mut Lod)>,
+) {
+    let scale = match cameras.iter().next() {
+        Some(projection) => projection.scale,
+        None => return,
+    };
+    for (transform, aligned, mut lod) in shapes.iter_mut() {
+        let pixels = aligned.size() * transform.scale.max_element() / scale;
+        let level = level_for(pixels, config.lod_threshold);
+        if lod.level != level {
+            lod.level = level;
+        }
+    }
+}
+
+fn level_for(pixels: f32, threshold: f32) -> u8 {
+    // Nothing measured yet, or LOD is off
+    if pixels <= 0.0 || threshold <= 0.0 || pixels >= threshold {
+        return 0;
+    }
+    ((threshold / pixels).log2().ceil() as u8).clamp(1, MAX_LEVEL)
+}
+
+// How far, in the target's local space, a level's simplified outline may stray from the full one:
+// about a pixel at the largest on-screen size the level covers
+pub fn tolerance(size: f32, level: u8, threshold: f32) -> f32 {
+    size * (1 << level) as f32 / threshold
+}
+
+// Flattens the path's curves and drops vertices within `tolerance` of the one before,
+// so small shapes have fewer events to lerp and triangles to tessellate
+pub fn simplified(path: &Path, tolerance: f32) -> Path {
+    let mut builder = Path::builder();
+    let mut points: Vec<Point> = Vec::new();
+    for event in path.iter().flattened(tolerance) {
+        match event {
+            Event::Begin { at } => {
+                points.clear();
+                points.push(at);
+            }
+            Event::Line { to, .. } => {
+                if points
+                    .last()
+                    .map_or(true, |last| last.distance_to(to) >= tolerance)
+                {
+                    points.push(to);
+                }
+            }
+            Event::End { close, .. } => {
+                builder.begin(points[0]);
+                for &point in &points[1..] {
+                    builder.line_to(point);
+                }
+                builder.end(close);
+            }
+            // Flattening leaves nothing but lines
+            Event::Quadratic { .. } | Event::Cubic { .. } => {}
+        }
+    }
+    builder.build()
+}
@@ -1,173 +1,428 @@
-mod path_lerping;
+// The demo app, built on ShapeLerpPlugin like any other app would be
+mod cli;
+mod compare;
+mod demo_config;
+mod idle;
+mod input;
+mod library;
+mod overlay;
+mod palette;
+mod preview;
+mod slideshow;
+mod teaching;
 
-use crate::path_lerping::Lerp;
-use bevy::prelude::*;
+use crate::cli::Args;
+use crate::demo_config::{ConfigWatcher, DemoConfig};
+use crate::input::{CursorPosition, Dragging};
+use crate::palette::ActivePalette;
+use bevy::{
+    app::AppExit,
+    diagnostic::{DiagnosticsPlugin, FrameTimeDiagnosticsPlugin, LogDiagnosticsPlugin},
+    log::LogPlugin,
+    prelude::*,
+    transform::TransformPlugin,
+};
 use bevy_prototype_lyon::entity::Path as PathComponent;
 use bevy_prototype_lyon::prelude::*;
-use std::ops::{Add, RangeBounds, RangeInclusive, Sub};
-use tess::path::Path;
+use clap::Parser;
+#[cfg(feature = "audio")]
+use shape_lerping::audio;
+use shape_lerping::config::{self, Config};
+use shape_lerping::export::{self, Export};
+#[cfg(feature = "midi")]
+use shape_lerping::midi;
+#[cfg(feature = "net")]
+use shape_lerping::net;
+#[cfg(feature = "osc")]
+use shape_lerping::osc;
+#[cfg(feature = "scripting")]
+use shape_lerping::scripting;
+use shape_lerping::target::PathData;
+#[cfg(feature = "websocket")]
+use shape_lerping::websocket;
+use shape_lerping::{
+    async_tessellation, control, diagnostics, lod, margin, recipe, replay, tolerance, ui,
+    visibility,
+};
+use shape_lerping::{
+    FixedDelta, FrameCount, LerpBudget, MainCamera, ShapeId, ShapeLerpPlugin, ShapeLerpSet, System,
+};
+use std::{fs, ops::RangeInclusive, time::Duration};
 
-enum Direction {
-    Increasing,
-    Decreasing,
-}
+struct FrameLimit(u64);
+
+fn main() {
+    let args = Args::parse();
+    let mut config = Config::load(&args.config);
+    let mut demo: DemoConfig = config::load(&args.config);
+    args.apply(&mut config, &mut demo);
+
+    let mut app = App::new();
+    app.insert_resource(ActivePalette(demo.palette))
+        .insert_resource(config)
+        .insert_resource(demo)
+        .insert_resource(ConfigWatcher::new(args.config.clone()))
+        .add_plugin(ShapeLerpPlugin)
+        .add_startup_system(setup)
+        .add_state(library::AppState::Loading)
+        .add_startup_system(library::start_loading)
+        .add_system_set(
+            SystemSet::on_update(library::AppState::Loading)
+                .with_system(library::spawn_when_loaded),
+        )
+        .add_startup_system(diagnostics::register_diagnostics)
+        .add_system(
+            demo_config::reload_config
+                .label(System::ReloadConfig)
+                .before(ShapeLerpSet::UpdateTarget),
+        )
+        .add_system(
+            palette::apply_palette
+                .label(System::ApplyPalette)
+                .after(System::ReloadConfig),
+        )
+        .add_system(diagnostics::report_lerp_stats.after(ShapeLerpSet::Lerp));
 
-impl Direction {
-    fn inverted(&self) -> Self {
-        match self {
-            Direction::Increasing => Direction::Decreasing,
-            Direction::Decreasing => Direction::Increasing,
+    #[cfg(feature = "scripting")]
+    if let Some(path) = &args.script {
+        match scripting::Script::load(path) {
+            Ok(script) => {
+                app.insert_resource(script).add_system(
+                    scripting::run_script_callbacks
+                        .label(System::Script)
+                        .after(ShapeLerpSet::Lerp),
+                );
+            }
+            Err(e) => eprintln!("{}, running without it", e),
         }
     }
 
-    fn invert(&mut self) {
-        *self = self.inverted();
+    #[cfg(feature = "osc")]
+    if let Some(addr) = args.osc {
+        let sender = app
+            .world
+            .get_resource::<control::MorphCommands>()
+            .unwrap()
+            .sender();
+        if let Err(e) = osc::listen(addr, sender) {
+            eprintln!("Failed to listen for OSC on {}: {}", addr, e);
+        }
     }
 
-    fn get_operation<T: Add<Rhs, Output = Output> + Sub<Rhs, Output = Output>, Rhs, Output>(
-        &self,
-    ) -> &dyn Fn(T, Rhs) -> Output {
-        match self {
-            Direction::Increasing => &Add::add,
-            Direction::Decreasing => &Sub::sub,
+    #[cfg(feature = "midi")]
+    if args.midi {
+        let sender = app
+            .world
+            .get_resource::<control::MorphCommands>()
+            .unwrap()
+            .sender();
+        match midi::connect(args.midi_port.as_deref(), sender) {
+            Ok(connection) => {
+                app.insert_non_send_resource(connection);
+            }
+            Err(e) => eprintln!("{}, running without MIDI input", e),
         }
     }
-}
 
-#[derive(Component)]
-struct SidesChangingShape<T: RangeBounds<u8>> {
-    sides: u8,
-    bounds: T,
-    direction: Direction,
-}
+    #[cfg(feature = "audio")]
+    if args.audio {
+        match audio::record_default_input() {
+            Ok((stream, samples)) => {
+                app.insert_non_send_resource(stream)
+                    .insert_resource(samples)
+                    .init_resource::<audio::AudioAnalysis>()
+                    .add_system(audio::analyze_audio.label(System::AnalyzeAudio));
+                if args.ambient {
+                    app.add_system(audio::start_ambient_mode).add_system(
+                        audio::blend_by_amplitude
+                            .after(System::AnalyzeAudio)
+                            .after(System::ApplyCommands)
+                            .before(System::FitTargets),
+                    );
+                } else {
+                    app.add_system(
+                        audio::react_to_audio
+                            .after(System::AnalyzeAudio)
+                            .before(System::ApplyCommands),
+                    );
+                }
+            }
+            Err(e) => eprintln!("{}, running without audio input", e),
+        }
+    }
 
-impl<T: RangeBounds<u8>> SidesChangingShape<T> {
-    fn increment_sides(&mut self) {
-        let op = self.direction.get_operation();
-        let new_sides = op(self.sides, 1);
-        if self.bounds.contains(&new_sides) {
-            self.sides = new_sides;
-        } else {
-            self.direction.invert();
-            self.increment_sides();
+    #[cfg(feature = "net")]
+    if let Some(addr) = args.serve {
+        match net::NetServer::listen(addr) {
+            Ok(server) => {
+                app.insert_resource(server)
+                    .add_system(net::broadcast_shapes.after(ShapeLerpSet::Lerp));
+            }
+            Err(e) => eprintln!("Failed to serve on {}: {}", addr, e),
+        }
+    }
+    #[cfg(feature = "net")]
+    if let Some(addr) = args.connect {
+        match net::NetClient::connect(addr) {
+            Ok(client) => {
+                app.insert_resource(client)
+                    .add_system(control::stop_changing_sides)
+                    .add_system(
+                        net::apply_shape_updates
+                            .after(System::ApplyCommands)
+                            .before(System::FitTargets),
+                    );
+            }
+            Err(e) => eprintln!("Failed to connect to {}: {}", addr, e),
         }
     }
-}
 
-#[derive(Component)]
-struct LerpingShape {
-    target: Path,
-    lerp_t: f32,
-    margin_of_error: f32,
-}
+    #[cfg(feature = "websocket")]
+    if let Some(addr) = args.websocket {
+        match websocket::RemoteRequests::listen(addr) {
+            Ok(requests) => {
+                app.insert_resource(requests)
+                    .add_system(websocket::handle_remote_requests.before(System::ApplyCommands));
+            }
+            Err(e) => eprintln!("Failed to listen for WebSockets on {}: {}", addr, e),
+        }
+    }
 
-// Event for when all points of a LerpingShape are within the margin-of-error of the target path
-struct LerpFinished(Entity);
+    if let Some(path) = &args.record {
+        match replay::Recorder::create(path) {
+            Ok(recorder) => {
+                app.insert_resource(recorder)
+                    .add_system(replay::record_changes.after(ShapeLerpSet::Lerp));
+            }
+            Err(e) => eprintln!("{}, not recording", e),
+        }
+    }
+    if let Some(path) = &args.replay {
+        match replay::Replayer::load(path) {
+            Ok(replayer) => {
+                app.insert_resource(replayer)
+                    .add_system(control::stop_changing_sides)
+                    .add_system(
+                        replay::replay_changes
+                            .after(System::ApplyCommands)
+                            .before(System::FitTargets),
+                    );
+            }
+            Err(e) => eprintln!("{}, not replaying", e),
+        }
+    }
 
-#[derive(Clone, Copy, Hash, PartialEq, Eq, Debug, SystemLabel)]
-enum System {
-    ChangeSides,
-    UpdateLerpTarget,
-    LerpShape,
-}
+    if args.headless {
+        app.add_plugins(MinimalPlugins)
+            .add_plugin(TransformPlugin)
+            .add_plugin(DiagnosticsPlugin);
+        if args.diagnostics {
+            app.add_plugin(LogPlugin);
+        }
+    } else {
+        let window = app
+            .world
+            .get_resource::<DemoConfig>()
+            .unwrap()
+            .window
+            .clone();
+        app.insert_resource(Msaa { samples: 8 })
+            .insert_resource(overlay::descriptor(&window))
+            .insert_resource(overlay::clear_color(&window))
+            .add_plugins(DefaultPlugins)
+            .add_system(overlay::apply_window_config)
+            .add_system(palette::cycle_palette.before(System::ApplyPalette))
+            .add_system(
+                tolerance::update_tolerance
+                    .after(System::ApplyPalette)
+                    .after(ShapeLerpSet::Lerp),
+            )
+            .add_plugin(ShapePlugin)
+            .init_resource::<CursorPosition>()
+            .init_resource::<Dragging>()
+            .add_system(input::update_cursor_position.label(System::UpdateCursor))
+            .add_system(
+                input::grab_shape
+                    .label(System::Drag)
+                    .after(System::UpdateCursor),
+            )
+            .add_system(input::drag_shape.after(System::Drag))
+            .add_system(input::toggle_pause.before(ShapeLerpSet::Lerp))
+            .init_resource::<idle::Idle>()
+            .add_system(idle::detect_idle.before(ShapeLerpSet::Lerp))
+            .add_system(
+                idle::randomize_finished
+                    .after(System::ChangeSides)
+                    .before(System::CheckSides),
+            )
+            .add_system(lod::update_lod.before(System::AlignTargets))
+            .add_system(margin::update_margin_scale.before(ShapeLerpSet::Lerp))
+            .add_system(
+                visibility::update_on_screen
+                    .label(System::Cull)
+                    .before(System::UpdateLerpTarget),
+            )
+            .add_system(
+                visibility::snap_on_reentry
+                    .after(System::Cull)
+                    .after(System::AlignTargets)
+                    .before(ShapeLerpSet::Lerp),
+            )
+            .add_system(
+                input::scroll_radius::<RangeInclusive<u8>>
+                    .label(System::ScrollRadius)
+                    .after(System::UpdateCursor)
+                    .before(System::UpdateLerpTarget),
+            )
+            .add_system(ui::spawn_widget_backgrounds)
+            .add_system(
+                ui::sync_widget_backgrounds
+                    .after(System::ApplyCommands)
+                    .before(System::FitTargets),
+            )
+            .add_system(ui::morph_nine_slices.after(System::FitTargets));
+        if args.ui {
+            app.add_startup_system(ui::spawn_demo_button);
+        }
+    }
 
-fn main() {
-    App::new()
-        .insert_resource(Msaa { samples: 8 })
-        .add_plugins(DefaultPlugins)
-        .add_plugin(ShapePlugin)
-        .add_startup_system(setup)
-        .add_event::<LerpFinished>()
-        .add_system(change_sides::<RangeInclusive<u8>>.label(System::ChangeSides))
-        .add_system(
-            update_lerp_target::<RangeInclusive<u8>>
-                .label(System::UpdateLerpTarget)
-                .after(System::ChangeSides),
-        )
-        .add_system(
-            lerp_shape
-                .label(System::LerpShape)
-                .after(System::UpdateLerpTarget),
-        )
-        .run();
-}
+    if args.preview && !args.headless {
+        app.add_startup_system(preview::open_preview_window)
+            .add_system(preview::add_preview_pass)
+            .add_system(preview::sync_previews.after(ShapeLerpSet::UpdateTarget));
+    }
 
-fn setup(mut commands: Commands) {
-    const SIDES: u8 = 5;
+    if args.teach && !args.headless {
+        app.add_system(teaching::step_frame.before(ShapeLerpSet::Lerp))
+            .add_system(teaching::label_points.after(ShapeLerpSet::Lerp));
+    }
 
-    let shape = shapes::RegularPolygon {
-        sides: SIDES as usize,
-        feature: shapes::RegularPolygonFeature::Radius(200.0),
-        ..Default::default()
-    };
+    if args.async_tessellation && !args.headless {
+        app.add_system(async_tessellation::start_async_tessellation)
+            .add_system(async_tessellation::tessellate_async.after(ShapeLerpSet::Lerp));
+    }
 
-    commands.spawn_bundle(OrthographicCameraBundle::new_2d());
-    commands
-        .spawn_bundle(GeometryBuilder::build_as(
-            &shape,
-            DrawMode::Outlined {
-                fill_mode: FillMode::color(Color::ORANGE),
-                outline_mode: StrokeMode::new(Color::ORANGE_RED, 8.0),
-            },
-            Transform::default(),
-        ))
-        .insert(SidesChangingShape {
-            sides: SIDES,
-            bounds: 3..=8,
-            direction: Direction::Increasing,
-        })
-        .insert(LerpingShape {
-            target: ShapePath::build_as(&shape).0,
-            lerp_t: 0.025,
-            margin_of_error: 1.0,
+    if args.library.is_some() {
+        if args.headless {
+            app.add_plugin(AssetPlugin);
+        }
+        app.add_asset::<library::ShapeLibrary>()
+            .init_asset_loader::<library::ShapeLibraryLoader>();
+    }
+
+    if args.compare {
+        app.add_startup_system(compare::spawn_comparison)
+            .add_system(compare::compare_strategies.before(System::Export));
+    }
+
+    if let Some(path) = &args.recipe {
+        match recipe::Recipe::load(path) {
+            Ok(recipe) => {
+                app.insert_resource(recipe)
+                    .add_startup_system(recipe::spawn_recipe)
+                    .add_startup_system(slideshow::spawn_slideshow)
+                    .add_system(
+                        slideshow::play_slides
+                            .label(System::PlaySlides)
+                            .before(System::Export),
+                    );
+                if !args.headless {
+                    app.add_system(slideshow::advance_slides.before(System::PlaySlides));
+                }
+            }
+            Err(e) => eprintln!("{}, not playing it", e),
+        }
+    }
+
+    if let Some(dir) = args.export.clone() {
+        if let Err(e) = fs::create_dir_all(&dir) {
+            eprintln!("Failed to create export directory {}: {}", dir.display(), e);
+        }
+        app.insert_resource(Export { dir }).add_system(
+            export::export_frame
+                .label(System::Export)
+                .after(ShapeLerpSet::Lerp),
+        );
+    }
+
+    if let Some(max_events_per_frame) = args.max_events_per_frame {
+        app.insert_resource(LerpBudget {
+            max_events_per_frame,
         });
-}
+    }
 
-fn change_sides<T: RangeBounds<u8> + 'static + Send + Sync>(
-    mut lerp_events: EventReader<LerpFinished>,
-    mut query: Query<&mut SidesChangingShape<T>>,
-) {
-    for LerpFinished(entity) in lerp_events.iter() {
-        if let Ok(mut sides) = query.get_mut(*entity) {
-            sides.increment_sides();
+    if args.deterministic {
+        app.insert_resource(FixedDelta(Duration::from_secs(1) / 60));
+    }
+
+    if args.diagnostics {
+        app.add_plugin(FrameTimeDiagnosticsPlugin)
+            .add_plugin(LogDiagnosticsPlugin::default());
+    }
+
+    if let Some(frames) = args.frames {
+        app.insert_resource(FrameLimit(frames)).add_system_to_stage(
+            CoreStage::Last,
+            exit_after_frames.after(System::CountFrames),
+        );
+        if args.deterministic {
+            app.add_system_to_stage(CoreStage::Last, print_checksum.after(System::CountFrames));
         }
     }
+
+    app.insert_resource(args).run();
 }
 
-fn update_lerp_target<T: RangeBounds<u8> + 'static + Send + Sync>(
-    mut query: Query<(&SidesChangingShape<T>, &mut LerpingShape), Changed<SidesChangingShape<T>>>,
+// The shapes themselves come out once they're built, see `library::spawn_when_loaded`
+fn setup(mut commands: Commands) {
+    commands
+        .spawn_bundle(OrthographicCameraBundle::new_2d())
+        .insert(MainCamera);
+}
+
+// Identical between runs that morphed identically, so two can be compared by this alone
+fn print_checksum(
+    frame: Res<FrameCount>,
+    limit: Res<FrameLimit>,
+    shapes: Query<(&ShapeId, &PathComponent)>,
 ) {
-    for (sides, mut shape) in query.iter_mut() {
-        if sides.sides % 2 == 0 {
-            shape.target = ShapePath::build_as(&shapes::Ellipse {
-                radii: Vec2::new(
-                    (sides.sides as f32).sin() * 200.0,
-                    (sides.sides as f32).cos() * 200.0,
-                ),
-                ..Default::default()
-            })
-            .0;
-        } else {
-            shape.target = ShapePath::build_as(&shapes::RegularPolygon {
-                sides: sides.sides as usize,
-                feature: shapes::RegularPolygonFeature::Radius(200.0),
-                ..Default::default()
-            })
-            .0;
+    if frame.0 != limit.0 {
+        return;
+    }
+    let mut shapes = shapes.iter().collect::<Vec<_>>();
+    shapes.sort_by_key(|&(id, _)| id.0);
+    let mut hash = FNV_OFFSET;
+    for (id, path) in shapes {
+        hash = fnv1a(hash, &id.0.to_le_bytes());
+        let data = PathData::from(&path.0);
+        for &verb in &data.verbs {
+            hash = fnv1a(hash, &[verb as u8]);
+        }
+        for &[x, y] in &data.points {
+            hash = fnv1a(hash, &x.to_bits().to_le_bytes());
+            hash = fnv1a(hash, &y.to_bits().to_le_bytes());
         }
     }
+    println!("Checksum after {} frames: {:016x}", frame.0, hash);
 }
 
-fn lerp_shape(
-    mut lerp_events: EventWriter<LerpFinished>,
-    mut query: Query<(Entity, &mut PathComponent, &LerpingShape)>,
+// FNV-1a, which unlike std's hashers is fixed by its definition, so checksums can be compared
+// across builds and Rust versions too
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(FNV_PRIME)
+    })
+}
+
+fn exit_after_frames(
+    frame: Res<FrameCount>,
+    limit: Res<FrameLimit>,
+    mut exit: EventWriter<AppExit>,
 ) {
-    for (entity, mut from, to) in query.iter_mut() {
-        let (is_within_margin_of_error, new_path) =
-            from.0.lerped(&to.target, to.lerp_t, to.margin_of_error);
-        from.0 = new_path;
-        if is_within_margin_of_error {
-            lerp_events.send(LerpFinished(entity));
-        }
+    if frame.0 >= limit.0 {
+        exit.send(AppExit);
     }
 }
@@ -0,0 +1,59 @@
+use crate::{config::Config, MainCamera};
+use bevy::prelude::*;
+use serde::Deserialize;
+
+// What a shape's margin_of_error is measured in
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Deserialize)]
+pub enum MarginSpace {
+    // The shape's own local space, however big it looks
+    World,
+    // Logical pixels on screen, so zooming in doesn't make snapping pop and zooming out
+    // doesn't spend frames converging on differences too small to see
+    Screen,
+    // Physical pixels on screen, the same as Screen but for the window's DPI scale
+    Physical,
+}
+
+impl Default for MarginSpace {
+    fn default() -> Self {
+        Self::World
+    }
+}
+
+// How far in the shape's local space one unit of its margin_of_error reaches.
+// Kept out of LerpingShape, it changes with every zoom and that mustn't count as a new target.
+#[derive(Component)]
+pub struct MarginScale(pub f32);
+
+impl Default for MarginScale {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+// Assumes the camera isn't rotated, and takes the larger axis of non-uniformly scaled shapes
+pub fn update_margin_scale(
+    config: Res<Config>,
+    windows: Res<Windows>,
+    cameras: Query<&OrthographicProjection, With<MainCamera>>,
+    mut shapes: Query<(&GlobalTransform, &mut MarginScale)>,
+) {
+    let world_per_pixel = match (config.margin_space, cameras.iter().next()) {
+        (MarginSpace::World, _) | (_, None) => None,
+        (MarginSpace::Screen, Some(projection)) => Some(projection.scale),
+        (MarginSpace::Physical, Some(projection)) => {
+            let scale_factor = windows
+                .get_primary()
+                .map_or(1.0, |w| w.scale_factor() as f32);
+            Some(projection.scale / scale_factor)
+        }
+    };
+    for (transform, mut margin_scale) in shapes.iter_mut() {
+        let scale = world_per_pixel.map_or(1.0, |world_per_pixel| {
+            world_per_pixel / transform.scale.max_element()
+        });
+        if margin_scale.0 != scale {
+            margin_scale.0 = scale;
+        }
+    }
+}
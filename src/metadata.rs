@@ -0,0 +1,56 @@
+// User data for each event of a shape's target, like IDs, colours or weights, followed onto the
+// events of the shape's path as it morphs. Events are otherwise anonymous, and padding changes
+// how many there are mid-morph, so this is the only way to tell which corner is which.
+// Not added by ShapeLerpPlugin, as it's generic over the data: apps add
+// `carry_metadata::<M>.after(ShapeLerpSet::Lerp)` for each kind they use.
+use crate::path_lerping::{carried_by_subpath, subpath_lengths};
+use crate::LerpingShape;
+use bevy::prelude::*;
+use bevy_prototype_lyon::entity::Path as PathComponent;
+use bevy_prototype_lyon::prelude::*;
+use tess::path::Path;
+
+#[derive(Component)]
+pub struct EventMetadata<M: Send + Sync + 'static> {
+    // Replaced along with the shape's target, one for each of its events
+    pub target: Vec<M>,
+    path: Vec<M>,
+    // Of the path `path` is for, which is what the next lerp goes from
+    subpaths: Vec<usize>,
+}
+
+impl<M: Send + Sync + 'static> EventMetadata<M> {
+    // For a shape starting out as `path`, with `path_data` for each of its events
+    pub fn new(path: &Path, path_data: Vec<M>, target: Vec<M>) -> Self {
+        Self {
+            target,
+            path: path_data,
+            subpaths: subpath_lengths(path),
+        }
+    }
+
+    // One for each event of the shape's path as of the last lerp
+    pub fn path(&self) -> &[M] {
+        &self.path
+    }
+}
+
+pub fn carry_metadata<M: Clone + Default + Send + Sync + 'static>(
+    mut shapes: Query<
+        (&PathComponent, &LerpingShape, &mut EventMetadata<M>),
+        Changed<PathComponent>,
+    >,
+) {
+    for (path, shape, mut metadata) in shapes.iter_mut() {
+        let metadata = &mut *metadata;
+        let lerped = subpath_lengths(&path.0);
+        metadata.path = carried_by_subpath(
+            &metadata.subpaths,
+            &metadata.path,
+            &subpath_lengths(&shape.target),
+            &metadata.target,
+            &lerped,
+        );
+        metadata.subpaths = lerped;
+    }
+}
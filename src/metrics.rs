@@ -0,0 +1,87 @@
+// Measurements of paths. Curves are flattened to within `tolerance` first, so the results are
+// exact for polygons and as close as the tolerance for anything curved.
+use bevy_prototype_lyon::prelude::*;
+use tess::{
+    math::{point, Point},
+    path::{iterator::PathIterator, Event, Path},
+};
+
+fn cross(a: Point, b: Point) -> f32 {
+    a.x * b.y - a.y * b.x
+}
+
+// Every edge of the flattened path, counting each subpath's closing edge only if it's closed
+fn edges(path: &Path, tolerance: f32, closing_all: bool) -> Vec<(Point, Point)> {
+    path.iter()
+        .flattened(tolerance)
+        .filter_map(|event| match event {
+            Event::Line { from, to } => Some((from, to)),
+            Event::End { last, first, close } if close || closing_all => Some((last, first)),
+            _ => None,
+        })
+        .collect()
+}
+
+// Total length of the outline, the perimeter for closed paths
+pub fn length(path: &Path, tolerance: f32) -> f32 {
+    edges(path, tolerance, false)
+        .iter()
+        .map(|(from, to)| from.distance_to(*to))
+        .sum()
+}
+
+// Positive for counter-clockwise outlines (with y up), negative for clockwise ones.
+// Open subpaths count as if they were closed, and subpaths winding opposite ways cancel out.
+pub fn signed_area(path: &Path, tolerance: f32) -> f32 {
+    edges_area(edges(path, tolerance, true))
+}
+
+// The signed area of closed rings of edges, the same way round as `signed_area`
+pub fn edges_area(edges: impl IntoIterator<Item = (Point, Point)>) -> f32 {
+    edges
+        .into_iter()
+        .map(|(from, to)| cross(from, to))
+        .sum::<f32>()
+        / 2.0
+}
+
+// The centre of mass of the area enclosed, or the mean of the points for paths enclosing none,
+// like lines. The origin for empty paths.
+pub fn centroid(path: &Path, tolerance: f32) -> Point {
+    let edges = edges(path, tolerance, true);
+    let (sum, doubled_area) = edges
+        .iter()
+        .fold((Point::origin(), 0.0), |(sum, area), &(a, b)| {
+            let cross = cross(a, b);
+            (sum + (a.to_vector() + b.to_vector()) * cross, area + cross)
+        });
+    if doubled_area != 0.0 {
+        return (sum.to_vector() / (3.0 * doubled_area)).to_point();
+    }
+    let (sum, count) = path
+        .iter()
+        .filter(|event| !matches!(event, Event::End { .. }))
+        .fold((Point::origin(), 0), |(sum, count), event| {
+            (sum + event.to().to_vector(), count + 1)
+        });
+    if count == 0 {
+        sum
+    } else {
+        sum / count as f32
+    }
+}
+
+// (min, max) corners of the box around the flattened outline, both the origin for empty paths
+pub fn bounding_box(path: &Path, tolerance: f32) -> (Point, Point) {
+    let mut points = path.iter().flattened(tolerance).map(|event| event.to());
+    let first = match points.next() {
+        Some(first) => first,
+        None => return (Point::origin(), Point::origin()),
+    };
+    points.fold((first, first), |(min, max), p| {
+        (
+            point(min.x.min(p.x), min.y.min(p.y)),
+            point(max.x.max(p.x), max.y.max(p.y)),
+        )
+    })
+}
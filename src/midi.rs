@@ -0,0 +1,73 @@
+// Plays the shapes like an instrument: the pitch class of each note-on picks a side count,
+// the mod wheel sets how fast shapes morph and channel volume sets their radius
+use crate::control::MorphCommand;
+use midir::{MidiInput, MidiInputConnection};
+use std::sync::mpsc::Sender;
+
+const NOTE_ON: u8 = 0x90;
+const CONTROL_CHANGE: u8 = 0xB0;
+const MOD_WHEEL: u8 = 1;
+const CHANNEL_VOLUME: u8 = 7;
+
+// C plays a triangle, C# a square, and so on up to B
+const LOWEST_SIDES: u8 = 3;
+const LERP_T_RANGE: (f32, f32) = (0.005, 0.2);
+const RADIUS_RANGE: (f32, f32) = (50.0, 400.0);
+
+// Dropping the connection closes the port, and it isn't Send on every backend
+pub struct MidiConnection(MidiInputConnection<()>);
+
+// Connects to the first input port whose name contains `port`, or the first one at all.
+// Commands go out through `sender`, one of MorphCommands' own.
+pub fn connect(port: Option<&str>, sender: Sender<MorphCommand>) -> Result<MidiConnection, String> {
+    let input = MidiInput::new("shape-lerping").map_err(|e| e.to_string())?;
+    let ports = input.ports();
+    let port = ports
+        .iter()
+        .find(|p| {
+            let name = input.port_name(p).unwrap_or_default();
+            port.map_or(true, |port| name.contains(port))
+        })
+        .ok_or_else(|| "No matching MIDI input port".to_owned())?;
+    let name = input.port_name(port).unwrap_or_default();
+
+    let connection = input
+        .connect(
+            port,
+            "shape-lerping-input",
+            move |_, message, _| on_message(message, &sender),
+            (),
+        )
+        .map_err(|e| format!("Failed to connect to {}: {}", name, e))?;
+    Ok(MidiConnection(connection))
+}
+
+fn scale(value: u8, (min, max): (f32, f32)) -> f32 {
+    min + (max - min) * value as f32 / 127.0
+}
+
+fn on_message(message: &[u8], sender: &Sender<MorphCommand>) {
+    let entity = None;
+    let command = match *message {
+        // Note-on with zero velocity is a note-off
+        [status, note, velocity] if status & 0xF0 == NOTE_ON && velocity > 0 => {
+            MorphCommand::SetSides {
+                entity,
+                sides: LOWEST_SIDES + note % 12,
+            }
+        }
+        [status, MOD_WHEEL, value] if status & 0xF0 == CONTROL_CHANGE => MorphCommand::SetLerpT {
+            entity,
+            lerp_t: scale(value, LERP_T_RANGE),
+        },
+        [status, CHANNEL_VOLUME, value] if status & 0xF0 == CONTROL_CHANGE => {
+            MorphCommand::SetRadius {
+                entity,
+                radius: scale(value, RADIUS_RANGE),
+            }
+        }
+        _ => return,
+    };
+    // Only fails once the app has shut down
+    let _ = sender.send(command);
+}
@@ -0,0 +1,284 @@
+// Client/server mode: the server makes every targeting decision and sends clients whatever of each
+// shape's target and lerp params changes. Clients only lerp towards what they're sent, which
+// smooths over however unevenly the updates arrive.
+use crate::target::PathData;
+use crate::{LerpingShape, ShapeId};
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use serde::{Deserialize, Serialize};
+use std::{
+    io::{self, Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    sync::{
+        mpsc::{self, Receiver, SyncSender, TrySendError},
+        Arc, Mutex,
+    },
+    thread,
+};
+
+// Anything bigger than this is a corrupt stream rather than a real path
+const MAX_MESSAGE_SIZE: usize = 16 * 1024 * 1024;
+// Frames of updates a client can fall behind by before it's dropped, rather than held up for
+const CLIENT_BACKLOG: usize = 120;
+
+#[derive(Serialize, Deserialize)]
+enum TargetUpdate {
+    Whole(PathData),
+    // The same verbs as the shape's last target, so only the points are sent
+    Points(Vec<[f32; 2]>),
+}
+
+// Only the fields that changed since the last update for the shape are set, and a client that's
+// just joined gets every field of every shape
+#[derive(Serialize, Deserialize)]
+struct ShapeUpdate {
+    id: u32,
+    target: Option<TargetUpdate>,
+    lerp_t: Option<Vec2>,
+    margin_of_error: Option<f32>,
+}
+
+// What clients were last sent of a shape
+#[derive(Clone, PartialEq)]
+struct SentShape {
+    target: PathData,
+    lerp_t: Vec2,
+    margin_of_error: f32,
+}
+
+impl SentShape {
+    fn new(shape: &LerpingShape) -> Self {
+        Self {
+            target: PathData::from(&shape.target),
+            lerp_t: shape.lerp_t,
+            margin_of_error: shape.margin_of_error,
+        }
+    }
+}
+
+impl ShapeUpdate {
+    fn whole(id: u32, shape: &SentShape) -> Self {
+        Self {
+            id,
+            target: Some(TargetUpdate::Whole(shape.target.clone())),
+            lerp_t: Some(shape.lerp_t),
+            margin_of_error: Some(shape.margin_of_error),
+        }
+    }
+
+    // None if nothing clients were sent has changed
+    fn delta(id: u32, old: &SentShape, new: &SentShape) -> Option<Self> {
+        if old == new {
+            return None;
+        }
+        let target = if new.target == old.target {
+            None
+        } else if new.target.verbs == old.target.verbs {
+            Some(TargetUpdate::Points(new.target.points.clone()))
+        } else {
+            Some(TargetUpdate::Whole(new.target.clone()))
+        };
+        Some(Self {
+            id,
+            target,
+            lerp_t: Some(new.lerp_t).filter(|&lerp_t| lerp_t != old.lerp_t),
+            margin_of_error: Some(new.margin_of_error).filter(|&m| m != old.margin_of_error),
+        })
+    }
+
+    // Length-prefixed bincode
+    fn encode(&self, message: &mut Vec<u8>) {
+        let body = bincode::serialize(self).expect("Shape updates are always serializable");
+        message.extend((body.len() as u32).to_le_bytes().iter());
+        message.extend(body);
+    }
+
+    fn read(stream: &mut impl Read) -> io::Result<Self> {
+        let mut len = [0; 4];
+        stream.read_exact(&mut len)?;
+        let len = u32::from_le_bytes(len) as usize;
+        if len > MAX_MESSAGE_SIZE {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Message of {} bytes is too large", len),
+            ));
+        }
+        let mut body = vec![0; len];
+        stream.read_exact(&mut body)?;
+        bincode::deserialize(&body).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+// A connected client, written to from its own thread so a slow one can't hold up the frame
+struct Client {
+    addr: io::Result<SocketAddr>,
+    messages: SyncSender<Arc<[u8]>>,
+}
+
+impl Client {
+    fn new(mut stream: TcpStream) -> Self {
+        let addr = stream.peer_addr();
+        let (messages, outgoing) = mpsc::sync_channel::<Arc<[u8]>>(CLIENT_BACKLOG);
+        thread::spawn(move || {
+            for message in outgoing {
+                if stream.write_all(&message).is_err() {
+                    info!("Client {:?} disconnected", stream.peer_addr());
+                    return;
+                }
+            }
+        });
+        Self { addr, messages }
+    }
+
+    // False once the client's gone, or so far behind it's dropped
+    fn send(&self, message: &Arc<[u8]>) -> bool {
+        match self.messages.try_send(message.clone()) {
+            Ok(()) => true,
+            Err(TrySendError::Full(_)) => {
+                warn!("Client {:?} fell too far behind, dropping it", self.addr);
+                false
+            }
+            Err(TrySendError::Disconnected(_)) => false,
+        }
+    }
+}
+
+pub struct NetServer {
+    clients: Vec<Client>,
+    // Accepted but not yet sent the current state of every shape
+    joining: Arc<Mutex<Vec<Client>>>,
+    // By shape id
+    sent: HashMap<u32, SentShape>,
+}
+
+impl NetServer {
+    pub fn listen(addr: SocketAddr) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        let joining = Arc::<Mutex<Vec<Client>>>::default();
+        let accepted = joining.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                match stream {
+                    Ok(stream) => {
+                        // Updates are small and latency matters more than throughput
+                        let _ = stream.set_nodelay(true);
+                        info!("Client connected from {:?}", stream.peer_addr());
+                        accepted.lock().unwrap().push(Client::new(stream));
+                    }
+                    Err(e) => warn!("Failed to accept client: {}", e),
+                }
+            }
+        });
+        Ok(Self {
+            clients: Vec::new(),
+            joining,
+            sent: HashMap::default(),
+        })
+    }
+}
+
+pub fn broadcast_shapes(
+    mut server: ResMut<NetServer>,
+    changed: Query<(&ShapeId, &LerpingShape), Changed<LerpingShape>>,
+    all: Query<(&ShapeId, &LerpingShape)>,
+) {
+    let server = &mut *server;
+    let mut updates = Vec::new();
+    for (id, shape) in changed.iter() {
+        let new = SentShape::new(shape);
+        let update = match server.sent.get(&id.0) {
+            Some(old) => ShapeUpdate::delta(id.0, old, &new),
+            None => Some(ShapeUpdate::whole(id.0, &new)),
+        };
+        if let Some(update) = update {
+            update.encode(&mut updates);
+            server.sent.insert(id.0, new);
+        }
+    }
+    if !updates.is_empty() {
+        let updates = Arc::<[u8]>::from(updates);
+        server.clients.retain(|client| client.send(&updates));
+    }
+
+    // After this frame's updates, so they're already part of the state joining clients are sent
+    let joining = server.joining.lock().unwrap().drain(..).collect::<Vec<_>>();
+    if !joining.is_empty() {
+        let mut state = Vec::new();
+        for (id, shape) in all.iter() {
+            ShapeUpdate::whole(id.0, &SentShape::new(shape)).encode(&mut state);
+        }
+        let state = Arc::<[u8]>::from(state);
+        let joined = joining.into_iter().filter(|client| client.send(&state));
+        server.clients.extend(joined);
+    }
+}
+
+pub struct NetClient(Mutex<Receiver<ShapeUpdate>>);
+
+impl NetClient {
+    pub fn connect(addr: SocketAddr) -> io::Result<Self> {
+        let mut stream = TcpStream::connect(addr)?;
+        let _ = stream.set_nodelay(true);
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || loop {
+            match ShapeUpdate::read(&mut stream) {
+                Ok(update) => {
+                    if sender.send(update).is_err() {
+                        return;
+                    }
+                }
+                Err(e) => {
+                    error!("Lost connection to server: {}", e);
+                    return;
+                }
+            }
+        });
+        Ok(Self(Mutex::new(receiver)))
+    }
+}
+
+// `targets` keeps the last target each shape was sent, for updates that only send its points
+pub fn apply_shape_updates(
+    client: Res<NetClient>,
+    mut targets: Local<HashMap<u32, PathData>>,
+    mut shapes: Query<(&ShapeId, &mut LerpingShape)>,
+) {
+    for update in client.0.lock().unwrap().try_iter() {
+        let mut shape = match shapes.iter_mut().find(|(id, _)| id.0 == update.id) {
+            Some((_, shape)) => shape,
+            None => {
+                warn!("Server sent an update for unknown shape {}", update.id);
+                continue;
+            }
+        };
+        let target = match update.target {
+            Some(TargetUpdate::Whole(data)) => {
+                targets.insert(update.id, data);
+                targets.get(&update.id)
+            }
+            Some(TargetUpdate::Points(points)) => {
+                let data = targets.get_mut(&update.id);
+                if data.is_none() {
+                    warn!("Server sent points for shape {} before a target", update.id);
+                }
+                data.map(|data| {
+                    data.points = points;
+                    &*data
+                })
+            }
+            None => None,
+        };
+        if let Some(data) = target {
+            match data.to_path() {
+                Ok(target) => shape.target = target,
+                Err(e) => warn!("Server sent a bad target for shape {}: {}", update.id, e),
+            }
+        }
+        if let Some(lerp_t) = update.lerp_t {
+            shape.lerp_t = lerp_t;
+        }
+        if let Some(margin_of_error) = update.margin_of_error {
+            shape.margin_of_error = margin_of_error;
+        }
+    }
+}
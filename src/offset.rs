@@ -0,0 +1,240 @@
+// Paths grown outward or shrunk inward by a distance, like the concentric copies in a ripple.
+// Curves are flattened to within `tolerance` first, so the results are polygons, and open
+// subpaths count as if they were closed.
+use crate::boolean::{chained, outlines};
+use bevy_prototype_lyon::prelude::*;
+use std::cmp::Ordering;
+use tess::{
+    math::{Point, Vector},
+    path::Path,
+};
+
+// How the offset edges meet where they've pulled apart, around the outside of a corner. On the
+// inside of a corner they always meet at the miter point.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Join {
+    // Bevelled anyway where the miter would be too long
+    Miter,
+    Round,
+    Bevel,
+}
+
+// Joins sharper than this many offsets long are bevelled, like StrokeOptions' default
+const MITER_LIMIT: f32 = 4.0;
+
+// Points closer than this count as the same point, and offsets this much too close to the path
+// still count as far enough from it
+const EPSILON: f32 = 1e-3;
+
+type Edge = (Point, Point);
+
+// To the left of `direction`, anticlockwise with y up
+fn normal(direction: Vector) -> Vector {
+    let direction = direction.normalize();
+    Vector::new(-direction.y, direction.x)
+}
+
+fn rotated(v: Vector, angle: f32) -> Vector {
+    let (sin, cos) = angle.sin_cos();
+    Vector::new(v.x * cos - v.y * sin, v.x * sin + v.y * cos)
+}
+
+// The point or points `offset` to the left of a corner at `at`, between edges with the normals
+// `before` and `after`. Negative offsets are to the right.
+fn join(
+    at: Point,
+    (before, after): (Vector, Vector),
+    offset: f32,
+    join: Join,
+    tolerance: f32,
+    side: &mut Vec<Point>,
+) {
+    let miter = before + after;
+    let cos = miter.length() / 2.0;
+    // Whether the offset edges pull apart here, rather than crossing
+    let outside = before.cross(after) * offset < 0.0;
+    match join {
+        Join::Round if outside => {
+            let angle = before.cross(after).atan2(before.dot(after));
+            // Each step's chord strays no more than `tolerance` from the arc
+            let step = 2.0 * (1.0 - tolerance / offset.abs()).max(-1.0).acos();
+            let steps = (angle.abs() / step).ceil().max(1.0) as usize;
+            for i in 0..=steps {
+                side.push(at + rotated(before, angle * i as f32 / steps as f32) * offset);
+            }
+        }
+        Join::Bevel if outside => {
+            side.push(at + before * offset);
+            side.push(at + after * offset);
+        }
+        _ if cos * MITER_LIMIT < 1.0 => {
+            side.push(at + before * offset);
+            side.push(at + after * offset);
+        }
+        _ => side.push(at + miter.normalize() * (offset / cos)),
+    }
+}
+
+// The polyline offset to its left by `offset`, or to its right if it's negative. Open polylines
+// end square with their ends.
+pub fn offset_polyline(
+    points: &[Point],
+    closed: bool,
+    offset: f32,
+    join_with: Join,
+    tolerance: f32,
+) -> Vec<Point> {
+    let normals = points
+        .windows(2)
+        .map(|pair| normal(pair[1] - pair[0]))
+        .chain(closed.then(|| normal(points[0] - points[points.len() - 1])))
+        .collect::<Vec<_>>();
+    let mut side = Vec::with_capacity(points.len());
+    for (i, &at) in points.iter().enumerate() {
+        let before = match i.checked_sub(1) {
+            Some(before) => Some(before),
+            None if closed => Some(normals.len() - 1),
+            None => None,
+        };
+        match (before, normals.get(i)) {
+            (Some(before), Some(&after)) => join(
+                at,
+                (normals[before], after),
+                offset,
+                join_with,
+                tolerance,
+                &mut side,
+            ),
+            (None, Some(&after)) => side.push(at + after * offset),
+            (Some(before), None) => side.push(at + normals[before] * offset),
+            (None, None) => {}
+        }
+    }
+    side
+}
+
+fn distance_to_edge(p: Point, (from, to): Edge) -> f32 {
+    let edge = to - from;
+    let t = ((p - from).dot(edge) / edge.square_length())
+        .max(0.0)
+        .min(1.0);
+    p.distance_to(from + edge * t)
+}
+
+// The ring offset to its left, each edge with the ring's edges it was offset from: one for the
+// edges alongside them, both on either side of a corner for the edges joining those
+fn offset_edges(
+    ring: &[Point],
+    offset: f32,
+    join_with: Join,
+    tolerance: f32,
+) -> Vec<(Edge, [usize; 2])> {
+    let n = ring.len();
+    let normals = (0..n)
+        .map(|i| normal(ring[(i + 1) % n] - ring[i]))
+        .collect::<Vec<_>>();
+    let corners = (0..n)
+        .map(|i| {
+            let mut points = Vec::new();
+            let before = normals[(i + n - 1) % n];
+            join(
+                ring[i],
+                (before, normals[i]),
+                offset,
+                join_with,
+                tolerance,
+                &mut points,
+            );
+            points
+        })
+        .collect::<Vec<_>>();
+    let mut edges = Vec::new();
+    for (i, corner) in corners.iter().enumerate() {
+        for pair in corner.windows(2) {
+            edges.push(((pair[0], pair[1]), [(i + n - 1) % n, i]));
+        }
+        edges.push(((corner[corner.len() - 1], corners[(i + 1) % n][0]), [i, i]));
+    }
+    edges
+}
+
+// Where `edge` crosses any of `others`, as fractions of the way along it
+fn crossings(edge: Edge, others: impl Iterator<Item = Edge>) -> Vec<f32> {
+    let (p, r) = (edge.0, edge.1 - edge.0);
+    let r_epsilon = EPSILON / r.length();
+    others
+        .filter_map(|(c, d)| {
+            let s = d - c;
+            let denominator = r.cross(s);
+            if denominator.abs() <= 1e-6 * r.length() * s.length() {
+                return None;
+            }
+            let t = (c - p).cross(s) / denominator;
+            let u = (c - p).cross(r) / denominator;
+            let on_other = u >= -EPSILON / s.length() && u <= 1.0 + EPSILON / s.length();
+            (t > r_epsilon && t < 1.0 - r_epsilon && on_other).then(|| t)
+        })
+        .collect()
+}
+
+// `path` grown outward by `distance`, or shrunk inward if it's negative, with holes shrinking as
+// the outlines around them grow. Outlines shrunk away to nothing are left out, and outlines
+// grown into each other merge.
+pub fn offset(path: &Path, distance: f32, join: Join, tolerance: f32) -> Path {
+    let rings = outlines(path, tolerance);
+    let mut edges = Vec::new();
+    let mut offsets = Vec::new();
+    for ring in &rings {
+        let first = edges.len();
+        edges.extend(
+            ring.iter()
+                .zip(ring.iter().cycle().skip(1))
+                .map(|(&from, &to)| (from, to)),
+        );
+        // Outlines are anticlockwise, so their outside is to the right
+        offsets.extend(
+            offset_edges(ring, -distance, join, tolerance)
+                .into_iter()
+                .filter(|&((from, to), _)| from.distance_to(to) > EPSILON)
+                .map(|(edge, from)| (edge, [from[0] + first, from[1] + first])),
+        );
+    }
+
+    // Where the offsets fold back over themselves or run into each other they come closer to
+    // the path than `distance`, so those pieces are cut away, leaving only the true outline
+    let mut kept = Vec::new();
+    for (i, &((from, to), offset_from)) in offsets.iter().enumerate() {
+        let others = offsets
+            .iter()
+            .enumerate()
+            .filter(|&(j, _)| j != i)
+            .map(|(_, &(edge, _))| edge);
+        let mut splits = crossings((from, to), others);
+        splits.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        let mut last = from;
+        for at in splits.into_iter().map(|t| from.lerp(to, t)).chain(Some(to)) {
+            if last.distance_to(at) <= EPSILON {
+                continue;
+            }
+            let middle = last.lerp(at, 0.5);
+            let too_close = edges.iter().enumerate().any(|(j, &edge)| {
+                !offset_from.contains(&j)
+                    && distance_to_edge(middle, edge) < distance.abs() - EPSILON
+            });
+            if !too_close {
+                kept.push((last, at));
+            }
+            last = at;
+        }
+    }
+
+    let mut builder = Path::builder();
+    for ring in chained(kept) {
+        builder.begin(ring[0]);
+        for &point in &ring[1..] {
+            builder.line_to(point);
+        }
+        builder.end(true);
+    }
+    builder.build()
+}
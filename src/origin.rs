@@ -0,0 +1,273 @@
+// Morphs worked out between a snapshot of the path, taken as the target is given, and the
+// target itself, rather than by lerping whatever the path has become toward the target each
+// frame. Progress from the origin closes in on the target the way the path itself would, so
+// the two modes look the same, but with the origin kept a morph can be scrubbed through or
+// played at any pace.
+use crate::easing::Easing;
+use crate::path_lerping::{EventZipper, Lerp, ZipStrategy};
+use crate::{frame_delta, FixedDelta, LerpingShape, Snapped};
+use bevy::prelude::*;
+use bevy_prototype_lyon::entity::Path as PathComponent;
+use bevy_prototype_lyon::prelude::*;
+use serde::Deserialize;
+use std::time::Duration;
+use tess::path::Path;
+
+// How many progresses `matched_progress` tries before narrowing in on the first that matches
+const MATCH_STEPS: usize = 64;
+const MATCH_HALVINGS: usize = 16;
+// How far either side of a progress an easing's slope is measured over
+const SLOPE_STEP: f32 = 1e-3;
+// Progress eased any closer to the end than this is left out of matching, as an origin put back
+// behind the path for it would be put back further the closer it gets
+const MIN_LEFT: f32 = 0.01;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Deserialize)]
+pub enum LerpMode {
+    // Each frame the path closes lerp_t of its distance to the target
+    Feedback,
+    // Each frame the progress from the path's origin closes lerp_t of its distance to the target
+    Origin,
+    // Each point is pulled toward the target by a spring, with the config's `spring` settings
+    Spring,
+}
+
+impl Default for LerpMode {
+    fn default() -> Self {
+        Self::Feedback
+    }
+}
+
+// What becomes of the progress of a morph from an origin when the shape's given a new target
+// partway through it. Morphs the feedback way have no progress, and just carry on from the path.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Deserialize)]
+pub enum RetargetPolicy {
+    // The morph to the new target starts from the beginning, so an eased one slows down to start
+    // over and a timed one takes its full time again
+    Restart,
+    // The morph to the new target starts as far along as it has to for the path to keep moving
+    // as fast as it was, or as near as it gets, with its origin put back behind the path so the
+    // path doesn't jump ahead
+    MatchVelocity,
+}
+
+impl Default for RetargetPolicy {
+    fn default() -> Self {
+        Self::Restart
+    }
+}
+
+// Shapes with one of these morph from their origin, without one the feedback way
+#[derive(Component)]
+pub struct MorphOrigin {
+    path: Path,
+    // What the origin was taken for, so a new target can be told from other changes to the shape
+    target: Path,
+    // How far from the origin to the target the path is along each axis, 0 to 1.
+    // Can be set to scrub through the morph, `lerp_shape` picks up from wherever it's left.
+    // Morphs over a duration go by `elapsed` instead.
+    pub progress: Vec2,
+    pub elapsed: Duration,
+    // The eased progress as of the last frame, and how far it moved on that frame
+    eased: Vec2,
+    step: Vec2,
+}
+
+impl MorphOrigin {
+    pub fn new() -> Self {
+        Self {
+            path: Path::new(),
+            target: Path::new(),
+            progress: Vec2::ZERO,
+            elapsed: Duration::ZERO,
+            eased: Vec2::ZERO,
+            step: Vec2::ZERO,
+        }
+    }
+
+    fn snapshot(path: &Path, target: &Path) -> Self {
+        Self {
+            path: path.clone(),
+            target: target.clone(),
+            ..Self::new()
+        }
+    }
+
+    // For a morph that's already over, from the target to itself
+    fn finished(target: &Path, duration: Option<Duration>) -> Self {
+        Self {
+            progress: Vec2::ONE,
+            elapsed: duration.unwrap_or_default(),
+            eased: Vec2::ONE,
+            ..Self::snapshot(target, target)
+        }
+    }
+
+    // For morphing from `path` to the shape's target as far along as it has to be for the path to
+    // keep moving as fast as it was
+    fn matched(&self, path: &Path, shape: &LerpingShape, delta: Duration) -> Self {
+        let speed = self.step * span(&self.path, &self.target);
+        let new_span = span(path, &shape.target);
+        // How far along the morph the path moves in a frame at each progress, with the origin put
+        // back behind it to start from there
+        let speed_at = |progress: f32, span: f32, lerp_t: f32| {
+            let left = 1.0 - shape.easing.apply(progress);
+            if left < MIN_LEFT {
+                return f32::INFINITY;
+            }
+            let rate = match shape.duration {
+                Some(duration) => delta.as_secs_f32() / duration.as_secs_f32(),
+                None => (1.0 - progress) * lerp_t,
+            };
+            span * slope(shape.easing, progress) * rate / left
+        };
+        let mut progress = Vec2::new(
+            matched_progress(speed.x, |p| speed_at(p, new_span.x, shape.lerp_t.x)),
+            matched_progress(speed.y, |p| speed_at(p, new_span.y, shape.lerp_t.y)),
+        );
+        let mut elapsed = Duration::ZERO;
+        // Timed morphs are as far along either way, the axis that has to be furthest decides
+        if let Some(duration) = shape.duration {
+            progress = Vec2::splat(progress.max_element());
+            elapsed = duration.mul_f32(progress.x);
+        }
+        let eased = shape.eased_progress(progress);
+        let behind = -eased / (Vec2::ONE - eased);
+        Self {
+            path: path.lerped(&shape.target, behind, f32::NEG_INFINITY).1,
+            target: shape.target.clone(),
+            progress,
+            elapsed,
+            eased,
+            step: Vec2::ZERO,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    // Notes how far along the morph the path's being put, for matching its speed on a new target
+    pub fn eased_to(&mut self, eased: Vec2) -> Vec2 {
+        self.step = eased - self.eased;
+        self.eased = eased;
+        eased
+    }
+
+    // Moves progress along by a frame at `t`, the same way a path closes in on its target
+    pub fn advance(&mut self, t: Vec2) -> Vec2 {
+        self.progress += (Vec2::ONE - self.progress) * t;
+        self.progress
+    }
+
+    // Moves time along by `delta`, for a morph that takes `duration`
+    pub fn advance_over(&mut self, delta: Duration, duration: Duration) -> Vec2 {
+        self.elapsed += delta;
+        let progress = if self.elapsed >= duration {
+            1.0
+        } else {
+            self.elapsed.as_secs_f32() / duration.as_secs_f32()
+        };
+        self.progress = Vec2::splat(progress);
+        self.progress
+    }
+}
+
+impl Default for MorphOrigin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Runs once the targets are final for the frame, the path is where the new morph starts from.
+// Shapes that morph over a duration or eased get an origin whether they have one or not.
+pub fn snapshot_origins(
+    mut commands: Commands,
+    time: Res<Time>,
+    fixed_delta: Option<Res<FixedDelta>>,
+    mut shapes: Query<
+        (
+            Entity,
+            &PathComponent,
+            &LerpingShape,
+            Option<&mut MorphOrigin>,
+            Option<&Snapped>,
+        ),
+        Or<(Changed<LerpingShape>, Added<MorphOrigin>)>,
+    >,
+) {
+    for (entity, path, shape, origin, snapped) in shapes.iter_mut() {
+        match origin {
+            // A snapped shape given the target it's at again has nothing to morph. Left as it
+            // was, a longer duration would carry on the old morph, back by its origin.
+            Some(mut origin)
+                if snapped.is_some() && origin.target.iter().eq(shape.target.iter()) =>
+            {
+                *origin = MorphOrigin::finished(&shape.target, shape.duration);
+            }
+            Some(mut origin) if !origin.target.iter().eq(shape.target.iter()) => {
+                // A snapped shape's already stopped, there's no speed to keep up
+                *origin = if shape.retarget == RetargetPolicy::MatchVelocity && snapped.is_none() {
+                    origin.matched(&path.0, shape, frame_delta(&time, fixed_delta.as_deref()))
+                } else {
+                    MorphOrigin::snapshot(&path.0, &shape.target)
+                };
+            }
+            None if shape.needs_origin() => {
+                let origin = MorphOrigin::snapshot(&path.0, &shape.target);
+                commands.entity(entity).insert(origin);
+            }
+            _ => {}
+        }
+    }
+}
+
+// The furthest any point has to go along each axis to get from `from` to `to`, paired up the way
+// lerping pairs them
+fn span(from: &Path, to: &Path) -> Vec2 {
+    EventZipper::new(from, to, ZipStrategy::Padded).fold(Vec2::ZERO, |span, (from, to)| {
+        let distance = to.to() - from.to();
+        span.max(Vec2::new(distance.x.abs(), distance.y.abs()))
+    })
+}
+
+// How fast the easing's going at `progress`
+fn slope(easing: Easing, progress: f32) -> f32 {
+    let before = (progress - SLOPE_STEP).max(0.0);
+    let after = (progress + SLOPE_STEP).min(1.0);
+    (easing.apply(after) - easing.apply(before)) / (after - before)
+}
+
+// The first progress, 0 to 1, at which a morph going `speed_at` each progress goes at `speed`,
+// or the one it gets nearest at if it never does. The first, so a morph that keeps speeding up
+// isn't started any further along than it has to be.
+pub fn matched_progress(speed: f32, speed_at: impl Fn(f32) -> f32) -> f32 {
+    let miss = |progress: f32| speed_at(progress) - speed;
+    let mut last = (0.0, miss(0.0));
+    let mut nearest = (0.0, last.1.abs());
+    if last.1 == 0.0 {
+        return 0.0;
+    }
+    for i in 1..MATCH_STEPS {
+        let progress = i as f32 / MATCH_STEPS as f32;
+        let missed = miss(progress);
+        if (missed > 0.0) != (last.1 > 0.0) {
+            // Kept on the side before the match, which is sure to be a speed it can go at
+            let (mut before, mut after) = (last.0, progress);
+            for _ in 0..MATCH_HALVINGS {
+                let middle = (before + after) / 2.0;
+                if (miss(middle) > 0.0) == (last.1 > 0.0) {
+                    before = middle;
+                } else {
+                    after = middle;
+                }
+            }
+            return before;
+        }
+        if missed.abs() < nearest.1 {
+            nearest = (progress, missed.abs());
+        }
+        last = (progress, missed);
+    }
+    nearest.0
+}
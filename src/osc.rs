@@ -0,0 +1,118 @@
+// Listens for OSC messages on a UDP socket and turns them into morph commands for every shape:
+//   /shape/sides  <int|float>
+//   /shape/radius <float>
+//   /shape/lerp_t <float>
+//   /shape/margin <float>
+//   /shape/target <int|float>                       same as /shape/sides
+//   /shape/target <svg path string> <width> <height>
+use crate::control::MorphCommand;
+use crate::target::TargetShape;
+use bevy::prelude::*;
+use rosc::{OscMessage, OscPacket, OscType};
+use std::{
+    net::{SocketAddr, UdpSocket},
+    sync::mpsc::Sender,
+    thread,
+};
+
+// rosc recommends a buffer this large, enough for any single UDP datagram
+const BUFFER_SIZE: usize = rosc::decoder::MTU;
+
+// Commands go out through `sender`, one of MorphCommands' own
+pub fn listen(addr: SocketAddr, sender: Sender<MorphCommand>) -> std::io::Result<()> {
+    let socket = UdpSocket::bind(addr)?;
+    thread::spawn(move || receive(socket, sender));
+    Ok(())
+}
+
+fn receive(socket: UdpSocket, sender: Sender<MorphCommand>) {
+    let mut buf = [0; BUFFER_SIZE];
+    loop {
+        let size = match socket.recv(&mut buf) {
+            Ok(size) => size,
+            Err(e) => {
+                error!("OSC socket failed: {}", e);
+                return;
+            }
+        };
+        match rosc::decoder::decode(&buf[..size]) {
+            Ok(packet) => {
+                if !send_packet(packet, &sender) {
+                    // The app has shut down
+                    return;
+                }
+            }
+            Err(e) => warn!("Ignoring malformed OSC packet: {:?}", e),
+        }
+    }
+}
+
+fn send_packet(packet: OscPacket, sender: &Sender<MorphCommand>) -> bool {
+    match packet {
+        OscPacket::Message(message) => match to_command(&message) {
+            Some(command) => sender.send(command).is_ok(),
+            None => {
+                warn!(
+                    "Ignoring unknown OSC message {} {:?}",
+                    message.addr, message.args
+                );
+                true
+            }
+        },
+        OscPacket::Bundle(bundle) => bundle
+            .content
+            .into_iter()
+            .all(|packet| send_packet(packet, sender)),
+    }
+}
+
+fn number(arg: &OscType) -> Option<f32> {
+    match *arg {
+        OscType::Int(i) => Some(i as f32),
+        OscType::Long(i) => Some(i as f32),
+        OscType::Float(f) => Some(f),
+        OscType::Double(f) => Some(f as f32),
+        _ => None,
+    }
+}
+
+fn to_command(message: &OscMessage) -> Option<MorphCommand> {
+    let entity = None;
+    let first = message.args.get(0);
+    let value = first.and_then(number);
+    Some(match message.addr.as_str() {
+        "/shape/sides" => MorphCommand::SetSides {
+            entity,
+            sides: value?.round().clamp(0.0, u8::MAX as f32) as u8,
+        },
+        "/shape/radius" => MorphCommand::SetRadius {
+            entity,
+            radius: value?,
+        },
+        "/shape/lerp_t" => MorphCommand::SetLerpT {
+            entity,
+            lerp_t: value?,
+        },
+        "/shape/margin" => MorphCommand::SetMarginOfError {
+            entity,
+            margin_of_error: value?,
+        },
+        "/shape/target" => match first? {
+            OscType::String(path) => MorphCommand::SetTarget {
+                entity,
+                target: TargetShape::Svg {
+                    path: path.clone(),
+                    size: Vec2::new(
+                        message.args.get(1).and_then(number)?,
+                        message.args.get(2).and_then(number)?,
+                    ),
+                },
+            },
+            _ => MorphCommand::SetSides {
+                entity,
+                sides: value?.round().clamp(0.0, u8::MAX as f32) as u8,
+            },
+        },
+        _ => return None,
+    })
+}
@@ -0,0 +1,54 @@
+// Window settings for running the shapes as a desktop overlay: a transparent, undecorated window
+// kept above the others. Only read at startup.
+use crate::demo_config::DemoConfig;
+use bevy::prelude::*;
+use bevy::winit::WinitWindows;
+use serde::Deserialize;
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct WindowConfig {
+    pub transparent: bool,
+    pub always_on_top: bool,
+    pub undecorated: bool,
+}
+
+// Set up before the plugins, the window is created from it
+pub fn descriptor(config: &WindowConfig) -> WindowDescriptor {
+    WindowDescriptor {
+        transparent: config.transparent,
+        decorations: !config.undecorated,
+        ..Default::default()
+    }
+}
+
+pub fn clear_color(config: &WindowConfig) -> ClearColor {
+    if config.transparent {
+        ClearColor(Color::NONE)
+    } else {
+        ClearColor::default()
+    }
+}
+
+// The rest isn't part of WindowDescriptor, so it's set on the winit window once there is one
+pub fn apply_window_config(
+    demo: Res<DemoConfig>,
+    windows: Res<Windows>,
+    winit_windows: Res<WinitWindows>,
+    mut applied: Local<bool>,
+) {
+    if *applied {
+        return;
+    }
+    let window = match windows
+        .get_primary()
+        .and_then(|window| winit_windows.get_window(window.id()))
+    {
+        Some(window) => window,
+        None => return,
+    };
+    *applied = true;
+    if demo.window.always_on_top {
+        window.set_always_on_top(true);
+    }
+}
@@ -0,0 +1,159 @@
+// Curated colour sets for the shapes, picked to stay distinguishable with each kind of colour
+// blindness (mostly from the Okabe-Ito set). Shapes take a palette's colours in turn by id.
+// The demo config picks the starting palette, C cycles through them at runtime.
+use crate::demo_config::DemoConfig;
+use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::*;
+use serde::Deserialize;
+use shape_lerping::async_tessellation::{set_mode, AsyncTessellation};
+use shape_lerping::crossfade::ModeTransition;
+use shape_lerping::{config::Config, ShapeId};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Deserialize)]
+pub enum Palette {
+    // The config's own fill and outline colours
+    Normal,
+    Deuteranopia,
+    Protanopia,
+    Tritanopia,
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self::Normal
+    }
+}
+
+// Outlines are their fill, darkened by this much
+const OUTLINE_SHADE: f32 = 0.6;
+
+const DEUTERANOPIA: &[(u8, u8, u8)] = &[
+    (0xe6, 0x9f, 0x00),
+    (0x56, 0xb4, 0xe9),
+    (0xf0, 0xe4, 0x42),
+    (0x00, 0x72, 0xb2),
+];
+const PROTANOPIA: &[(u8, u8, u8)] = &[
+    (0x00, 0x72, 0xb2),
+    (0xf0, 0xe4, 0x42),
+    (0x56, 0xb4, 0xe9),
+    (0x99, 0x99, 0x99),
+];
+const TRITANOPIA: &[(u8, u8, u8)] = &[
+    (0xd5, 0x5e, 0x00),
+    (0x00, 0x9e, 0x73),
+    (0xcc, 0x79, 0xa7),
+    (0x99, 0x99, 0x99),
+];
+
+impl Palette {
+    // Fill and outline pairs
+    pub fn colors(self, config: &Config) -> Vec<(Color, Color)> {
+        let fills = match self {
+            Self::Normal => return vec![(config.fill_color, config.outline_color)],
+            Self::Deuteranopia => DEUTERANOPIA,
+            Self::Protanopia => PROTANOPIA,
+            Self::Tritanopia => TRITANOPIA,
+        };
+        fills
+            .iter()
+            .map(|&(r, g, b)| {
+                let fill = Color::rgb_u8(r, g, b);
+                let shade = |c: u8| (c as f32 * OUTLINE_SHADE) as u8;
+                (fill, Color::rgb_u8(shade(r), shade(g), shade(b)))
+            })
+            .collect()
+    }
+
+    fn next(self) -> Self {
+        match self {
+            Self::Normal => Self::Deuteranopia,
+            Self::Deuteranopia => Self::Protanopia,
+            Self::Protanopia => Self::Tritanopia,
+            Self::Tritanopia => Self::Normal,
+        }
+    }
+}
+
+pub struct ActivePalette(pub Palette);
+
+pub fn draw_mode((fill, outline): (Color, Color), config: &Config) -> DrawMode {
+    DrawMode::Outlined {
+        fill_mode: FillMode::color(fill),
+        outline_mode: StrokeMode::new(outline, config.outline_width),
+    }
+}
+
+// `mode` in the new colours, still only filled or only stroked if it was. Strokes keep their
+// alpha, so faded ones stay faded.
+pub fn recolored(mode: &DrawMode, colors: (Color, Color), config: &Config) -> DrawMode {
+    let (fill, mut outline) = colors;
+    match *mode {
+        DrawMode::Fill(fill_mode) => DrawMode::Fill(FillMode {
+            color: fill,
+            ..fill_mode
+        }),
+        DrawMode::Stroke(stroke_mode) => {
+            outline.set_a(stroke_mode.color.a());
+            DrawMode::Stroke(StrokeMode {
+                color: outline,
+                ..stroke_mode
+            })
+        }
+        DrawMode::Outlined { .. } => draw_mode(colors, config),
+    }
+}
+
+// Recolours the shape's DrawMode, wherever it is
+pub fn recolor(
+    mode: Option<Mut<DrawMode>>,
+    tessellation: Option<Mut<AsyncTessellation>>,
+    colors: (Color, Color),
+    config: &Config,
+) {
+    let current = match (&mode, &tessellation) {
+        (Some(mode), _) => **mode,
+        (None, Some(tessellation)) => *tessellation.mode(),
+        (None, None) => return,
+    };
+    set_mode(mode, tessellation, recolored(&current, colors, config));
+}
+
+pub fn cycle_palette(keys: Res<Input<KeyCode>>, mut palette: ResMut<ActivePalette>) {
+    if keys.just_pressed(KeyCode::C) {
+        palette.0 = palette.0.next();
+        info!("Using the {:?} palette", palette.0);
+    }
+}
+
+// Recolours every shape whenever the palette or config changes, or shapes are added.
+// Shapes part way through a crossfade are left to finish it.
+pub fn apply_palette(
+    config: Res<Config>,
+    demo: Res<DemoConfig>,
+    mut palette: ResMut<ActivePalette>,
+    mut configured: Local<Option<Palette>>,
+    added: Query<(), Added<ShapeId>>,
+    mut shapes: Query<
+        (
+            &ShapeId,
+            Option<&mut DrawMode>,
+            Option<&mut AsyncTessellation>,
+        ),
+        Without<ModeTransition>,
+    >,
+) {
+    // A reload only switches palettes if it's the config's palette that changed
+    if *configured != Some(demo.palette) {
+        *configured = Some(demo.palette);
+        palette.0 = demo.palette;
+    }
+    if !palette.is_changed() && !config.is_changed() && added.iter().next().is_none() {
+        return;
+    }
+    let colors = palette.0.colors(&config);
+    for (id, mode, tessellation) in shapes.iter_mut() {
+        let pair = colors[id.0 as usize % colors.len()];
+        recolor(mode, tessellation, pair, &config);
+    }
+}
@@ -1,21 +1,30 @@
+use bevy::log::{info_span, warn};
+use bevy::math::Vec2;
 use bevy_prototype_lyon::prelude::*;
-use std::{
-    cmp::Ordering,
-    iter::{self, FromIterator},
-};
+use std::{cell::RefCell, cmp::Ordering, fmt};
 use tess::{
-    math::Point,
-    path::{Event, Path, PathEvent},
+    math::{point, Point},
+    path::{
+        iterator::PathIterator,
+        path::{Builder, Iter},
+        Event, Path, PathEvent,
+    },
 };
 
+// `t` is how far toward `other` to go along each axis, for morphs that converge faster one way
 pub trait Lerp<T = Self, U = Self> {
-    fn lerped(self, other: T, t: f32, p: f32) -> (bool, U);
+    fn lerped(self, other: T, t: Vec2, p: f32) -> (bool, U);
 }
 
 impl Lerp for Point {
-    fn lerped(self, other: Self, t: f32, p: f32) -> (bool, Self) {
-        let mut result = self.lerp(other, t);
+    fn lerped(self, other: Self, t: Vec2, p: f32) -> (bool, Self) {
+        // Same arithmetic as `Point::lerp`, so equal axes give bit for bit the same result
+        let mut result = point(
+            (1.0 - t.x) * self.x + t.x * other.x,
+            (1.0 - t.y) * self.y + t.y * other.y,
+        );
         let snapped = result.distance_to(other) <= p;
+        // Exactly the target, not just near it, so snapped paths compare equal to their targets
         if snapped {
             result = other;
         }
@@ -24,14 +33,14 @@ impl Lerp for Point {
 }
 
 impl Lerp for PathEvent {
-    fn lerped(self, mut other: Self, t: f32, p: f32) -> (bool, Self) {
+    fn lerped(self, mut other: Self, t: Vec2, p: f32) -> (bool, Self) {
         fn lerp_other(
             from_from: Point,
             from_to: Point,
             from_ctrl: Point,
             from_ctrl2: Point,
             other: PathEvent,
-            t: f32,
+            t: Vec2,
             p: f32,
         ) -> (bool, PathEvent) {
             match other {
@@ -221,70 +230,884 @@ impl Lerp for PathEvent {
     }
 }
 
+thread_local! {
+    // One per thread rather than one per call or per shape, so scenes with many shapes share
+    // a handful of allocations between them, sized for the biggest path each thread has seen
+    static SCRATCH_EVENTS: RefCell<Vec<PathEvent>> = RefCell::new(Vec::new());
+}
+
+// Runs `f` with this thread's scratch buffer for a lerp's intermediate events.
+// Not reentrant, `f` mustn't call it again.
+pub fn with_scratch_events<R>(f: impl FnOnce(&mut Vec<PathEvent>) -> R) -> R {
+    SCRATCH_EVENTS.with(|events| f(&mut events.borrow_mut()))
+}
+
 impl Lerp<Self, Path> for &Path {
-    fn lerped(self, other: Self, t: f32, p: f32) -> (bool, Path) {
-        match self.iter().count().cmp(&other.iter().count()) {
-            Ordering::Equal => lerp_equal_sides(self, other, t, p),
-            Ordering::Less => lerp_less_sides(self, other, t, p),
-            Ordering::Greater => lerp_greater_sides(self, other, t, p),
+    fn lerped(self, other: Self, t: Vec2, p: f32) -> (bool, Path) {
+        with_scratch_events(|buffer| lerped_with_buffer(self, other, t, p, buffer))
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct LerpOptions {
+    pub t: Vec2,
+    pub margin_of_error: f32,
+}
+
+// What a step of a morph did, on top of the path it made
+#[derive(Clone, Debug)]
+pub struct LerpOutcome {
+    pub path: Path,
+    pub snapped: bool,
+    // How many events were lerped, and how many of those landed on their targets
+    pub events: usize,
+    pub snapped_events: usize,
+    // The difference in event counts between the two paths, made up by padding
+    pub padding: usize,
+}
+
+// The error-aware counterpart to `Lerp for &Path`, for paths that come from outside
+pub trait TryLerp {
+    fn try_lerped(&self, other: &Path, options: LerpOptions) -> Result<LerpOutcome, MorphError>;
+}
+
+// Errors on non-finite points in either path, or a target `check` rejects; an empty or
+// zero-length source is fine, that's just a morph growing out of a point. Besides the
+// returned path, the only allocation is a buffer for the events lerped into it.
+impl TryLerp for Path {
+    fn try_lerped(&self, other: &Path, options: LerpOptions) -> Result<LerpOutcome, MorphError> {
+        if let Err(error @ MorphError::NonFinite { .. }) = check(self) {
+            return Err(error);
+        }
+        check(other)?;
+        let mut buffer = Vec::new();
+        with_scratch_events(|aligned| {
+            let padding = aligned_events(self, other, aligned);
+            let (snapped, path) = lerped_aligned(
+                self,
+                other,
+                aligned,
+                padding,
+                options.t,
+                options.margin_of_error,
+                &mut buffer,
+            );
+            // Either way `buffer` lines up with `aligned` event for event, except with
+            // mismatched subpaths where nothing's aligned and none are counted
+            let snapped_events = buffer
+                .iter()
+                .zip(aligned.iter())
+                .filter(|(event, target)| event == target)
+                .count();
+            let (from_count, to_count) = (self.iter().count(), other.iter().count());
+            Ok(LerpOutcome {
+                path,
+                snapped,
+                events: buffer.len(),
+                snapped_events,
+                padding: from_count.max(to_count) - from_count.min(to_count),
+            })
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct SampleOptions {
+    // Return `from` and `to` themselves at t = 0 and 1, rather than padded to each other's size
+    pub exact_endpoints: bool,
+    // Keep t within 0 and 1, rather than extrapolating past either end
+    pub clamp_t: bool,
+}
+
+impl Default for SampleOptions {
+    fn default() -> Self {
+        Self {
+            exact_endpoints: true,
+            clamp_t: true,
+        }
+    }
+}
+
+// The morph from `from` to `to` at `t` along it, independent of any step before it, for
+// scrubbing through a morph or exporting its keyframes. Nothing snaps, a point that's a hair
+// off its target at t stays a hair off.
+pub fn sample_morph(from: &Path, to: &Path, t: f32, options: SampleOptions) -> Path {
+    let t = if options.clamp_t {
+        t.clamp(0.0, 1.0)
+    } else {
+        t
+    };
+    if options.exact_endpoints {
+        if t == 0.0 {
+            return from.clone();
+        }
+        if t == 1.0 {
+            return to.clone();
+        }
+    }
+    // No distance is within a margin of -infinity
+    with_scratch_events(|buffer| {
+        lerped_with_buffer(from, to, Vec2::splat(t), f32::NEG_INFINITY, buffer).1
+    })
+}
+
+// `count` points spaced evenly by distance along the path's outline, with curves flattened to
+// within `tolerance`. Subpaths are walked one after another as if they were joined up.
+pub fn resample(path: &Path, count: usize, tolerance: f32) -> Vec<Point> {
+    let segments = path
+        .iter()
+        .flattened(tolerance)
+        .filter_map(|event| match event {
+            Event::Line { from, to } => Some((from, to)),
+            Event::End {
+                last,
+                first,
+                close: true,
+            } => Some((last, first)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let total = segments
+        .iter()
+        .map(|(from, to)| from.distance_to(*to))
+        .sum::<f32>();
+    let start = match path.iter().next() {
+        Some(event) => event.from(),
+        None => return Vec::new(),
+    };
+    if total == 0.0 {
+        return vec![start; count];
+    }
+
+    let mut points = Vec::with_capacity(count);
+    let mut segments = segments.iter();
+    let (mut from, mut to) = (start, start);
+    // How far along the outline `from` is
+    let mut walked = 0.0;
+    for i in 0..count {
+        let at = total * i as f32 / count as f32;
+        while walked + from.distance_to(to) < at {
+            match segments.next() {
+                Some(&(next_from, next_to)) => {
+                    walked += from.distance_to(to);
+                    from = next_from;
+                    to = next_to;
+                }
+                // Float error in the total, the rest is at the very end
+                None => break,
+            }
         }
+        let length = from.distance_to(to);
+        let along = if length > 0.0 {
+            ((at - walked) / length).min(1.0)
+        } else {
+            0.0
+        };
+        points.push(from.lerp(to, along));
     }
+    points
 }
 
-fn lerp_equal_sides<T, U>(from: T, to: U, t: f32, p: f32) -> (bool, Path)
+// The morph from `from` to `to` at `t`, between `count` points resampled evenly along each,
+// instead of between their events. Always a single closed polygon, whatever the two paths are.
+pub fn sample_resampled(from: &Path, to: &Path, t: f32, count: usize, tolerance: f32) -> Path {
+    let from = resample(from, count, tolerance);
+    let to = resample(to, count, tolerance);
+    let mut builder = Path::builder();
+    let mut points = from.iter().zip(&to).map(|(a, b)| a.lerp(*b, t));
+    if let Some(first) = points.next() {
+        builder.begin(first);
+        for point in points {
+            builder.line_to(point);
+        }
+        builder.end(true);
+    }
+    builder.build()
+}
+
+// Like `Lerp for &Path`, but with `buffer` for the intermediate events
+pub fn lerped_with_buffer(
+    from: &Path,
+    to: &Path,
+    t: Vec2,
+    p: f32,
+    buffer: &mut Vec<PathEvent>,
+) -> (bool, Path) {
+    let _span = info_span!("lerp_path").entered();
+    buffer.clear();
+    // Counted once here and passed on, walking a path's events isn't free
+    let (from_count, from_subpaths) = counts(from);
+    let (to_count, to_subpaths) = counts(to);
+    if subpaths_mismatch(from_subpaths, to_subpaths) {
+        return lerp_mismatched_subpaths(from, to, t, p, buffer);
+    }
+    #[cfg(feature = "simd")]
+    if from_count == to_count && from_count >= simd::THRESHOLD {
+        if let Some(all_snapped) = simd::lerp_matching(from, to.iter(), t, p, buffer) {
+            return (all_snapped, build_path(buffer));
+        }
+    }
+    let pairs = EventZipper::with_counts(from, to, from_count, to_count, ZipStrategy::Padded);
+    let all_snapped = lerp_pairs(pairs, t, p, buffer);
+    // Once snapped the padding of `to` has served its purpose, and the target itself is the result
+    if all_snapped && from_count > to_count {
+        return (true, to.clone());
+    }
+    (all_snapped, build_path(buffer))
+}
+
+// Fills `events` with `to`'s events, padded to as many as `from` has the same way lerping
+// pads them, and returns how many were padding. Lerping towards these with `lerped_aligned`
+// is then a plain zip for as long as `from` keeps its size.
+pub fn aligned_events(from: &Path, to: &Path, events: &mut Vec<PathEvent>) -> usize {
+    let _span = info_span!("align_events").entered();
+    events.clear();
+    let (from_count, from_subpaths) = counts(from);
+    let (to_count, to_subpaths) = counts(to);
+    if subpaths_mismatch(from_subpaths, to_subpaths) {
+        // Left empty, which has `lerped_aligned` fall back on the general lerp
+        warn!(
+            from_subpaths,
+            to_subpaths,
+            "Morphing between different numbers of subpaths, the extras collapse into the target's middle"
+        );
+        return 0;
+    }
+    let padding = from_count.saturating_sub(to_count);
+    if padding == 0 {
+        events.extend(to.iter());
+        return 0;
+    }
+    let pairs = EventZipper::with_counts(from, to, from_count, to_count, ZipStrategy::Padded);
+    events.extend(pairs.map(|(_, to)| to));
+    padding
+}
+
+// Same result as `lerped_with_buffer`, given `aligned` and `padding` from `aligned_events`
+pub fn lerped_aligned(
+    from: &Path,
+    to: &Path,
+    aligned: &[PathEvent],
+    padding: usize,
+    t: Vec2,
+    p: f32,
+    buffer: &mut Vec<PathEvent>,
+) -> (bool, Path) {
+    let _span = info_span!("lerp_aligned_path", events = aligned.len()).entered();
+    let from_count = from.iter().count();
+    if from_count != aligned.len() {
+        // `from` has changed size since aligning, e.g. it had to be padded itself on the first frame
+        return lerped_with_buffer(from, to, t, p, buffer);
+    }
+    buffer.clear();
+    #[cfg(feature = "simd")]
+    let simd_snapped = if from_count >= simd::THRESHOLD {
+        simd::lerp_matching(from, aligned.iter().copied(), t, p, buffer)
+    } else {
+        None
+    };
+    #[cfg(not(feature = "simd"))]
+    let simd_snapped = None;
+    let all_snapped = match simd_snapped {
+        Some(all_snapped) => all_snapped,
+        None => lerp_pairs(from.iter().zip(aligned.iter().copied()), t, p, buffer),
+    };
+    // Once snapped the padding has served its purpose, and the target itself is the result
+    if all_snapped && padding > 0 {
+        return (true, to.clone());
+    }
+    (all_snapped, build_path(buffer))
+}
+
+// How many events and subpaths the path has
+fn counts(path: &Path) -> (usize, usize) {
+    path.iter().fold((0, 0), |(events, subpaths), event| {
+        let begins = matches!(event, Event::Begin { .. }) as usize;
+        (events + 1, subpaths + begins)
+    })
+}
+
+// Lining events up one for one only works out if the subpaths line up too.
+// An empty path is fine, it has no subpaths to get mixed up.
+fn subpaths_mismatch(from_subpaths: usize, to_subpaths: usize) -> bool {
+    from_subpaths != to_subpaths && from_subpaths > 0 && to_subpaths > 0
+}
+
+// Each subpath of `from` is lerped toward the same one of `to`. Any `from` has over `to`
+// collapse into a point in the middle of `to`, and any `to` has over `from` grow out of it.
+// Returns `to` itself once snapped, dropping the collapsed ones.
+fn lerp_mismatched_subpaths(
+    from: &Path,
+    to: &Path,
+    t: Vec2,
+    p: f32,
+    buffer: &mut Vec<PathEvent>,
+) -> (bool, Path) {
+    let _span = info_span!("lerp_mismatched_subpaths").entered();
+    let at = point_at_centroid(to).to();
+    let from_events = from.iter().collect::<Vec<_>>();
+    let to_events = to.iter().collect::<Vec<_>>();
+    let (from_subpaths, to_subpaths) = (split_subpaths(&from_events), split_subpaths(&to_events));
+    let mut all_snapped = true;
+    let mut pair_buffer = Vec::new();
+    for i in 0..from_subpaths.len().max(to_subpaths.len()) {
+        let (from, to) = match (from_subpaths.get(i), to_subpaths.get(i)) {
+            (Some(from), Some(to)) => (build_path(from), build_path(to)),
+            (Some(from), None) => (build_path(from), collapsed(at, from.len())),
+            (None, Some(to)) => (collapsed(at, to.len()), build_path(to)),
+            (None, None) => unreachable!(),
+        };
+        let (snapped, path) = lerped_with_buffer(&from, &to, t, p, &mut pair_buffer);
+        all_snapped &= snapped;
+        buffer.extend(path.iter());
+    }
+    if all_snapped {
+        return (true, to.clone());
+    }
+    (false, build_path(buffer))
+}
+
+// Splits the events at each Begin
+fn split_subpaths(events: &[PathEvent]) -> Vec<&[PathEvent]> {
+    let mut subpaths = Vec::new();
+    let mut start = 0;
+    for (i, event) in events.iter().enumerate().skip(1) {
+        if let Event::Begin { .. } = event {
+            subpaths.push(&events[start..i]);
+            start = i;
+        }
+    }
+    if start < events.len() {
+        subpaths.push(&events[start..]);
+    }
+    subpaths
+}
+
+// A subpath of `events` events that never leaves `at`
+fn collapsed(at: Point, events: usize) -> Path {
+    let mut builder = Path::builder();
+    builder.begin(at);
+    for _ in 2..events {
+        builder.line_to(at);
+    }
+    builder.end(false);
+    builder.build()
+}
+
+// What an empty path stands in as when lerped with `other`: a single point in the middle of it,
+// which the other path grows out of or shrinks into
+fn point_at_centroid(other: &Path) -> PathEvent {
+    let (sum, count) = other
+        .iter()
+        .filter(|event| !matches!(event, Event::End { .. }))
+        .fold((Point::origin(), 0), |(sum, count), event| {
+            (sum + event.to().to_vector(), count + 1)
+        });
+    let at = if count == 0 { sum } else { sum / count as f32 };
+    Event::Begin { at }
+}
+
+// Sized up front, so building doesn't reallocate as it goes
+pub fn build_path(events: &[PathEvent]) -> Path {
+    let _span = info_span!("build_path", events = events.len()).entered();
+    let points = events
+        .iter()
+        .map(|event| match event {
+            Event::Begin { .. } | Event::Line { .. } => 1,
+            Event::Quadratic { .. } => 2,
+            Event::Cubic { .. } => 3,
+            Event::End { .. } => 0,
+        })
+        .sum();
+    let mut builder = Builder::with_capacity(points, events.len());
+    for event in events {
+        match *event {
+            Event::Begin { at } => {
+                builder.begin(at);
+            }
+            Event::Line { to, .. } => {
+                builder.line_to(to);
+            }
+            Event::Quadratic { ctrl, to, .. } => {
+                builder.quadratic_bezier_to(ctrl, to);
+            }
+            Event::Cubic {
+                ctrl1, ctrl2, to, ..
+            } => {
+                builder.cubic_bezier_to(ctrl1, ctrl2, to);
+            }
+            Event::End { close, .. } => builder.end(close),
+        }
+    }
+    builder.build()
+}
+
+fn lerp_pairs<T, U>(
+    pairs: impl Iterator<Item = (T, U)>,
+    t: Vec2,
+    p: f32,
+    buffer: &mut Vec<PathEvent>,
+) -> bool
 where
-    T: IntoIterator,
-    U: IntoIterator,
-    T::Item: Lerp<U::Item>,
-    Path: FromIterator<T::Item>,
+    T: Lerp<U, PathEvent>,
 {
     let mut all_snapped = true;
-    let result = from
-        .into_iter()
-        .zip(to)
-        .map(|(from, to)| from.lerped(to, t, p))
-        .inspect(|(snapped, _)| all_snapped &= snapped)
-        .map(|(_, event)| event)
-        .collect::<Path>();
-    (all_snapped, result)
+    buffer.extend(pairs.map(|(from, to)| {
+        let (snapped, event) = from.lerped(to, t, p);
+        all_snapped &= snapped;
+        event
+    }));
+    all_snapped
 }
 
-fn lerp_less_sides(from: &Path, to: &Path, t: f32, p: f32) -> (bool, Path) {
-    let from_count = from.iter().count();
-    let to_count = to.iter().count();
-    assert!(from_count < to_count);
-    lerp_equal_sides(
-        iter::repeat(
-            from.iter()
-                .next()
-                .unwrap_or_else(|| to.iter().next().unwrap()),
-        )
-        .take(to_count - from_count)
-        .chain(from),
-        to,
-        t,
-        p,
+// How `EventZipper` pairs up the events of paths of different sizes
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ZipStrategy {
+    // The way lerping does, with the shorter path padded out to the longer one's size.
+    // Short of `to`, the first of `from`'s events stands in for the ones it's missing, leading
+    // into the rest. Short of `from`, `to` goes on after its first event with lines that go
+    // nowhere from its start, so the extras collapse into the start of its first subpath.
+    // Padding with more `Begin`s would start a sliver of a subpath for each one.
+    // An empty path is a single point in the middle of the other.
+    Padded,
+    // Plainly zipped, with the longer path's extra events left out
+    Truncated,
+}
+
+// Where the stand-ins for the shorter path's missing events go
+#[derive(Clone, Copy)]
+enum Padding {
+    None,
+    From {
+        stand_in: PathEvent,
+        left: usize,
+    },
+    To {
+        first: PathEvent,
+        left: usize,
+        started: bool,
+    },
+}
+
+// The events of two paths paired up for lerping one toward the other, for building other
+// kinds of interpolation on the same correspondence. Paths with different numbers of subpaths
+// are paired as if they were one, lerping them handles each subpath apart instead.
+pub struct EventZipper<'a> {
+    from: Iter<'a>,
+    to: Iter<'a>,
+    padding: Padding,
+}
+
+impl<'a> EventZipper<'a> {
+    pub fn new(from: &'a Path, to: &'a Path, strategy: ZipStrategy) -> Self {
+        let (from_count, to_count) = (from.iter().count(), to.iter().count());
+        Self::with_counts(from, to, from_count, to_count, strategy)
+    }
+
+    // Given the paths' event counts, rather than walking them to count
+    fn with_counts(
+        from: &'a Path,
+        to: &'a Path,
+        from_count: usize,
+        to_count: usize,
+        strategy: ZipStrategy,
+    ) -> Self {
+        let (from_events, mut to_events) = (from.iter(), to.iter());
+        let padding = match (strategy, from_count.cmp(&to_count)) {
+            (ZipStrategy::Truncated, _) | (_, Ordering::Equal) => Padding::None,
+            (ZipStrategy::Padded, Ordering::Less) => Padding::From {
+                stand_in: from.iter().next().unwrap_or_else(|| point_at_centroid(to)),
+                left: to_count - from_count,
+            },
+            (ZipStrategy::Padded, Ordering::Greater) => Padding::To {
+                first: to_events.next().unwrap_or_else(|| point_at_centroid(from)),
+                left: from_count - to_count,
+                started: false,
+            },
+        };
+        Self {
+            from: from_events,
+            to: to_events,
+            padding,
+        }
+    }
+}
+
+impl Iterator for EventZipper<'_> {
+    type Item = (PathEvent, PathEvent);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.padding {
+            Padding::From { stand_in, left } if *left > 0 => {
+                *left -= 1;
+                let stand_in = *stand_in;
+                return self.to.next().map(|to| (stand_in, to));
+            }
+            Padding::To {
+                first,
+                left,
+                started,
+            } => {
+                let first = *first;
+                if !*started {
+                    *started = true;
+                    return self.from.next().map(|from| (from, first));
+                }
+                if *left > 0 {
+                    *left -= 1;
+                    let at = first.to();
+                    let stand_in = Event::Line { from: at, to: at };
+                    return self.from.next().map(|from| (from, stand_in));
+                }
+            }
+            _ => {}
+        }
+        self.from.next().zip(self.to.next())
+    }
+}
+
+// How many events each subpath has
+pub fn subpath_lengths(path: &Path) -> Vec<usize> {
+    let events = path.iter().collect::<Vec<_>>();
+    split_subpaths(&events)
+        .iter()
+        .map(|subpath| subpath.len())
+        .collect()
+}
+
+// The data for each event of `lerped`, a lerp of `from` toward `to`, given data for each event of
+// those, so user data like which corner is which can be followed through a morph. Each event
+// gets the data of the event of `to` it's heading for, except the events padding collapses away,
+// which keep the data of the event of `from` they came from.
+pub fn carried_metadata<M: Clone + Default>(
+    from: &Path,
+    from_data: &[M],
+    to: &Path,
+    to_data: &[M],
+    lerped: &Path,
+) -> Vec<M> {
+    carried_by_subpath(
+        &subpath_lengths(from),
+        from_data,
+        &subpath_lengths(to),
+        to_data,
+        &subpath_lengths(lerped),
     )
 }
 
-fn lerp_greater_sides(from: &Path, to: &Path, t: f32, p: f32) -> (bool, Path) {
-    let from_count = from.iter().count();
-    let to_count = to.iter().count();
-    assert!(from_count > to_count);
-    let (all_snapped, mut result) = lerp_equal_sides(
-        from,
-        iter::repeat(
-            to.iter()
-                .next()
-                .unwrap_or_else(|| from.iter().next().unwrap()),
-        )
-        .take(from_count - to_count)
-        .chain(to),
-        t,
-        p,
-    );
-    if all_snapped {
-        result = to.clone();
+// Like `carried_metadata`, given the paths' subpath lengths rather than the paths themselves,
+// for when `from` is gone by the time the lerp's done. Data missing for an event falls back on
+// the default.
+pub fn carried_by_subpath<M: Clone + Default>(
+    from_lengths: &[usize],
+    from_data: &[M],
+    to_lengths: &[usize],
+    to_data: &[M],
+    lerped_lengths: &[usize],
+) -> Vec<M> {
+    let mut data = Vec::with_capacity(lerped_lengths.iter().sum());
+    if !subpaths_mismatch(from_lengths.len(), to_lengths.len()) {
+        carry(from_data, to_data, lerped_lengths.iter().sum(), &mut data);
+        return data;
+    }
+    // Lerped subpath by subpath, with the extras collapsing or growing out of nothing
+    let split = |mut data: &[M], lengths: &[usize], i: usize| {
+        for &length in lengths.iter().take(i) {
+            data = &data[length.min(data.len())..];
+        }
+        let length = lengths.get(i).copied().unwrap_or(0);
+        data[..length.min(data.len())].to_vec()
+    };
+    for (i, &lerped) in lerped_lengths.iter().enumerate() {
+        let from_subpath = split(from_data, from_lengths, i);
+        let to_subpath = split(to_data, to_lengths, i);
+        carry(&from_subpath, &to_subpath, lerped, &mut data);
+    }
+    data
+}
+
+// One lerp's worth, subpaths and all if they line up. The lerped events line up with `to`'s,
+// except for those padding it out, which come straight after its first event.
+fn carry<M: Clone + Default>(from_data: &[M], to_data: &[M], lerped: usize, data: &mut Vec<M>) {
+    let padding = lerped.saturating_sub(to_data.len());
+    for i in 0..lerped {
+        let carried = if padding == 0 {
+            to_data.get(i)
+        } else if i == 0 {
+            to_data.first()
+        } else if i <= padding {
+            from_data.get(i)
+        } else {
+            to_data.get(i - padding)
+        };
+        data.push(
+            carried
+                .or_else(|| from_data.get(i))
+                .cloned()
+                .unwrap_or_default(),
+        );
+    }
+}
+
+// What can be wrong with a path given to a morph, mostly down to user-provided SVGs
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum MorphError {
+    // Nothing to morph into, the shape shrinks to a point and stays there
+    EmptyPath,
+    // The event at this index has a NaN or infinite point, which makes the shape vanish
+    NonFinite { event: usize },
+    // Every point is the same one, so there's no outline left to see
+    ZeroLength,
+}
+
+impl fmt::Display for MorphError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MorphError::EmptyPath => write!(f, "the path is empty"),
+            MorphError::NonFinite { event } => {
+                write!(f, "event {} of the path isn't a finite point", event)
+            }
+            MorphError::ZeroLength => write!(f, "the path has no length"),
+        }
+    }
+}
+
+fn is_finite(event: &PathEvent) -> bool {
+    let finite = |p: Point| p.x.is_finite() && p.y.is_finite();
+    finite(event.from())
+        && finite(event.to())
+        && match *event {
+            Event::Quadratic { ctrl, .. } => finite(ctrl),
+            Event::Cubic { ctrl1, ctrl2, .. } => finite(ctrl1) && finite(ctrl2),
+            _ => true,
+        }
+}
+
+// The first problem with the path, if any
+pub fn check(path: &Path) -> Result<(), MorphError> {
+    let mut first = None;
+    let mut zero_length = true;
+    for (i, event) in path.iter().enumerate() {
+        if !is_finite(&event) {
+            return Err(MorphError::NonFinite { event: i });
+        }
+        let (from, to) = (event.from(), event.to());
+        let first = *first.get_or_insert(from);
+        zero_length &= from == first && to == first;
+    }
+    match first {
+        None => Err(MorphError::EmptyPath),
+        Some(_) if zero_length => Err(MorphError::ZeroLength),
+        Some(_) => Ok(()),
+    }
+}
+
+// Why the path isn't well-formed, if it isn't: every subpath has a Begin and an End, each event
+// starts where the last one finished, an End's points agree with its subpath, and every point is
+// finite
+pub fn malformation(path: &Path) -> Option<String> {
+    let mut subpath: Option<(Point, Point)> = None;
+    for (i, event) in path.iter().enumerate() {
+        if !is_finite(&event) {
+            return Some(format!("event {} ({:?}) isn't finite", i, event));
+        }
+        subpath = match (subpath, event) {
+            (None, Event::Begin { at }) => Some((at, at)),
+            (Some(_), Event::Begin { .. }) => {
+                return Some(format!("event {} begins a subpath inside another", i))
+            }
+            (None, _) => return Some(format!("event {} ({:?}) is outside a subpath", i, event)),
+            (Some((_, last)), event) if event.from() != last => {
+                return Some(format!(
+                    "event {} ({:?}) doesn't start where the last one finished, at {:?}",
+                    i, event, last
+                ))
+            }
+            (Some((start, _)), Event::End { first, .. }) if first != start => {
+                return Some(format!(
+                    "event {} ends a subpath that began at {:?}, not {:?}",
+                    i, start, first
+                ))
+            }
+            (Some(_), Event::End { .. }) => None,
+            (Some((start, _)), event) => Some((start, event.to())),
+        };
+    }
+    subpath.map(|_| "the last subpath has no End".to_string())
+}
+
+// Lerps paths of the same shape (the same kinds of events in the same order) a batch of points
+// at a time. Produces exactly what the per-event lerp does, only faster for big paths.
+#[cfg(feature = "simd")]
+mod simd {
+    use bevy::log::info_span;
+    use bevy::math::Vec2;
+    use bevy_prototype_lyon::prelude::*;
+    use std::{cell::RefCell, convert::TryFrom, mem};
+    use tess::{
+        math::{point, Point},
+        path::{Event, Path, PathEvent},
+    };
+    use wide::f32x8;
+
+    // Below this many events gathering the points costs more than it saves
+    pub const THRESHOLD: usize = 64;
+    const LANES: usize = 8;
+
+    // Separate x and y buffers, so x and y of eight points can be loaded at once
+    #[derive(Default)]
+    struct Points {
+        x: Vec<f32>,
+        y: Vec<f32>,
+    }
+
+    thread_local! {
+        // The from, to and result points, reused like the scratch events
+        static SCRATCH_POINTS: RefCell<[Points; 3]> = RefCell::new(Default::default());
+    }
+
+    impl Points {
+        fn clear(&mut self) {
+            self.x.clear();
+            self.y.clear();
+        }
+
+        fn push(&mut self, point: Point) {
+            self.x.push(point.x);
+            self.y.push(point.y);
+        }
+
+        fn push_event(&mut self, event: &PathEvent) {
+            match *event {
+                Event::Begin { at } => self.push(at),
+                Event::Line { from, to } => {
+                    self.push(from);
+                    self.push(to);
+                }
+                Event::Quadratic { from, ctrl, to } => {
+                    self.push(from);
+                    self.push(ctrl);
+                    self.push(to);
+                }
+                Event::Cubic {
+                    from,
+                    ctrl1,
+                    ctrl2,
+                    to,
+                } => {
+                    self.push(from);
+                    self.push(ctrl1);
+                    self.push(ctrl2);
+                    self.push(to);
+                }
+                Event::End { last, first, .. } => {
+                    self.push(last);
+                    self.push(first);
+                }
+            }
+        }
+
+        // Zeroed lanes lerp from the origin to the origin, so always snap and never show up
+        fn pad(&mut self) {
+            let len = (self.x.len() + LANES - 1) / LANES * LANES;
+            self.x.resize(len, 0.0);
+            self.y.resize(len, 0.0);
+        }
+
+        fn load(&self, i: usize) -> (f32x8, f32x8) {
+            let lanes =
+                |v: &[f32]| f32x8::from(<[f32; LANES]>::try_from(&v[i..i + LANES]).unwrap());
+            (lanes(&self.x), lanes(&self.y))
+        }
+    }
+
+    // None if the paths don't match event for event, and need the general lerp instead
+    pub fn lerp_matching<I>(
+        from: &Path,
+        to: I,
+        t: Vec2,
+        p: f32,
+        buffer: &mut Vec<PathEvent>,
+    ) -> Option<bool>
+    where
+        I: Iterator<Item = PathEvent> + Clone,
+    {
+        let _span = info_span!("simd_lerp_path").entered();
+        SCRATCH_POINTS.with(|points| {
+            let [from_points, to_points, result] = &mut *points.borrow_mut();
+            from_points.clear();
+            to_points.clear();
+            result.clear();
+            for (a, b) in from.iter().zip(to.clone()) {
+                if mem::discriminant(&a) != mem::discriminant(&b) {
+                    return None;
+                }
+                from_points.push_event(&a);
+                to_points.push_event(&b);
+            }
+            from_points.pad();
+            to_points.pad();
+            let all_snapped = lerp_points(from_points, to_points, t, p, result);
+            rebuild_events(to, result, buffer);
+            Some(all_snapped)
+        })
+    }
+
+    fn lerp_points(from: &Points, to: &Points, t: Vec2, p: f32, result: &mut Points) -> bool {
+        // Same arithmetic as `Point::lerped` and `distance_to`, so the results are bit for bit the same
+        let (one_minus_tx, tx) = (f32x8::splat(1.0 - t.x), f32x8::splat(t.x));
+        let (one_minus_ty, ty) = (f32x8::splat(1.0 - t.y), f32x8::splat(t.y));
+        let p = f32x8::splat(p);
+        let mut all_snapped = true;
+        for i in (0..from.x.len()).step_by(LANES) {
+            let (from_x, from_y) = from.load(i);
+            let (to_x, to_y) = to.load(i);
+            let x = one_minus_tx * from_x + tx * to_x;
+            let y = one_minus_ty * from_y + ty * to_y;
+            let (dx, dy) = (x - to_x, y - to_y);
+            let snapped = (dx * dx + dy * dy).sqrt().cmp_le(p);
+            all_snapped &= snapped.move_mask() == (1 << LANES) - 1;
+            result
+                .x
+                .extend_from_slice(&snapped.blend(to_x, x).to_array());
+            result
+                .y
+                .extend_from_slice(&snapped.blend(to_y, y).to_array());
+        }
+        all_snapped
+    }
+
+    // Puts the lerped points back into events shaped like `to`'s
+    fn rebuild_events(
+        to: impl Iterator<Item = PathEvent>,
+        points: &Points,
+        buffer: &mut Vec<PathEvent>,
+    ) {
+        let mut points = points.x.iter().zip(&points.y).map(|(&x, &y)| point(x, y));
+        let mut next = || points.next().unwrap();
+        buffer.extend(to.map(|event| match event {
+            Event::Begin { .. } => Event::Begin { at: next() },
+            Event::Line { .. } => Event::Line {
+                from: next(),
+                to: next(),
+            },
+            Event::Quadratic { .. } => Event::Quadratic {
+                from: next(),
+                ctrl: next(),
+                to: next(),
+            },
+            Event::Cubic { .. } => Event::Cubic {
+                from: next(),
+                ctrl1: next(),
+                ctrl2: next(),
+                to: next(),
+            },
+            // The per-event lerp always closes subpaths
+            Event::End { .. } => Event::End {
+                last: next(),
+                first: next(),
+                close: true,
+            },
+        }));
     }
-    (all_snapped, result)
 }
@@ -0,0 +1,196 @@
+// A second window showing every shape's target rather than its path, so where each morph is
+// heading can be seen next to the morph itself. The previews are entities of their own, on a
+// render layer only the preview window's camera sees, mirroring their shapes' targets and
+// positions.
+use bevy::prelude::*;
+use bevy::render::{
+    camera::{ActiveCameras, RenderLayers},
+    pass::{
+        LoadOp, Operations, PassDescriptor, RenderPassDepthStencilAttachmentDescriptor,
+        TextureAttachment,
+    },
+    render_graph::{
+        base::MainPass, CameraNode, PassNode, RenderGraph, WindowSwapChainNode, WindowTextureNode,
+    },
+    texture::{Extent3d, TextureDescriptor, TextureDimension, TextureFormat, TextureUsage},
+};
+use bevy::utils::HashMap;
+use bevy::window::{CreateWindow, WindowCreated, WindowId};
+use bevy_prototype_lyon::entity::Path as PathComponent;
+use shape_lerping::{config::Config, shape_bundle, LerpingShape};
+
+const PREVIEW_CAMERA: &str = "preview_camera";
+// The main camera only sees the default layer, 0
+const PREVIEW_LAYER: u8 = 1;
+// The render graph only draws into the primary window, these draw the preview camera into its own
+const PREVIEW_SWAP_CHAIN: &str = "preview_swap_chain";
+const PREVIEW_DEPTH: &str = "preview_depth";
+const PREVIEW_SAMPLED_COLOR: &str = "preview_sampled_color";
+const PREVIEW_PASS: &str = "preview_pass";
+
+// The shape being previewed
+#[derive(Component)]
+pub struct Preview(pub Entity);
+
+pub struct PreviewWindow(WindowId);
+
+pub fn open_preview_window(mut commands: Commands, mut windows: EventWriter<CreateWindow>) {
+    let id = WindowId::new();
+    windows.send(CreateWindow {
+        id,
+        descriptor: WindowDescriptor {
+            title: "Targets".to_string(),
+            ..Default::default()
+        },
+    });
+    commands.insert_resource(PreviewWindow(id));
+}
+
+// The swap chain can't be set up until the window's been made, which is a frame or so after it's
+// asked for
+pub fn add_preview_pass(
+    mut commands: Commands,
+    window: Res<PreviewWindow>,
+    mut created: EventReader<WindowCreated>,
+    msaa: Res<Msaa>,
+    clear_color: Res<ClearColor>,
+    mut graph: ResMut<RenderGraph>,
+    mut active_cameras: ResMut<ActiveCameras>,
+) {
+    if !created.iter().any(|event| event.id == window.0) {
+        return;
+    }
+    let id = window.0;
+    graph.add_node(PREVIEW_SWAP_CHAIN, WindowSwapChainNode::new(id));
+    graph.add_node(
+        PREVIEW_DEPTH,
+        WindowTextureNode::new(
+            id,
+            TextureDescriptor {
+                format: TextureFormat::Depth32Float,
+                usage: TextureUsage::OUTPUT_ATTACHMENT,
+                sample_count: msaa.samples,
+                ..Default::default()
+            },
+        ),
+    );
+    graph.add_system_node(PREVIEW_CAMERA, CameraNode::new(PREVIEW_CAMERA));
+    let mut pass = PassNode::<&MainPass>::new(PassDescriptor {
+        color_attachments: vec![msaa.color_attachment_descriptor(
+            TextureAttachment::Input("color_attachment".to_string()),
+            TextureAttachment::Input("color_resolve_target".to_string()),
+            Operations {
+                load: LoadOp::Clear(clear_color.0),
+                store: true,
+            },
+        )],
+        depth_stencil_attachment: Some(RenderPassDepthStencilAttachmentDescriptor {
+            attachment: TextureAttachment::Input("depth".to_string()),
+            depth_ops: Some(Operations {
+                load: LoadOp::Clear(1.0),
+                store: true,
+            }),
+            stencil_ops: None,
+        }),
+        sample_count: msaa.samples,
+    });
+    pass.add_camera(PREVIEW_CAMERA);
+    graph.add_node(PREVIEW_PASS, pass);
+    // With multisampling the swap chain's only resolved into, it's drawn into a texture of its own
+    let swap_chain_slot = if msaa.samples > 1 {
+        "color_resolve_target"
+    } else {
+        "color_attachment"
+    };
+    graph
+        .add_slot_edge(
+            PREVIEW_SWAP_CHAIN,
+            WindowSwapChainNode::OUT_TEXTURE,
+            PREVIEW_PASS,
+            swap_chain_slot,
+        )
+        .unwrap();
+    graph
+        .add_slot_edge(
+            PREVIEW_DEPTH,
+            WindowTextureNode::OUT_TEXTURE,
+            PREVIEW_PASS,
+            "depth",
+        )
+        .unwrap();
+    graph.add_node_edge(PREVIEW_CAMERA, PREVIEW_PASS).unwrap();
+    if msaa.samples > 1 {
+        graph.add_node(
+            PREVIEW_SAMPLED_COLOR,
+            WindowTextureNode::new(
+                id,
+                TextureDescriptor {
+                    size: Extent3d::new(1, 1, 1),
+                    mip_level_count: 1,
+                    sample_count: msaa.samples,
+                    dimension: TextureDimension::D2,
+                    format: TextureFormat::default(),
+                    usage: TextureUsage::OUTPUT_ATTACHMENT,
+                },
+            ),
+        );
+        graph
+            .add_slot_edge(
+                PREVIEW_SAMPLED_COLOR,
+                WindowTextureNode::OUT_TEXTURE,
+                PREVIEW_PASS,
+                "color_attachment",
+            )
+            .unwrap();
+    }
+
+    active_cameras.add(PREVIEW_CAMERA);
+    let mut camera = OrthographicCameraBundle::new_2d();
+    camera.camera.name = Some(PREVIEW_CAMERA.to_string());
+    camera.camera.window = id;
+    commands
+        .spawn_bundle(camera)
+        .insert(RenderLayers::layer(PREVIEW_LAYER));
+}
+
+// Previews are spawned along with their shapes and despawned after them
+pub fn sync_previews(
+    mut commands: Commands,
+    config: Res<Config>,
+    mut previews: Local<HashMap<Entity, Entity>>,
+    shapes: Query<
+        (Entity, &LerpingShape, &Transform),
+        (
+            Without<Preview>,
+            Or<(Changed<LerpingShape>, Changed<Transform>)>,
+        ),
+    >,
+    mut preview_shapes: Query<(&mut PathComponent, &mut Transform), With<Preview>>,
+    removed: RemovedComponents<LerpingShape>,
+) {
+    for entity in removed.iter() {
+        if let Some(preview) = previews.remove(&entity) {
+            commands.entity(preview).despawn();
+        }
+    }
+    for (entity, shape, transform) in shapes.iter() {
+        match previews
+            .get(&entity)
+            .and_then(|&preview| preview_shapes.get_mut(preview).ok())
+        {
+            Some((mut path, mut preview_transform)) => {
+                path.0 = shape.target().clone();
+                *preview_transform = *transform;
+            }
+            None => {
+                let bundle = shape_bundle(&config, transform.translation, shape.target());
+                let preview = commands
+                    .spawn_bundle(bundle)
+                    .insert(Preview(entity))
+                    .insert(RenderLayers::layer(PREVIEW_LAYER))
+                    .id();
+                previews.insert(entity, preview);
+            }
+        }
+    }
+}
@@ -0,0 +1,75 @@
+// A heartbeat: the shape swells to (1 + scale_amplitude) times its size and back once every
+// `period` seconds. The swelling is a morph toward a scaled copy of the target, rather than a
+// change of scale, so the outline keeps its width. It beats around whatever the target is, so when
+// anything else retargets the shape the next beat starts from the new target.
+use crate::metrics;
+use crate::{frame_delta, FixedDelta, LerpingShape};
+use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::*;
+use tess::{math::Transform, path::Path};
+
+// For finding each target's centre, which it's scaled about
+const CENTROID_TOLERANCE: f32 = 0.1;
+
+#[derive(Component)]
+pub struct PulseShape {
+    pub scale_amplitude: f32,
+    pub period: f32,
+    // The resting target, and its swollen copy
+    targets: Option<(Path, Path)>,
+    elapsed: f32,
+    swollen: bool,
+}
+
+impl PulseShape {
+    pub fn new(scale_amplitude: f32, period: f32) -> Self {
+        Self {
+            scale_amplitude,
+            period,
+            targets: None,
+            elapsed: 0.0,
+            swollen: false,
+        }
+    }
+}
+
+fn scaled(path: &Path, scale: f32) -> Path {
+    let center = metrics::centroid(path, CENTROID_TOLERANCE).to_vector();
+    let transform = Transform::translation(-center.x, -center.y)
+        .then_scale(scale, scale)
+        .then_translate(center);
+    path.clone().transformed(&transform)
+}
+
+pub fn pulse_shapes(
+    time: Res<Time>,
+    fixed_delta: Option<Res<FixedDelta>>,
+    mut shapes: Query<(&mut PulseShape, &mut LerpingShape)>,
+) {
+    let delta = frame_delta(&time, fixed_delta.as_deref()).as_secs_f32();
+    for (mut pulse, mut shape) in shapes.iter_mut() {
+        let retargeted = pulse.targets.as_ref().map_or(true, |(resting, swollen)| {
+            let target = || shape.target.iter();
+            !target().eq(resting.iter()) && !target().eq(swollen.iter())
+        });
+        if retargeted {
+            let swollen = scaled(&shape.target, 1.0 + pulse.scale_amplitude);
+            pulse.targets = Some((shape.target.clone(), swollen));
+            pulse.elapsed = 0.0;
+            pulse.swollen = false;
+        }
+        pulse.elapsed += delta;
+        // Half the period swelling, half shrinking back
+        if pulse.elapsed < pulse.period / 2.0 {
+            continue;
+        }
+        pulse.elapsed = 0.0;
+        pulse.swollen = !pulse.swollen;
+        let (resting, swollen) = pulse.targets.as_ref().unwrap();
+        shape.target = if pulse.swollen {
+            swollen.clone()
+        } else {
+            resting.clone()
+        };
+    }
+}
@@ -0,0 +1,136 @@
+// A whole animation as a data file: which shapes there are, what each morphs through in what
+// order, how long each morph takes and how it's eased, and what happens at the end.
+// RON, or JSON for files ending in .json:
+//   (
+//       shapes: [(
+//           position: (0, 0),
+//           steps: [
+//               (target: Polygon(sides: 3, radius: 200), duration: 1.0),
+//               (target: Ellipse(radii: (200, 100)), duration: 0.5, easing: QuadInOut),
+//           ],
+//       )],
+//       loop_mode: PingPong,
+//   )
+// A recipe can also have a slideshow, a shape that only morphs into its next or previous slide
+// at a keypress:
+//   slideshow: Some((
+//       slides: [
+//           (name: "Triangle", target: Polygon(sides: 3, radius: 200), duration: 0.5),
+//           (name: "Oval", target: Ellipse(radii: (200, 100)), duration: 1.0, easing: QuadOut),
+//       ],
+//   )),
+use crate::easing::Easing;
+use crate::target::TargetShape;
+use crate::timeline::{Keyframe, KeyframeTiming, LerpTimeline, PlaybackMode};
+use crate::{config::Config, shape_bundle, LerpingShape, ShapeId};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path as FilePath, time::Duration};
+
+// Morphs into `target` over `duration` seconds
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RecipeStep {
+    pub target: TargetShape,
+    pub duration: f32,
+    #[serde(default)]
+    pub easing: Easing,
+}
+
+// Starts out as its first step's target, the first step's duration and easing are only used
+// when looping back into it
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RecipeShape {
+    pub position: Vec2,
+    pub steps: Vec<RecipeStep>,
+}
+
+// Morphs into `target` over `duration` seconds once it's stepped to
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Slide {
+    pub name: String,
+    pub target: TargetShape,
+    pub duration: f32,
+    #[serde(default)]
+    pub easing: Easing,
+}
+
+// Starts out as its first slide
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Slideshow {
+    #[serde(default)]
+    pub position: Vec2,
+    pub slides: Vec<Slide>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Recipe {
+    #[serde(default)]
+    pub shapes: Vec<RecipeShape>,
+    // Played the way a LerpTimeline is, so it can also be Repeat(times)
+    #[serde(default)]
+    pub loop_mode: PlaybackMode,
+    #[serde(default)]
+    pub slideshow: Option<Slideshow>,
+}
+
+fn is_json(path: &FilePath) -> bool {
+    path.extension()
+        .map_or(false, |extension| extension == "json")
+}
+
+impl Recipe {
+    pub fn load(path: &FilePath) -> Result<Self, String> {
+        let contents = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let parsed = if is_json(path) {
+            serde_json::from_str(&contents).map_err(|e| e.to_string())
+        } else {
+            ron::from_str(&contents).map_err(|e| e.to_string())
+        };
+        parsed.map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
+    }
+
+    pub fn save(&self, path: &FilePath) -> Result<(), String> {
+        let contents = if is_json(path) {
+            serde_json::to_string_pretty(self).map_err(|e| e.to_string())
+        } else {
+            ron::ser::to_string_pretty(self, Default::default()).map_err(|e| e.to_string())
+        }?;
+        fs::write(path, contents).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+}
+
+// Each shape plays its steps as a LerpTimeline, built up front so playing doesn't rebuild targets
+pub fn spawn_recipe(mut commands: Commands, config: Res<Config>, recipe: Res<Recipe>) {
+    for (i, shape) in recipe.shapes.iter().enumerate() {
+        let keyframes = shape
+            .steps
+            .iter()
+            .map(|step| Keyframe {
+                target: step.target.build(),
+                timing: KeyframeTiming::Over(Duration::from_secs_f32(step.duration.max(0.0))),
+                easing: step.easing,
+            })
+            .collect::<Vec<_>>();
+        let first = match keyframes.first() {
+            Some(first) => first.target.clone(),
+            None => continue,
+        };
+        let bundle = shape_bundle(&config, shape.position.extend(0.0), &first);
+        commands
+            .spawn_bundle(bundle)
+            .insert(ShapeId(i as u32))
+            .insert(LerpingShape::new(first, &config))
+            .insert(LerpTimeline::new(keyframes).played(recipe.loop_mode));
+    }
+}
+
+// How far into a morph of `duration` seconds `elapsed` is, eased
+pub fn progress(elapsed: f32, duration: f32, easing: Easing) -> (f32, bool) {
+    let progress = if duration > 0.0 {
+        (elapsed / duration).min(1.0)
+    } else {
+        1.0
+    };
+    (easing.apply(progress), progress >= 1.0)
+}
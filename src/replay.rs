@@ -0,0 +1,109 @@
+// Records every target assignment and lerp parameter change to a file, one RON entry per line,
+// and plays them back. Entries are applied on the same frame they were recorded on, and morphs
+// advance per frame rather than per second, so a replay reproduces the recorded run exactly.
+use crate::target::PathData;
+use crate::{FrameCount, LerpingShape, ShapeId};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::VecDeque,
+    fs::{self, File},
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+#[derive(Serialize, Deserialize)]
+struct Entry {
+    frame: u64,
+    // Wall-clock time of the entry, for reading recordings rather than replaying them
+    seconds: f64,
+    shape: u32,
+    target: PathData,
+    lerp_t: Vec2,
+    margin_of_error: f32,
+}
+
+pub struct Recorder(BufWriter<File>);
+
+impl Recorder {
+    pub fn create(path: &Path) -> Result<Self, String> {
+        File::create(path)
+            .map(|file| Self(BufWriter::new(file)))
+            .map_err(|e| format!("Failed to create {}: {}", path.display(), e))
+    }
+}
+
+// Every shape counts as changed on its first frame, so recordings start with the full scene
+pub fn record_changes(
+    mut recorder: ResMut<Recorder>,
+    frame: Res<FrameCount>,
+    time: Res<Time>,
+    shapes: Query<(&ShapeId, &LerpingShape), Changed<LerpingShape>>,
+) {
+    for (id, shape) in shapes.iter() {
+        let entry = Entry {
+            frame: frame.0,
+            seconds: time.seconds_since_startup(),
+            shape: id.0,
+            target: PathData::from(&shape.target),
+            lerp_t: shape.lerp_t,
+            margin_of_error: shape.margin_of_error,
+        };
+        let line = ron::to_string(&entry).expect("Entries are always serializable");
+        if let Err(e) = writeln!(recorder.0, "{}", line) {
+            error!("Failed to record frame {}: {}", frame.0, e);
+        }
+    }
+    // A partial recording of a crashed run is still worth having
+    if let Err(e) = recorder.0.flush() {
+        error!("Failed to flush recording: {}", e);
+    }
+}
+
+pub struct Replayer(VecDeque<Entry>);
+
+impl Replayer {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let contents = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        contents
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| {
+                ron::from_str(line).map_err(|e| {
+                    format!("Failed to parse {} line {}: {}", path.display(), i + 1, e)
+                })
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+pub fn replay_changes(
+    mut replayer: ResMut<Replayer>,
+    frame: Res<FrameCount>,
+    mut shapes: Query<(&ShapeId, &mut LerpingShape)>,
+) {
+    while replayer
+        .0
+        .front()
+        .map_or(false, |entry| entry.frame <= frame.0)
+    {
+        let entry = replayer.0.pop_front().unwrap();
+        match shapes.iter_mut().find(|(id, _)| id.0 == entry.shape) {
+            Some((_, mut shape)) => match entry.target.to_path() {
+                Ok(target) => {
+                    shape.target = target;
+                    shape.lerp_t = entry.lerp_t;
+                    shape.margin_of_error = entry.margin_of_error;
+                }
+                Err(e) => warn!(
+                    "Recording has a bad target for shape {}: {}",
+                    entry.shape, e
+                ),
+            },
+            None => warn!("Recording has an entry for unknown shape {}", entry.shape),
+        }
+    }
+}
@@ -0,0 +1,97 @@
+// Gives shapes ripples: concentric outlines offset around the shape, each morphing toward the
+// offset of the shape's new target `delay_frames` frames after the one inside it, so every change
+// of target spreads outward. The ripples are child entities, so they move with the shape.
+use crate::config::Config;
+use crate::offset::{offset, Join};
+use crate::target::FLATTEN_TOLERANCE;
+use crate::{shape_bundle, FrameCount, LerpingShape};
+use bevy::prelude::*;
+use bevy_prototype_lyon::entity::Path as PathComponent;
+use bevy_prototype_lyon::prelude::*;
+use std::collections::VecDeque;
+use tess::path::Path;
+
+// How opaque the innermost ripple's outline is next to the shape's, fading further out
+const RIPPLE_ALPHA: f32 = 0.6;
+// Behind the shape
+const RIPPLE_DEPTH: f32 = -0.5;
+
+#[derive(Component)]
+pub struct MorphRipple {
+    pub rings: usize,
+    // Negative to ripple inward
+    pub spacing: f32,
+    pub delay_frames: usize,
+}
+
+#[derive(Component)]
+pub struct Ripple {
+    // 1 for the innermost
+    ring: usize,
+    // The shape's target as of the last offset queued
+    seen: Path,
+    // The frames the offset targets still to come are due on, oldest first
+    pending: VecDeque<(u64, Path)>,
+}
+
+fn ripple_of(path: &Path, ripple: &MorphRipple, ring: usize) -> Path {
+    offset(
+        path,
+        ripple.spacing * ring as f32,
+        Join::Round,
+        FLATTEN_TOLERANCE,
+    )
+}
+
+pub fn spawn_ripples(
+    mut commands: Commands,
+    config: Res<Config>,
+    shapes: Query<(Entity, &PathComponent, &LerpingShape, &MorphRipple), Added<MorphRipple>>,
+) {
+    for (entity, path, shape, ripple) in shapes.iter() {
+        let rings = (1..=ripple.rings)
+            .map(|ring| {
+                let start = ripple_of(&path.0, ripple, ring);
+                let mut bundle = shape_bundle(&config, Vec3::new(0.0, 0.0, RIPPLE_DEPTH), &start);
+                let mut color = config.outline_color;
+                color.set_a(RIPPLE_ALPHA / ring as f32);
+                bundle.mode = DrawMode::Stroke(StrokeMode::new(color, config.outline_width));
+                let target = ripple_of(&shape.target, ripple, ring);
+                commands
+                    .spawn_bundle(bundle)
+                    .insert(LerpingShape::new(target, &config))
+                    .insert(Ripple {
+                        ring,
+                        seen: shape.target.clone(),
+                        pending: VecDeque::new(),
+                    })
+                    .id()
+            })
+            .collect::<Vec<_>>();
+        commands.entity(entity).push_children(&rings);
+    }
+}
+
+pub fn follow_outward(
+    frame: Res<FrameCount>,
+    shapes: Query<(&LerpingShape, &MorphRipple, &Children), Without<Ripple>>,
+    mut ripples: Query<(&mut Ripple, &mut LerpingShape)>,
+) {
+    for (shape, ripple, children) in shapes.iter() {
+        for &child in children.iter() {
+            let (mut ring, mut ring_shape) = match ripples.get_mut(child) {
+                Ok(child) => child,
+                Err(_) => continue,
+            };
+            if !ring.seen.iter().eq(shape.target.iter()) {
+                let due = frame.0 + (ripple.delay_frames * ring.ring) as u64;
+                let target = ripple_of(&shape.target, ripple, ring.ring);
+                ring.seen = shape.target.clone();
+                ring.pending.push_back((due, target));
+            }
+            while matches!(ring.pending.front(), Some(&(due, _)) if due <= frame.0) {
+                ring_shape.target = ring.pending.pop_front().unwrap().1;
+            }
+        }
+    }
+}
@@ -0,0 +1,90 @@
+// Each shape has its own random number generator, for everything random it does: the
+// screensaver's targets, speeds and colours, and noise on its targets. Seeded from the config's
+// `seed` and the shape's id, so a seeded scene plays out the same on every run and each shape's
+// randomness doesn't depend on how many others there are.
+use crate::ShapeId;
+use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::*;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tess::{
+    math::{point, Point},
+    path::{Event, Path},
+};
+
+// xorshift64*, plenty for picking shapes and colours
+#[derive(Component)]
+pub struct ShapeRng(u64);
+
+// splitmix64's finaliser, so neighbouring ids get unrelated streams
+fn mix(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+impl ShapeRng {
+    // Seeded from the time if there's no seed
+    pub fn for_shape(seed: Option<u64>, id: ShapeId) -> Self {
+        let seed = seed.unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_nanos() as u64)
+        });
+        let state = mix(seed ^ mix(id.0 as u64));
+        // Zero would get stuck at zero
+        Self(if state == 0 { 1 } else { state })
+    }
+
+    pub fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    // Uniform in [0, 1)
+    pub fn unit(&mut self) -> f32 {
+        (self.next() >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    // Uniform in [min, max)
+    pub fn range(&mut self, min: f32, max: f32) -> f32 {
+        min + self.unit() * (max - min)
+    }
+
+    // Uniform in [0, len), len has to be above 0
+    pub fn index(&mut self, len: usize) -> usize {
+        (self.next() % len as u64) as usize
+    }
+}
+
+// The path with every point moved up to `amount` along each axis
+pub fn perturbed(path: &Path, amount: f32, rng: &mut ShapeRng) -> Path {
+    let mut nudge = |p: Point| {
+        let (dx, dy) = (rng.range(-amount, amount), rng.range(-amount, amount));
+        point(p.x + dx, p.y + dy)
+    };
+    let mut builder = Path::builder();
+    for event in path.iter() {
+        match event {
+            Event::Begin { at } => {
+                builder.begin(nudge(at));
+            }
+            Event::Line { to, .. } => {
+                builder.line_to(nudge(to));
+            }
+            Event::Quadratic { ctrl, to, .. } => {
+                let ctrl = nudge(ctrl);
+                builder.quadratic_bezier_to(ctrl, nudge(to));
+            }
+            Event::Cubic {
+                ctrl1, ctrl2, to, ..
+            } => {
+                let (ctrl1, ctrl2) = (nudge(ctrl1), nudge(ctrl2));
+                builder.cubic_bezier_to(ctrl1, ctrl2, nudge(to));
+            }
+            Event::End { close, .. } => builder.end(close),
+        }
+    }
+    builder.build()
+}
@@ -0,0 +1,156 @@
+// Scripts passed with `--script` can call:
+//   set_target(entity, sides)              step the shape to a side count
+//   set_target(entity, svg, width, height) morph into SVG path data
+//   set_radius(entity, radius)
+//   set_speed(lerp_t), set_speed(entity, lerp_t)
+// and may define `fn on_lerp_finished(entity)`, called whenever a shape snaps to its target.
+use crate::control::MorphCommand;
+use crate::target::TargetShape;
+use crate::LerpBatchFinished;
+use bevy::prelude::*;
+use rhai::{Engine, Scope, AST, FLOAT, INT};
+use std::{
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+// Callback the script may define, called with the entity's id whenever one of its shapes snaps
+const ON_LERP_FINISHED: &str = "on_lerp_finished";
+
+// Entities cross into the script as their raw bits
+fn to_script(entity: Entity) -> INT {
+    entity.to_bits() as INT
+}
+
+fn from_script(entity: INT) -> Entity {
+    Entity::from_bits(entity as u64)
+}
+
+// Commands issued by the script's functions are queued here and sent as events once it returns
+type Queue = Arc<Mutex<Vec<MorphCommand>>>;
+
+fn push(queue: &Queue, command: MorphCommand) {
+    queue.lock().unwrap().push(command);
+}
+
+pub struct Script {
+    engine: Engine,
+    ast: AST,
+    scope: Scope<'static>,
+    queue: Queue,
+}
+
+impl Script {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let queue = Queue::default();
+        let mut engine = Engine::new();
+
+        let q = queue.clone();
+        engine.register_fn("set_target", move |entity: INT, sides: INT| {
+            push(
+                &q,
+                MorphCommand::SetSides {
+                    entity: Some(from_script(entity)),
+                    sides: sides.clamp(0, u8::MAX as INT) as u8,
+                },
+            )
+        });
+        let q = queue.clone();
+        engine.register_fn(
+            "set_target",
+            move |entity: INT, svg: &str, width: FLOAT, height: FLOAT| {
+                push(
+                    &q,
+                    MorphCommand::SetTarget {
+                        entity: Some(from_script(entity)),
+                        target: TargetShape::Svg {
+                            path: svg.to_owned(),
+                            size: Vec2::new(width as f32, height as f32),
+                        },
+                    },
+                )
+            },
+        );
+        let q = queue.clone();
+        engine.register_fn("set_radius", move |entity: INT, radius: FLOAT| {
+            push(
+                &q,
+                MorphCommand::SetRadius {
+                    entity: Some(from_script(entity)),
+                    radius: radius as f32,
+                },
+            )
+        });
+        let q = queue.clone();
+        engine.register_fn("set_speed", move |lerp_t: FLOAT| {
+            push(
+                &q,
+                MorphCommand::SetLerpT {
+                    entity: None,
+                    lerp_t: lerp_t as f32,
+                },
+            )
+        });
+        let q = queue.clone();
+        engine.register_fn("set_speed", move |entity: INT, lerp_t: FLOAT| {
+            push(
+                &q,
+                MorphCommand::SetLerpT {
+                    entity: Some(from_script(entity)),
+                    lerp_t: lerp_t as f32,
+                },
+            )
+        });
+
+        let ast = engine
+            .compile_file(path.into())
+            .map_err(|e| format!("Failed to compile {}: {}", path.display(), e))?;
+        let mut scope = Scope::new();
+        // Run the top level once so scripts can set initial speeds and define globals
+        engine
+            .run_ast_with_scope(&mut scope, &ast)
+            .map_err(|e| format!("Failed to run {}: {}", path.display(), e))?;
+
+        Ok(Self {
+            engine,
+            ast,
+            scope,
+            queue,
+        })
+    }
+
+    fn has_fn(&self, name: &str) -> bool {
+        self.ast.iter_functions().any(|f| f.name == name)
+    }
+
+    fn drain(&self) -> Vec<MorphCommand> {
+        self.queue.lock().unwrap().drain(..).collect()
+    }
+}
+
+pub fn run_script_callbacks(
+    mut script: ResMut<Script>,
+    mut finished: EventReader<LerpBatchFinished>,
+    mut commands: EventWriter<MorphCommand>,
+) {
+    let script = &mut *script;
+    if script.has_fn(ON_LERP_FINISHED) {
+        for &entity in finished
+            .iter()
+            .flat_map(|LerpBatchFinished(entities)| entities)
+        {
+            let result: Result<(), _> = script.engine.call_fn(
+                &mut script.scope,
+                &script.ast,
+                ON_LERP_FINISHED,
+                (to_script(entity),),
+            );
+            if let Err(e) = result {
+                error!("Script callback {} failed: {}", ON_LERP_FINISHED, e);
+            }
+        }
+    }
+    for command in script.drain() {
+        commands.send(command);
+    }
+}
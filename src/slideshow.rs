@@ -0,0 +1,101 @@
+// Plays a recipe's slideshow, a shape that only morphs into its next or previous slide at a
+// keypress, see `recipe.rs` for the format
+use bevy::prelude::*;
+use bevy_prototype_lyon::entity::Path as PathComponent;
+use bevy_prototype_lyon::prelude::*;
+use shape_lerping::easing::Easing;
+use shape_lerping::path_lerping::{sample_morph, SampleOptions};
+use shape_lerping::recipe::{progress, Recipe};
+use shape_lerping::{config::Config, frame_delta, shape_bundle, FixedDelta, Paused, ShapeId};
+use tess::path::Path;
+
+#[derive(Component)]
+pub struct SlideshowPlayer {
+    slides: Vec<(String, Path, f32, Easing)>,
+    current: usize,
+    // The path the morph into the current slide started from, and how far into it the player is
+    from: Path,
+    elapsed: f32,
+    settled: bool,
+}
+
+pub fn spawn_slideshow(mut commands: Commands, config: Res<Config>, recipe: Res<Recipe>) {
+    let slideshow = match &recipe.slideshow {
+        Some(slideshow) => slideshow,
+        None => return,
+    };
+    let slides = slideshow
+        .slides
+        .iter()
+        .map(|slide| {
+            let path = slide.target.build();
+            (slide.name.clone(), path, slide.duration, slide.easing)
+        })
+        .collect::<Vec<_>>();
+    let first = match slides.first() {
+        Some((name, first, _, _)) => {
+            info!("Slide 1: {}", name);
+            first.clone()
+        }
+        None => return,
+    };
+    let bundle = shape_bundle(&config, slideshow.position.extend(0.0), &first);
+    commands
+        .spawn_bundle(bundle)
+        .insert(ShapeId(recipe.shapes.len() as u32))
+        .insert(SlideshowPlayer {
+            slides,
+            current: 0,
+            from: first,
+            elapsed: 0.0,
+            settled: true,
+        });
+}
+
+// Stepping past either end stays on the first or last slide.
+// Stepping mid-morph starts the next morph from wherever the shape has got to.
+pub fn advance_slides(
+    keys: Res<Input<KeyCode>>,
+    mut slideshows: Query<(&PathComponent, &mut SlideshowPlayer)>,
+) {
+    let step = if keys.just_pressed(KeyCode::Right) || keys.just_pressed(KeyCode::PageDown) {
+        1
+    } else if keys.just_pressed(KeyCode::Left) || keys.just_pressed(KeyCode::PageUp) {
+        -1
+    } else {
+        return;
+    };
+    for (path, mut player) in slideshows.iter_mut() {
+        let last = player.slides.len() as isize - 1;
+        let current = (player.current as isize + step).max(0).min(last) as usize;
+        if current == player.current {
+            continue;
+        }
+        info!("Slide {}: {}", current + 1, player.slides[current].0);
+        player.current = current;
+        player.from = path.0.clone();
+        player.elapsed = 0.0;
+        player.settled = false;
+    }
+}
+
+pub fn play_slides(
+    time: Res<Time>,
+    fixed_delta: Option<Res<FixedDelta>>,
+    paused: Res<Paused>,
+    mut slideshows: Query<(&mut PathComponent, &mut SlideshowPlayer)>,
+) {
+    if paused.0 {
+        return;
+    }
+    for (mut path, mut player) in slideshows.iter_mut() {
+        if player.settled {
+            continue;
+        }
+        player.elapsed += frame_delta(&time, fixed_delta.as_deref()).as_secs_f32();
+        let (_, target, duration, easing) = &player.slides[player.current];
+        let (t, done) = progress(player.elapsed, *duration, *easing);
+        path.0 = sample_morph(&player.from, target, t, SampleOptions::default());
+        player.settled = done;
+    }
+}
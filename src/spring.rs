@@ -0,0 +1,156 @@
+// Morphs as if each point were on a spring pulled toward its target, in place of closing lerp_t
+// of the distance each frame. Points carry their velocity from frame to frame, so they overshoot
+// and settle, and a new target mid-morph bends their motion rather than starting it over.
+use crate::path_lerping::Lerp;
+use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::*;
+use serde::Deserialize;
+use std::{iter, mem};
+use tess::{
+    math::{Point, Vector},
+    path::{Event, Path, PathEvent},
+};
+
+// Long frames are stepped through in slices of at most this many seconds, as one big step
+// would fling stiff springs apart
+const MAX_STEP: f32 = 1.0 / 120.0;
+
+#[derive(Component, Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct SpringLerp {
+    // How hard each point's pulled toward its target, per pixel away
+    pub stiffness: f32,
+    // How hard its motion's resisted, per pixel per second. Twice the square root of stiffness
+    // times mass or more and it never overshoots.
+    pub damping: f32,
+    pub mass: f32,
+    // One per point, in the order the path's events have them
+    #[serde(skip)]
+    velocities: Vec<Vector>,
+}
+
+impl Default for SpringLerp {
+    fn default() -> Self {
+        Self::new(120.0, 12.0, 1.0)
+    }
+}
+
+impl SpringLerp {
+    pub fn new(stiffness: f32, damping: f32, mass: f32) -> Self {
+        Self {
+            stiffness,
+            damping,
+            mass,
+            velocities: Vec::new(),
+        }
+    }
+
+    // Moves each point `dt` seconds on toward its target
+    pub fn step(&mut self, points: &mut [Point], targets: &[Point], dt: f32) {
+        self.velocities.resize(points.len(), Vector::zero());
+        let steps = (dt / MAX_STEP).ceil().max(1.0) as usize;
+        let step = dt / steps as f32;
+        for ((point, &target), velocity) in points.iter_mut().zip(targets).zip(&mut self.velocities)
+        {
+            for _ in 0..steps {
+                let force = (target - *point) * self.stiffness - *velocity * self.damping;
+                *velocity += force * (step / self.mass);
+                *point += *velocity * step;
+            }
+        }
+    }
+
+    // Whether every point's within `margin` of its target and would move less than that in `dt`
+    pub fn settled(&self, points: &[Point], targets: &[Point], margin: f32, dt: f32) -> bool {
+        points
+            .iter()
+            .zip(targets)
+            .zip(&self.velocities)
+            .all(|((point, &target), velocity)| {
+                point.distance_to(target) <= margin && velocity.length() * dt <= margin
+            })
+    }
+}
+
+// The points an event adds to a path, which are the ones that move
+fn push_points(event: &PathEvent, points: &mut Vec<Point>) {
+    match *event {
+        Event::Begin { at } => points.push(at),
+        Event::Line { to, .. } => points.push(to),
+        Event::Quadratic { ctrl, to, .. } => points.extend(&[ctrl, to]),
+        Event::Cubic {
+            ctrl1, ctrl2, to, ..
+        } => points.extend(&[ctrl1, ctrl2, to]),
+        Event::End { .. } => {}
+    }
+}
+
+// A path shaped like `events`, through `points` in place of theirs
+fn rebuilt(events: &[PathEvent], points: &[Point]) -> Path {
+    let mut points = points.iter().copied();
+    let mut next = || points.next().unwrap();
+    let mut builder = Path::builder();
+    for event in events {
+        match *event {
+            Event::Begin { .. } => {
+                builder.begin(next());
+            }
+            Event::Line { .. } => {
+                builder.line_to(next());
+            }
+            Event::Quadratic { .. } => {
+                let ctrl = next();
+                builder.quadratic_bezier_to(ctrl, next());
+            }
+            Event::Cubic { .. } => {
+                let (ctrl1, ctrl2) = (next(), next());
+                builder.cubic_bezier_to(ctrl1, ctrl2, next());
+            }
+            Event::End { close, .. } => builder.end(close),
+        }
+    }
+    builder.build()
+}
+
+// Springs `path` a frame of `dt` seconds on toward `target`, which `aligned` lines `path` up
+// with. Returns `target` itself once every point's settled, like `lerped_aligned` does.
+pub fn sprung(
+    path: &Path,
+    target: &Path,
+    aligned: &[PathEvent],
+    lerp_t: Vec2,
+    spring: &mut SpringLerp,
+    margin: f32,
+    dt: f32,
+) -> (bool, Path) {
+    // Nothing lines up with paths of different numbers of subpaths, they just lerp
+    if aligned.is_empty() {
+        return path.lerped(target, lerp_t, margin);
+    }
+    // Padded to line up with the target first, the same way lerping pads it
+    let padded;
+    let path = if path.iter().count() != aligned.len() {
+        padded = path.lerped(target, Vec2::ZERO, 0.0).1;
+        &padded
+    } else {
+        path
+    };
+
+    let (mut points, mut targets) = (Vec::new(), Vec::new());
+    for (event, target_event) in path.iter().zip(aligned) {
+        push_points(target_event, &mut targets);
+        if mem::discriminant(&event) == mem::discriminant(target_event) {
+            push_points(&event, &mut points);
+        } else {
+            // The extra points of a curve start out where the event it's turning into ends
+            let missing = targets.len() - points.len();
+            points.extend(iter::repeat(event.to()).take(missing));
+        }
+    }
+    spring.step(&mut points, &targets, dt);
+    if spring.settled(&points, &targets, margin, dt) {
+        (true, target.clone())
+    } else {
+        (false, rebuilt(aligned, &points))
+    }
+}
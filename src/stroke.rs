@@ -0,0 +1,79 @@
+// Strokes turned into the outlines of the areas they cover, so a stroke can be morphed like any
+// filled shape and a change of stroke width becomes a change of geometry. Curves are flattened to
+// within `tolerance` first. Joins are mitred, bevelled where they'd be too long, and the ends of
+// open subpaths are cut off square with their ends, like butt caps.
+use crate::offset::{offset_polyline, Join};
+use bevy_prototype_lyon::prelude::*;
+use tess::{
+    math::Point,
+    path::{iterator::PathIterator, Event, Path},
+};
+
+// Points closer than this count as the same point
+const EPSILON: f32 = 1e-4;
+
+// Each subpath's points, with whether it's closed. A closing point repeating the first is left
+// out, and so are subpaths of a single point.
+fn polylines(path: &Path, tolerance: f32) -> Vec<(Vec<Point>, bool)> {
+    let mut polylines = Vec::new();
+    let mut points: Vec<Point> = Vec::new();
+    for event in path.iter().flattened(tolerance) {
+        match event {
+            Event::Begin { at } => points.push(at),
+            Event::Line { to, .. } => {
+                if points
+                    .last()
+                    .map_or(true, |last| last.distance_to(to) > EPSILON)
+                {
+                    points.push(to);
+                }
+            }
+            Event::End { first, close, .. } => {
+                let mut finished = std::mem::take(&mut points);
+                if close
+                    && finished.len() > 1
+                    && finished[finished.len() - 1].distance_to(first) <= EPSILON
+                {
+                    finished.pop();
+                }
+                // Closed subpaths of two points are stroked like the line between them
+                if finished.len() > 1 {
+                    let closed = close && finished.len() > 2;
+                    polylines.push((finished, closed));
+                }
+            }
+            _ => {}
+        }
+    }
+    polylines
+}
+
+// The outline of `path` stroked `width` wide, anticlockwise around the outside. Open subpaths
+// become a single closed outline; closed ones become an outline around the outside and another
+// clockwise around the hole in the middle, so it fills with either fill rule.
+pub fn stroked(path: &Path, width: f32, tolerance: f32) -> Path {
+    let mut builder = Path::builder();
+    if width <= 0.0 {
+        return builder.build();
+    }
+    let half = width / 2.0;
+    let mut add = |points: Vec<Point>| {
+        builder.begin(points[0]);
+        for &point in &points[1..] {
+            builder.line_to(point);
+        }
+        builder.end(true);
+    };
+    for (points, closed) in polylines(path, tolerance) {
+        let right = offset_polyline(&points, closed, -half, Join::Miter, tolerance);
+        let mut left = offset_polyline(&points, closed, half, Join::Miter, tolerance);
+        left.reverse();
+        if closed {
+            add(right);
+            add(left);
+        } else {
+            add(right.into_iter().chain(left).collect());
+        }
+    }
+    builder.build()
+}
@@ -0,0 +1,126 @@
+use crate::target::PathData;
+use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::*;
+use std::{fs, iter, path::Path as FilePath};
+use tess::path::Path;
+
+// Every `d` attribute of the document's <path> elements, as one path. Transforms, styles and
+// other elements are ignored, it's the outlines that are morphed.
+pub fn path_data(document: &str) -> String {
+    let mut data = String::new();
+    for element in document.split("<path").skip(1) {
+        // Some other element that only starts with "path"
+        if !element.starts_with(|c: char| c.is_whitespace() || c == '/' || c == '>') {
+            continue;
+        }
+        let d = attributes(element).find(|&(name, _)| name == "d");
+        if let Some((_, d)) = d {
+            data.push_str(d);
+            data.push(' ');
+        }
+    }
+    data
+}
+
+// The name and value of each attribute at the start of `element`, up to the end of its tag.
+// Values can be quoted either way, with any whitespace around the `=`.
+fn attributes(element: &str) -> impl Iterator<Item = (&str, &str)> {
+    let mut rest = element;
+    iter::from_fn(move || {
+        rest = rest.trim_start();
+        let name_end =
+            rest.find(|c: char| c == '=' || c.is_whitespace() || c == '/' || c == '>')?;
+        let (name, after_name) = rest.split_at(name_end);
+        let after_equals = after_name.trim_start().strip_prefix('=')?.trim_start();
+        let quote = after_equals
+            .chars()
+            .next()
+            .filter(|&c| c == '"' || c == '\'')?;
+        let (value, after_value) = after_equals[1..].split_once(quote)?;
+        rest = after_value;
+        Some((name, value))
+    })
+}
+
+// Imports an SVG file's outlines, recentred like `shapes::SvgPathShape`
+pub fn load(file: &FilePath, size: Vec2) -> Result<Path, String> {
+    #[cfg(feature = "cache")]
+    if let Some(path) = cache::read(file, size) {
+        return Ok(path);
+    }
+    let document = fs::read_to_string(file)
+        .map_err(|e| format!("Failed to read {}: {}", file.display(), e))?;
+    let path = ShapePath::build_as(&shapes::SvgPathShape {
+        svg_path_string: path_data(&document),
+        svg_doc_size_in_px: size,
+    })
+    .0;
+    #[cfg(feature = "cache")]
+    cache::write(file, size, &path);
+    Ok(path)
+}
+
+// Converting big documents is slow enough to notice at startup, so the result is kept next to
+// the file and reused for as long as the file and the size it was converted at stay the same
+#[cfg(feature = "cache")]
+mod cache {
+    use super::PathData;
+    use bevy::prelude::*;
+    use bevy_prototype_lyon::prelude::*;
+    use serde::{Deserialize, Serialize};
+    use std::{
+        fs,
+        path::{Path as FilePath, PathBuf},
+        time::SystemTime,
+    };
+    use tess::path::Path;
+
+    #[derive(Serialize, Deserialize)]
+    struct Cached {
+        modified: SystemTime,
+        len: u64,
+        size: [f32; 2],
+        path: PathData,
+    }
+
+    fn cache_file(file: &FilePath) -> PathBuf {
+        let mut name = file.as_os_str().to_owned();
+        name.push(".pathcache");
+        PathBuf::from(name)
+    }
+
+    fn source_stamp(file: &FilePath) -> Option<(SystemTime, u64)> {
+        let metadata = fs::metadata(file).ok()?;
+        Some((metadata.modified().ok()?, metadata.len()))
+    }
+
+    // None on anything from a missing cache to a stale or unreadable one, it's only ever a shortcut
+    pub fn read(file: &FilePath, size: Vec2) -> Option<Path> {
+        let (modified, len) = source_stamp(file)?;
+        let bytes = fs::read(cache_file(file)).ok()?;
+        let cached: Cached = bincode::deserialize(&bytes).ok()?;
+        if cached.modified != modified || cached.len != len || cached.size != size.to_array() {
+            return None;
+        }
+        cached.path.to_path().ok()
+    }
+
+    pub fn write(file: &FilePath, size: Vec2, path: &Path) {
+        let (modified, len) = match source_stamp(file) {
+            Some(stamp) => stamp,
+            None => return,
+        };
+        let cached = Cached {
+            modified,
+            len,
+            size: size.to_array(),
+            path: PathData::from(path),
+        };
+        let result = bincode::serialize(&cached)
+            .map_err(|e| e.to_string())
+            .and_then(|bytes| fs::write(cache_file(file), bytes).map_err(|e| e.to_string()));
+        if let Err(e) = result {
+            warn!("Failed to cache {}: {}", file.display(), e);
+        }
+    }
+}
@@ -0,0 +1,251 @@
+use crate::boolean::{self, BooleanOp};
+use crate::stroke;
+use crate::svg;
+use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::*;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::{f32::consts::PI, path::PathBuf};
+use tess::{
+    math::point,
+    path::{Event, Path},
+};
+
+// A serializable description of a morph target, for anything driving the shapes from outside Rust
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum TargetShape {
+    Polygon { sides: u8, radius: f32 },
+    Ellipse { radii: Vec2 },
+    // Path data in SVG document coordinates, recentred on the entity like `shapes::SvgPathShape`
+    Svg { path: String, size: Vec2 },
+    // The outlines of an SVG document on disk, see `svg::load`
+    SvgFile { file: PathBuf, size: Vec2 },
+    Path(PathData),
+    // Two shapes built and then combined, like a polygon with an ellipse cut out of it
+    Combined(Box<Combination>),
+    // The outline of the area a stroke along the shape would cover, filled in place of the stroke
+    Stroked(Box<Stroke>),
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Combination {
+    pub op: BooleanOp,
+    pub a: TargetShape,
+    pub b: TargetShape,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Stroke {
+    pub shape: TargetShape,
+    pub width: f32,
+}
+
+// How closely curves are followed when combining, stroking or offsetting shapes, as the results
+// are made of lines
+pub const FLATTEN_TOLERANCE: f32 = 0.1;
+
+impl TargetShape {
+    // Even side counts morph into an ellipse, odd ones into a regular polygon
+    pub fn for_sides(sides: u8, radius: f32) -> Self {
+        if sides % 2 == 0 {
+            Self::Ellipse {
+                radii: Vec2::new((sides as f32).sin() * radius, (sides as f32).cos() * radius),
+            }
+        } else {
+            Self::Polygon { sides, radius }
+        }
+    }
+
+    pub fn build(&self) -> Path {
+        match self {
+            Self::Polygon { sides, radius } => return polygon(*sides, *radius),
+            Self::Ellipse { radii } => ShapePath::build_as(&shapes::Ellipse {
+                radii: *radii,
+                ..Default::default()
+            }),
+            Self::Svg { path, size } => ShapePath::build_as(&shapes::SvgPathShape {
+                svg_path_string: path.clone(),
+                svg_doc_size_in_px: *size,
+            }),
+            Self::SvgFile { file, size } => {
+                return svg::load(file, *size).unwrap_or_else(|e| {
+                    warn!("{}, morphing to nothing instead", e);
+                    Path::new()
+                })
+            }
+            Self::Path(data) => {
+                return data.to_path().unwrap_or_else(|e| {
+                    warn!("Invalid path data, morphing to nothing instead: {}", e);
+                    Path::new()
+                })
+            }
+            Self::Combined(combination) => {
+                let Combination { op, a, b } = &**combination;
+                return boolean::combined(&a.build(), &b.build(), *op, FLATTEN_TOLERANCE);
+            }
+            Self::Stroked(stroke) => {
+                return stroke::stroked(&stroke.shape.build(), stroke.width, FLATTEN_TOLERANCE)
+            }
+        }
+        .0
+    }
+}
+
+// Below this, polygons and the ellipses standing in for them collapse into points and lines
+pub const MIN_SIDES: u8 = 3;
+
+// Vertices of every regular polygon with a radius of 1, indexed by side count. Laid out exactly like
+// `shapes::RegularPolygon` lays them out, so scaling them gives the very same paths.
+static UNIT_POLYGONS: Lazy<Vec<Vec<Vec2>>> = Lazy::new(|| {
+    (0..=u8::MAX as usize)
+        .map(|sides| {
+            if sides < MIN_SIDES as usize {
+                return Vec::new();
+            }
+            let n = sides as f32;
+            let offset = -(n - 2.0) * PI / n / 2.0;
+            let step = 2.0 * PI / n;
+            (0..sides)
+                .map(|i| {
+                    let angle = (i as f32).mul_add(step, offset);
+                    Vec2::new(angle.cos(), angle.sin())
+                })
+                .collect()
+        })
+        .collect()
+});
+
+// The vertices of a regular polygon with this many sides and a radius of 1, starting from the bottom
+// right and going anticlockwise. Empty below 3 sides.
+pub fn unit_polygon(sides: u8) -> &'static [Vec2] {
+    &UNIT_POLYGONS[sides as usize]
+}
+
+fn polygon(sides: u8, radius: f32) -> Path {
+    let vertices = unit_polygon(sides);
+    let mut builder = Path::builder();
+    if let Some((first, rest)) = vertices.split_first() {
+        builder.begin(point(first.x * radius, first.y * radius));
+        for vertex in rest {
+            builder.line_to(point(vertex.x * radius, vertex.y * radius));
+        }
+        builder.end(true);
+    }
+    builder.build()
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Verb {
+    Begin,
+    Line,
+    Quadratic,
+    Cubic,
+    End,
+    Close,
+}
+
+// Any path in a compact serializable form: each verb consumes the next points it needs,
+// without repeating the `from` point events carry
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct PathData {
+    pub verbs: Vec<Verb>,
+    pub points: Vec<[f32; 2]>,
+}
+
+impl From<&Path> for PathData {
+    fn from(path: &Path) -> Self {
+        let mut data = Self::default();
+        for event in path.iter() {
+            let verb = match event {
+                Event::Begin { at } => {
+                    data.points.push(at.to_array());
+                    Verb::Begin
+                }
+                Event::Line { to, .. } => {
+                    data.points.push(to.to_array());
+                    Verb::Line
+                }
+                Event::Quadratic { ctrl, to, .. } => {
+                    data.points.extend([ctrl.to_array(), to.to_array()].iter());
+                    Verb::Quadratic
+                }
+                Event::Cubic {
+                    ctrl1, ctrl2, to, ..
+                } => {
+                    data.points
+                        .extend([ctrl1.to_array(), ctrl2.to_array(), to.to_array()].iter());
+                    Verb::Cubic
+                }
+                Event::End { close: false, .. } => Verb::End,
+                Event::End { close: true, .. } => Verb::Close,
+            };
+            data.verbs.push(verb);
+        }
+        data
+    }
+}
+
+impl PathData {
+    // Checks the data describes a path the builder will take before building it, as it can come
+    // from files and the network: every point finite, every verb with the points it needs, and
+    // segments only ever inside a subpath that's begun and later ended
+    pub fn to_path(&self) -> Result<Path, String> {
+        if let Some(i) = self
+            .points
+            .iter()
+            .position(|p| !p.iter().all(|c| c.is_finite()))
+        {
+            return Err(format!("Point {} is {:?}", i, self.points[i]));
+        }
+        let mut builder = Path::builder();
+        let mut points = self.points.iter().map(|&[x, y]| point(x, y));
+        let mut open = false;
+        for (i, verb) in self.verbs.iter().enumerate() {
+            let in_subpath = match verb {
+                Verb::Begin => !open,
+                _ => open,
+            };
+            if !in_subpath {
+                return Err(format!(
+                    "{:?} at verb {} is {} a subpath",
+                    verb,
+                    i,
+                    if open { "inside" } else { "outside" }
+                ));
+            }
+            let mut next = || points.next();
+            let complete = match verb {
+                Verb::Begin => next().map(|at| {
+                    builder.begin(at);
+                }),
+                Verb::Line => next().map(|to| {
+                    builder.line_to(to);
+                }),
+                Verb::Quadratic => next().zip(next()).map(|(ctrl, to)| {
+                    builder.quadratic_bezier_to(ctrl, to);
+                }),
+                Verb::Cubic => next().zip(next()).zip(next()).map(|((ctrl1, ctrl2), to)| {
+                    builder.cubic_bezier_to(ctrl1, ctrl2, to);
+                }),
+                Verb::End => Some(builder.end(false)),
+                Verb::Close => Some(builder.end(true)),
+            };
+            if complete.is_none() {
+                return Err(format!("{:?} at verb {} runs out of points", verb, i));
+            }
+            open = match verb {
+                Verb::Begin => true,
+                Verb::End | Verb::Close => false,
+                _ => open,
+            };
+        }
+        if open {
+            return Err("The last subpath is never ended".to_string());
+        }
+        let unused = points.count();
+        if unused > 0 {
+            return Err(format!("{} points are left over", unused));
+        }
+        Ok(builder.build())
+    }
+}
@@ -0,0 +1,114 @@
+// Labels every point of every morphing shape with the `t` it's being lerped by and how far it
+// still has to go, or that it's snapped, updated each frame. While paused, the period key steps
+// a single frame at a time, so how each point closes in and snaps can be followed one step at
+// a time.
+use crate::demo_config::DemoConfig;
+use bevy::prelude::*;
+use bevy_prototype_lyon::entity::Path as PathComponent;
+use bevy_prototype_lyon::prelude::*;
+use shape_lerping::margin::MarginScale;
+use shape_lerping::{config::Config, AlignedTarget, LerpingShape, Paused};
+use tess::path::Event;
+
+const FONT_SIZE: f32 = 14.0;
+// Keeps the labels clear of the point they belong to, and drawn over the shapes
+const LABEL_OFFSET: Vec3 = Vec3::new(8.0, 8.0, 100.0);
+
+#[derive(Component)]
+pub struct PointLabel;
+
+// Unpauses for one frame, the lerp runs on the frame the key is pressed
+pub fn step_frame(
+    keys: Res<Input<KeyCode>>,
+    mut paused: ResMut<Paused>,
+    mut stepping: Local<bool>,
+) {
+    if *stepping {
+        paused.0 = true;
+        *stepping = false;
+    }
+    if paused.0 && keys.just_pressed(KeyCode::Period) {
+        paused.0 = false;
+        *stepping = true;
+    }
+}
+
+// Labels are reused from frame to frame, spare ones are hidden rather than despawned
+pub fn label_points(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    config: Res<Config>,
+    demo: Res<DemoConfig>,
+    shapes: Query<(
+        &PathComponent,
+        &LerpingShape,
+        &AlignedTarget,
+        &GlobalTransform,
+        Option<&MarginScale>,
+    )>,
+    mut labels: Query<(&mut Text, &mut Transform, &mut Visibility), With<PointLabel>>,
+) {
+    let mut wanted = Vec::new();
+    for (path, shape, aligned, transform, margin_scale) in shapes.iter() {
+        // The same margin `lerp_shape` snaps within
+        let margin = shape.margin(margin_scale, config.snap_epsilon);
+        let lerp_t = shape.lerp_t();
+        let speed = if lerp_t.x == lerp_t.y {
+            format!("t {:.3}", lerp_t.x)
+        } else {
+            format!("t {:.3}, {:.3}", lerp_t.x, lerp_t.y)
+        };
+        // Paths that don't line up with the target yet are lerped without the aligned events,
+        // so there's nothing to measure against until they do
+        let events = if path.0.iter().count() == aligned.events().len() {
+            aligned.events()
+        } else {
+            &[]
+        };
+        let targets = events.iter().map(|event| Some(event.to()));
+        let targets = targets.chain(std::iter::repeat(None));
+        for (event, target) in path.0.iter().zip(targets) {
+            // An End's point is its subpath's Begin, which already has a label
+            if let Event::End { .. } = event {
+                continue;
+            }
+            let at = event.to();
+            let (text, color) = match target.map(|target| at.distance_to(target)) {
+                Some(distance) if distance <= margin => ("snapped".to_string(), Color::GREEN),
+                Some(distance) => (format!("{}\nd {:.1}", speed, distance), Color::WHITE),
+                None => (speed.clone(), Color::WHITE),
+            };
+            let position = transform.mul_vec3(Vec3::new(at.x, at.y, 0.0)) + LABEL_OFFSET;
+            wanted.push((position, text, color));
+        }
+    }
+
+    let mut wanted = wanted.into_iter();
+    for (mut text, mut transform, mut visibility) in labels.iter_mut() {
+        match wanted.next() {
+            Some((position, value, color)) => {
+                let section = &mut text.sections[0];
+                section.value = value;
+                section.style.color = color;
+                transform.translation = position;
+                visibility.is_visible = true;
+            }
+            None => visibility.is_visible = false,
+        }
+    }
+    let font = asset_server.load(demo.label_font.as_path());
+    for (position, value, color) in wanted {
+        let style = TextStyle {
+            font: font.clone(),
+            font_size: FONT_SIZE,
+            color,
+        };
+        commands
+            .spawn_bundle(Text2dBundle {
+                text: Text::with_section(value, style, TextAlignment::default()),
+                transform: Transform::from_translation(position),
+                ..Default::default()
+            })
+            .insert(PointLabel);
+    }
+}
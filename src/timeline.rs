@@ -0,0 +1,212 @@
+// A sequence of keyframes a shape morphs through in order, each taking its own time with its own
+// easing, so a whole animation can be set up at once instead of chaining targets by hand. A
+// timeline that hasn't started yet starts straight away, taking over from whatever the shape was
+// doing, and the shape keeps the last keyframe's timing once it's through. Played once, it's a
+// queue: keyframes pushed onto a finished timeline start as they're pushed, and ones pushed onto
+// a running timeline wait their turn.
+// A keyframe with the same target as the one before it finishes as soon as it starts, whatever its
+// duration.
+// Timelines can play through more than once, each time starting over from the keyframe the last
+// one ended on, without anything having to listen for the end and set them going again.
+use crate::easing::Easing;
+use crate::{LerpBatchFinished, LerpingShape};
+use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tess::path::Path;
+
+// How the morph into a keyframe, from the one before it, goes
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum KeyframeTiming {
+    Over(Duration),
+    // Closing lerp_t of the distance each frame, the way a shape without a duration does
+    Lerped { lerp_t: Vec2, margin_of_error: f32 },
+}
+
+#[derive(Clone, Debug)]
+pub struct Keyframe {
+    pub target: Path,
+    pub timing: KeyframeTiming,
+    pub easing: Easing,
+}
+
+impl Keyframe {
+    // Reached by closing lerp_t of the distance each frame, for queueing up morphs
+    pub fn lerped(target: Path, lerp_t: Vec2, margin_of_error: f32, easing: Easing) -> Self {
+        Self {
+            target,
+            timing: KeyframeTiming::Lerped {
+                lerp_t,
+                margin_of_error,
+            },
+            easing,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PlaybackMode {
+    // Stopping on the last keyframe
+    Once,
+    // Back to the first keyframe after the last, forever
+    Loop,
+    // Back down through the keyframes after the last, then up again, forever. Going back down,
+    // each morph takes the time and easing of the one it's undoing.
+    PingPong,
+    // Looping until it's played through this many times in all
+    Repeat(u32),
+}
+
+impl Default for PlaybackMode {
+    fn default() -> Self {
+        Self::Once
+    }
+}
+
+#[derive(Component, Clone, Debug, Default)]
+pub struct LerpTimeline {
+    keyframes: Vec<Keyframe>,
+    playback: PlaybackMode,
+    // The next keyframe to start morphing toward, past the end once it's through
+    next: usize,
+    // The keyframe being morphed toward, or last reached
+    current: usize,
+    running: bool,
+    // Going back down through the keyframes, playing ping-pong
+    backward: bool,
+    // How many times it's played through
+    passes: u32,
+}
+
+impl LerpTimeline {
+    pub fn new(keyframes: Vec<Keyframe>) -> Self {
+        Self {
+            keyframes,
+            ..Self::default()
+        }
+    }
+
+    pub fn played(self, playback: PlaybackMode) -> Self {
+        Self { playback, ..self }
+    }
+
+    pub fn playback(&self) -> PlaybackMode {
+        self.playback
+    }
+
+    pub fn push(&mut self, keyframe: Keyframe) {
+        self.keyframes.push(keyframe);
+    }
+
+    pub fn keyframes(&self) -> &[Keyframe] {
+        &self.keyframes
+    }
+
+    // The keyframe being morphed toward, if it's started and not yet through
+    pub fn current(&self) -> Option<usize> {
+        self.running.then(|| self.current)
+    }
+
+    // Played through as many times as it's set to, until more keyframes are pushed
+    pub fn is_finished(&self) -> bool {
+        !self.running && self.next >= self.keyframes.len()
+    }
+
+    // Back to the start, the first keyframe starting on the next frame
+    pub fn restart(&mut self) {
+        self.next = 0;
+        self.running = false;
+        self.backward = false;
+        self.passes = 0;
+    }
+
+    // The keyframe to go on to from `reached`, counting the pass if that's the end of one
+    fn after(&mut self, reached: usize) -> usize {
+        let len = self.keyframes.len();
+        if self.backward {
+            if reached > 0 {
+                return reached - 1;
+            }
+            self.backward = false;
+            return 1.min(len - 1);
+        }
+        if reached + 1 < len {
+            return reached + 1;
+        }
+        self.passes += 1;
+        match self.playback {
+            PlaybackMode::Loop => 0,
+            PlaybackMode::Repeat(times) if self.passes < times => 0,
+            PlaybackMode::PingPong => {
+                self.backward = true;
+                reached.saturating_sub(1)
+            }
+            _ => len,
+        }
+    }
+}
+
+// Sent as a shape reaches each keyframe of its timeline
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KeyframeReached {
+    pub entity: Entity,
+    pub keyframe: usize,
+    // The last keyframe of the timeline's last time through, so it's finished
+    pub last: bool,
+}
+
+// Runs after the shapes' own retargeting, so a timeline overrides it
+pub fn advance_timelines(
+    mut lerp_events: EventReader<LerpBatchFinished>,
+    mut reached: EventWriter<KeyframeReached>,
+    mut shapes: Query<(&mut LerpTimeline, &mut LerpingShape)>,
+) {
+    for LerpBatchFinished(entities) in lerp_events.iter() {
+        for &entity in entities {
+            let mut timeline = match shapes.get_mut(entity) {
+                Ok((timeline, _)) if timeline.running => timeline,
+                _ => continue,
+            };
+            let keyframe = timeline.current;
+            timeline.running = false;
+            timeline.next = timeline.after(keyframe);
+            reached.send(KeyframeReached {
+                entity,
+                keyframe,
+                last: timeline.is_finished(),
+            });
+        }
+    }
+    for (mut timeline, mut shape) in shapes.iter_mut() {
+        if timeline.running {
+            continue;
+        }
+        let next = timeline.next;
+        let keyframe = match timeline.keyframes.get(next) {
+            Some(keyframe) => keyframe,
+            None => continue,
+        };
+        // Going back down, it's the morph up from this keyframe being undone
+        let timing = timeline
+            .keyframes
+            .get(next + 1)
+            .filter(|_| timeline.backward)
+            .unwrap_or(keyframe);
+        shape.target = keyframe.target.clone();
+        match timing.timing {
+            KeyframeTiming::Over(duration) => shape.duration = Some(duration),
+            KeyframeTiming::Lerped {
+                lerp_t,
+                margin_of_error,
+            } => {
+                shape.duration = None;
+                shape.lerp_t = lerp_t;
+                shape.margin_of_error = margin_of_error;
+            }
+        }
+        shape.easing = timing.easing;
+        timeline.current = next;
+        timeline.running = true;
+    }
+}
@@ -0,0 +1,78 @@
+// Shapes are tessellated to the config's tolerances once they've snapped, but while they're
+// morphing, detail that fine moves too fast to see, so they're tessellated more coarsely.
+use crate::async_tessellation::AsyncTessellation;
+use crate::{config::Config, LerpingShape, Snapped};
+use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::*;
+
+// The tolerances of a shape that's snapped, or `morphing_tolerance` for both if it isn't
+fn tolerances(config: &Config, snapped: bool) -> (f32, f32) {
+    if snapped || config.morphing_tolerance <= 0.0 {
+        (config.fill_tolerance, config.stroke_tolerance)
+    } else {
+        (config.morphing_tolerance, config.morphing_tolerance)
+    }
+}
+
+fn current(mode: &DrawMode) -> (Option<f32>, Option<f32>) {
+    match mode {
+        DrawMode::Fill(fill) => (Some(fill.options.tolerance), None),
+        DrawMode::Stroke(stroke) => (None, Some(stroke.options.tolerance)),
+        DrawMode::Outlined {
+            fill_mode,
+            outline_mode,
+        } => (
+            Some(fill_mode.options.tolerance),
+            Some(outline_mode.options.tolerance),
+        ),
+    }
+}
+
+fn with_tolerances(mode: &DrawMode, (fill, stroke): (f32, f32)) -> DrawMode {
+    let mut mode = *mode;
+    match &mut mode {
+        DrawMode::Fill(fill_mode) => fill_mode.options.tolerance = fill,
+        DrawMode::Stroke(stroke_mode) => stroke_mode.options.tolerance = stroke,
+        DrawMode::Outlined {
+            fill_mode,
+            outline_mode,
+        } => {
+            fill_mode.options.tolerance = fill;
+            outline_mode.options.tolerance = stroke;
+        }
+    }
+    mode
+}
+
+// Also catches draw modes replaced without regard for tolerance, like by a palette change.
+// Only writes when the tolerance has to change, as any write re-tessellates the shape.
+pub fn update_tolerance(
+    config: Res<Config>,
+    mut shapes: Query<
+        (
+            Option<&Snapped>,
+            Option<&mut DrawMode>,
+            Option<&mut AsyncTessellation>,
+        ),
+        With<LerpingShape>,
+    >,
+) {
+    for (snapped, mode, tessellation) in shapes.iter_mut() {
+        let (fill, stroke) = tolerances(&config, snapped.is_some());
+        let needs = |mode: &DrawMode| {
+            let (current_fill, current_stroke) = current(mode);
+            current_fill.map_or(false, |t| t != fill)
+                || current_stroke.map_or(false, |t| t != stroke)
+        };
+        if let Some(mut mode) = mode {
+            if needs(&mode) {
+                *mode = with_tolerances(&mode, (fill, stroke));
+            }
+        } else if let Some(mut tessellation) = tessellation {
+            if needs(tessellation.mode()) {
+                let new_mode = with_tolerances(tessellation.mode(), (fill, stroke));
+                tessellation.set_mode(new_mode);
+            }
+        }
+    }
+}
@@ -0,0 +1,135 @@
+// Draws only part of a shape's outline, from `start` to `end` of the way around it by length, for
+// drawing line art on as it morphs. The trimmed outline is a child entity stroked the way the
+// config says, and the shape itself is hidden while it's trimmed, as its fill and outline would
+// give the rest away. Curves are flattened first, so the trimmed outline is made of lines.
+use crate::config::Config;
+use crate::metrics::length;
+use crate::shape_bundle;
+use bevy::prelude::*;
+use bevy_prototype_lyon::entity::Path as PathComponent;
+use bevy_prototype_lyon::prelude::*;
+use tess::{
+    math::Point,
+    path::{iterator::PathIterator, Event, Path},
+};
+
+// How closely curves are followed, as the trimmed outline is made of lines
+const TRIM_TOLERANCE: f32 = 0.1;
+
+// Either way round, between 0 and 1
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct TrimPath {
+    pub start: f32,
+    pub end: f32,
+}
+
+#[derive(Component)]
+pub struct TrimmedOutline;
+
+// The part of `path` from `start` to `end` of the way along it, through its subpaths in order.
+// Closed subpaths are followed round their closing edges, and stay closed if they're kept whole.
+pub fn trimmed(path: &Path, start: f32, end: f32, tolerance: f32) -> Path {
+    let total = length(path, tolerance);
+    let (trim_start, trim_end) = (
+        start.min(end).clamp(0.0, 1.0) * total,
+        start.max(end).clamp(0.0, 1.0) * total,
+    );
+    let mut builder = Path::builder();
+    let mut points: Vec<Point> = Vec::new();
+    // How far along the path the subpath and the edge being walked start
+    let (mut subpath_start, mut along) = (0.0, 0.0);
+    let edge = |a: Point, b: Point, along: &mut f32, points: &mut Vec<Point>| {
+        let length = a.distance_to(b);
+        let (edge_start, edge_end) = (*along, *along + length);
+        *along = edge_end;
+        if length <= 0.0 || edge_end <= trim_start || edge_start >= trim_end {
+            return;
+        }
+        let clipped = |distance: f32| a.lerp(b, ((distance - edge_start) / length).clamp(0.0, 1.0));
+        if points.is_empty() {
+            points.push(clipped(trim_start.max(edge_start)));
+        }
+        points.push(clipped(trim_end.min(edge_end)));
+    };
+    for event in path.iter().flattened(tolerance) {
+        match event {
+            Event::Begin { .. } => subpath_start = along,
+            Event::Line { from, to } => edge(from, to, &mut along, &mut points),
+            Event::End { last, first, close } => {
+                if close {
+                    edge(last, first, &mut along, &mut points);
+                }
+                let whole = close && subpath_start >= trim_start && along <= trim_end;
+                if whole {
+                    points.pop();
+                }
+                if points.len() > 1 {
+                    builder.begin(points[0]);
+                    for &point in &points[1..] {
+                        builder.line_to(point);
+                    }
+                    builder.end(whole);
+                }
+                points.clear();
+            }
+            _ => {}
+        }
+    }
+    builder.build()
+}
+
+pub fn spawn_trimmed_outlines(
+    mut commands: Commands,
+    config: Res<Config>,
+    mut shapes: Query<(Entity, &PathComponent, &TrimPath, &mut Visibility), Added<TrimPath>>,
+) {
+    for (entity, path, trim, mut visibility) in shapes.iter_mut() {
+        let outline = trimmed(&path.0, trim.start, trim.end, TRIM_TOLERANCE);
+        let mut bundle = shape_bundle(&config, Vec3::ZERO, &outline);
+        bundle.mode = DrawMode::Stroke(StrokeMode::new(config.outline_color, config.outline_width));
+        let outline = commands.spawn_bundle(bundle).insert(TrimmedOutline).id();
+        commands.entity(entity).push_children(&[outline]);
+        visibility.is_visible = false;
+    }
+}
+
+// Follows the morph, and any animating of the trim itself
+pub fn trim_outlines(
+    shapes: Query<
+        (&PathComponent, &TrimPath, &Children),
+        (
+            Without<TrimmedOutline>,
+            Or<(Changed<PathComponent>, Changed<TrimPath>)>,
+        ),
+    >,
+    mut outlines: Query<&mut PathComponent, With<TrimmedOutline>>,
+) {
+    for (path, trim, children) in shapes.iter() {
+        for &child in children.iter() {
+            if let Ok(mut outline) = outlines.get_mut(child) {
+                outline.0 = trimmed(&path.0, trim.start, trim.end, TRIM_TOLERANCE);
+            }
+        }
+    }
+}
+
+// Untrimmed shapes are drawn whole again
+pub fn untrim(
+    mut commands: Commands,
+    removed: RemovedComponents<TrimPath>,
+    mut shapes: Query<(&mut Visibility, Option<&Children>), Without<TrimmedOutline>>,
+    outlines: Query<(), With<TrimmedOutline>>,
+) {
+    for entity in removed.iter() {
+        let (mut visibility, children) = match shapes.get_mut(entity) {
+            Ok(shape) => shape,
+            Err(_) => continue,
+        };
+        visibility.is_visible = true;
+        for &child in children.into_iter().flat_map(|children| children.iter()) {
+            if outlines.get(child).is_ok() {
+                commands.entity(child).despawn_recursive();
+            }
+        }
+    }
+}
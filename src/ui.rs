@@ -0,0 +1,252 @@
+// Backgrounds for Bevy UI widgets drawn as morphing shapes, which change shape with the
+// widget's Interaction and resize with its layout. Each background is a shape in the world of
+// its own, kept over its widget's node through the main camera, so it morphs like any other.
+// The node itself is best left transparent.
+use crate::{config::Config, shape_bundle, LerpingShape, MainCamera, Paused};
+use bevy::prelude::*;
+use bevy_prototype_lyon::entity::Path as PathComponent;
+use bevy_prototype_lyon::prelude::*;
+use tess::{math::point, path::Path};
+
+// In front of the config's shapes
+const BACKGROUND_Z: f32 = 100.0;
+// How far along its tangents a cubic's control points sit for a quarter circle
+const KAPPA: f32 = 0.552_284_8;
+
+// How a widget's background looks in one state, in the UI's pixels
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct WidgetShape {
+    pub corner_radius: f32,
+    // How far inside the node's edges the outline sits, negative to stand out past them
+    pub inset: f32,
+}
+
+#[derive(Component)]
+pub struct MorphingWidget {
+    pub idle: WidgetShape,
+    pub hovered: WidgetShape,
+    pub pressed: WidgetShape,
+    // Morph the background's size and corner radius rather than its outline's points, so the
+    // corners keep their shape and only the edges between them stretch, like a nine-slice.
+    // Lerping the points only bends the corners when they move at different rates along each
+    // axis, or when the radius changes too. Nine-sliced backgrounds don't have a LerpingShape,
+    // so they aren't counted in LerpBatchFinished.
+    pub nine_slice: bool,
+}
+
+impl MorphingWidget {
+    // The same shape whatever the interaction, for panels that only change size
+    pub fn fixed(shape: WidgetShape) -> Self {
+        Self {
+            idle: shape,
+            hovered: shape,
+            pressed: shape,
+            nine_slice: false,
+        }
+    }
+
+    fn shape(&self, interaction: Option<&Interaction>) -> WidgetShape {
+        match interaction {
+            Some(Interaction::Clicked) => self.pressed,
+            Some(Interaction::Hovered) => self.hovered,
+            Some(Interaction::None) | None => self.idle,
+        }
+    }
+}
+
+// On the shape drawn for a widget
+#[derive(Component)]
+pub struct WidgetBackground(pub Entity);
+
+// The size and corner radius of a nine-sliced background, and what they're morphing toward
+#[derive(Component)]
+pub struct NineSlice {
+    size: Vec2,
+    radius: f32,
+    target_size: Vec2,
+    target_radius: f32,
+}
+
+// Around the origin, with the same events whatever the size and radius, so one state morphs
+// into another event for event
+pub fn rounded_rectangle(size: Vec2, radius: f32) -> Path {
+    let (x, y) = (size.x.max(0.0) / 2.0, size.y.max(0.0) / 2.0);
+    let r = radius.clamp(0.0, x.min(y));
+    let k = r * KAPPA;
+    let mut builder = Path::builder();
+    builder.begin(point(-x + r, -y));
+    builder.line_to(point(x - r, -y));
+    builder.cubic_bezier_to(point(x - r + k, -y), point(x, -y + r - k), point(x, -y + r));
+    builder.line_to(point(x, y - r));
+    builder.cubic_bezier_to(point(x, y - r + k), point(x - r + k, y), point(x - r, y));
+    builder.line_to(point(-x + r, y));
+    builder.cubic_bezier_to(point(-x + r - k, y), point(-x, y - r + k), point(-x, y - r));
+    builder.line_to(point(-x, -y + r));
+    builder.cubic_bezier_to(
+        point(-x, -y + r - k),
+        point(-x + r - k, -y),
+        point(-x + r, -y),
+    );
+    builder.end(true);
+    builder.build()
+}
+
+fn background_size(node: &Node, shape: WidgetShape) -> Vec2 {
+    node.size - Vec2::splat(shape.inset * 2.0)
+}
+
+pub fn spawn_widget_backgrounds(
+    mut commands: Commands,
+    config: Res<Config>,
+    widgets: Query<(Entity, &Node, Option<&Interaction>, &MorphingWidget), Added<MorphingWidget>>,
+) {
+    for (entity, node, interaction, widget) in widgets.iter() {
+        let shape = widget.shape(interaction);
+        let (size, radius) = (background_size(node, shape), shape.corner_radius);
+        let path = rounded_rectangle(size, radius);
+        let translation = Vec3::new(0.0, 0.0, BACKGROUND_Z);
+        let mut background = commands.spawn_bundle(shape_bundle(&config, translation, &path));
+        background.insert(WidgetBackground(entity));
+        if widget.nine_slice {
+            background.insert(NineSlice {
+                size,
+                radius,
+                target_size: size,
+                target_radius: radius,
+            });
+        } else {
+            background.insert(LerpingShape::new(path, &config));
+        }
+    }
+}
+
+// Retargets each background for its widget's state and size, and moves it over the widget.
+// Backgrounds are despawned along with their widgets.
+pub fn sync_widget_backgrounds(
+    mut commands: Commands,
+    windows: Res<Windows>,
+    cameras: Query<(&GlobalTransform, &OrthographicProjection), With<MainCamera>>,
+    widgets: Query<(
+        &Node,
+        &GlobalTransform,
+        Option<&Interaction>,
+        &MorphingWidget,
+    )>,
+    mut backgrounds: Query<(
+        Entity,
+        &WidgetBackground,
+        &mut Transform,
+        Option<&mut LerpingShape>,
+        Option<&mut NineSlice>,
+    )>,
+) {
+    let (window, (camera, projection)) = match (windows.get_primary(), cameras.iter().next()) {
+        (Some(window), Some(camera)) => (window, camera),
+        _ => return,
+    };
+    let window_size = Vec2::new(window.width(), window.height());
+    for (entity, background, mut transform, lerping, nine_slice) in backgrounds.iter_mut() {
+        let (node, node_transform, interaction, widget) = match widgets.get(background.0) {
+            Ok(widget) => widget,
+            Err(_) => {
+                commands.entity(entity).despawn();
+                continue;
+            }
+        };
+        let shape = widget.shape(interaction);
+        let (size, radius) = (background_size(node, shape), shape.corner_radius);
+        if let Some(mut slice) = nine_slice {
+            if slice.target_size != size || slice.target_radius != radius {
+                slice.target_size = size;
+                slice.target_radius = radius;
+            }
+        } else if let Some(mut lerping) = lerping {
+            let target = rounded_rectangle(size, radius);
+            if !target.iter().eq(lerping.target.iter()) {
+                lerping.target = target;
+            }
+        }
+        // Nodes are placed in window pixels from the bottom left, the path is in pixels too
+        let offset = (node_transform.translation.truncate() - window_size / 2.0) * projection.scale;
+        let at = camera
+            .mul_vec3(offset.extend(0.0))
+            .truncate()
+            .extend(BACKGROUND_Z);
+        let scale = Vec3::new(projection.scale, projection.scale, 1.0);
+        if transform.translation != at || transform.scale != scale {
+            transform.translation = at;
+            transform.scale = scale;
+        }
+    }
+}
+
+// Closes in on the target size and radius the way `lerp_shape` closes in on a target path,
+// rebuilding the outline from them
+pub fn morph_nine_slices(
+    config: Res<Config>,
+    paused: Res<Paused>,
+    mut backgrounds: Query<(&mut NineSlice, &mut PathComponent)>,
+) {
+    if paused.0 {
+        return;
+    }
+    let t = config.axis_lerp_t();
+    let margin = config.margin_of_error + config.snap_epsilon;
+    for (mut slice, mut path) in backgrounds.iter_mut() {
+        if slice.size == slice.target_size && slice.radius == slice.target_radius {
+            continue;
+        }
+        let mut size = slice.size + (slice.target_size - slice.size) * t;
+        // The radius bends both axes at once, so it goes at the slower one's pace
+        let mut radius = slice.radius + (slice.target_radius - slice.radius) * t.min_element();
+        if (size - slice.target_size).abs().max_element() <= margin
+            && (radius - slice.target_radius).abs() <= margin
+        {
+            size = slice.target_size;
+            radius = slice.target_radius;
+        }
+        slice.size = size;
+        slice.radius = radius;
+        path.0 = rounded_rectangle(size, radius);
+    }
+}
+
+// For --ui, a button at the bottom of the window that rounds off when hovered and shrinks
+// when pressed
+pub fn spawn_demo_button(mut commands: Commands, mut materials: ResMut<Assets<ColorMaterial>>) {
+    commands.spawn_bundle(UiCameraBundle::default());
+    commands
+        .spawn_bundle(ButtonBundle {
+            style: Style {
+                size: Size::new(Val::Px(240.0), Val::Px(80.0)),
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    left: Val::Percent(50.0),
+                    bottom: Val::Px(40.0),
+                    ..Default::default()
+                },
+                margin: Rect {
+                    left: Val::Px(-120.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            material: materials.add(Color::NONE.into()),
+            ..Default::default()
+        })
+        .insert(MorphingWidget {
+            idle: WidgetShape {
+                corner_radius: 8.0,
+                inset: 0.0,
+            },
+            hovered: WidgetShape {
+                corner_radius: 40.0,
+                inset: -8.0,
+            },
+            pressed: WidgetShape {
+                corner_radius: 24.0,
+                inset: 8.0,
+            },
+            nine_slice: true,
+        });
+}
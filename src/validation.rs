@@ -0,0 +1,171 @@
+#[cfg(debug_assertions)]
+use crate::path_lerping::malformation;
+use crate::path_lerping::{check, MorphError};
+use crate::target::MIN_SIDES;
+use crate::{config::Config, Direction, LerpingShape, SidesChangingShape};
+use bevy::prelude::*;
+use bevy_prototype_lyon::entity::Path as PathComponent;
+use bevy_prototype_lyon::prelude::*;
+use serde::Deserialize;
+use std::ops::RangeBounds;
+use tess::{
+    math::Point,
+    path::{Event, Path},
+};
+
+// What's done with a target that has NaN or infinite points in it
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Deserialize)]
+pub enum NonFinitePolicy {
+    // Each bad point is replaced with the last good one before it
+    Clamp,
+    // The target is dropped, and the shape holds still where it is
+    Reject,
+}
+
+impl Default for NonFinitePolicy {
+    fn default() -> Self {
+        Self::Clamp
+    }
+}
+
+// What's done with shapes whose bounds let them step below MIN_SIDES, where the targets
+// degenerate into a point or a line
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Deserialize)]
+pub enum SidesPolicy {
+    // Treat MIN_SIDES as the lower bound, bouncing off it like any other
+    Clamp,
+    // Warn about it, but let them through
+    Warn,
+    // Let them through without a word
+    Allow,
+}
+
+impl Default for SidesPolicy {
+    fn default() -> Self {
+        Self::Clamp
+    }
+}
+
+// Event for when a shape is given a target it can't sensibly morph into.
+// Non-finite targets are dealt with as the config says, otherwise the morph still goes ahead;
+// this is for apps to surface the problem.
+pub struct InvalidTarget {
+    pub entity: Entity,
+    pub error: MorphError,
+}
+
+// The path with every NaN or infinite point replaced by the last finite point before it,
+// or the origin if there isn't one yet
+pub fn clamped(path: &Path) -> Path {
+    let mut last = Point::origin();
+    let mut clamp = |p: Point| {
+        if p.x.is_finite() && p.y.is_finite() {
+            last = p;
+        }
+        last
+    };
+    let mut builder = Path::builder();
+    for event in path.iter() {
+        match event {
+            Event::Begin { at } => {
+                builder.begin(clamp(at));
+            }
+            Event::Line { to, .. } => {
+                builder.line_to(clamp(to));
+            }
+            Event::Quadratic { ctrl, to, .. } => {
+                let ctrl = clamp(ctrl);
+                builder.quadratic_bezier_to(ctrl, clamp(to));
+            }
+            Event::Cubic {
+                ctrl1, ctrl2, to, ..
+            } => {
+                let (ctrl1, ctrl2) = (clamp(ctrl1), clamp(ctrl2));
+                builder.cubic_bezier_to(ctrl1, ctrl2, clamp(to));
+            }
+            Event::End { close, .. } => builder.end(close),
+        }
+    }
+    builder.build()
+}
+
+// Runs before targets are aligned, so a bad one never reaches `lerp_shape`
+pub fn check_targets(
+    config: Res<Config>,
+    mut errors: EventWriter<InvalidTarget>,
+    mut shapes: Query<(Entity, &PathComponent, &mut LerpingShape), Changed<LerpingShape>>,
+) {
+    for (entity, path, mut shape) in shapes.iter_mut() {
+        let error = match check(&shape.target) {
+            Ok(()) => continue,
+            Err(error) => error,
+        };
+        warn!("Invalid target for {:?}: {}", entity, error);
+        errors.send(InvalidTarget { entity, error });
+        if let MorphError::NonFinite { .. } = error {
+            shape.target = match config.non_finite {
+                NonFinitePolicy::Clamp => clamped(&shape.target),
+                NonFinitePolicy::Reject => path.0.clone(),
+            };
+        }
+    }
+}
+
+// Optional, for catching NaNs that get into the path some other way, like a NaN lerp_t
+pub fn check_live_paths(
+    config: Res<Config>,
+    mut errors: EventWriter<InvalidTarget>,
+    mut shapes: Query<(Entity, &mut PathComponent), (With<LerpingShape>, Changed<PathComponent>)>,
+) {
+    if !config.check_live_paths {
+        return;
+    }
+    for (entity, mut path) in shapes.iter_mut() {
+        if let Err(error @ MorphError::NonFinite { .. }) = check(&path.0) {
+            warn!("Invalid path for {:?}: {}", entity, error);
+            errors.send(InvalidTarget { entity, error });
+            path.0 = clamped(&path.0);
+        }
+    }
+}
+
+// Runs between stepping the sides and building the target from them.
+// Only warns as shapes are added, clamping sides that step below the minimum is routine.
+pub fn check_sides<T: RangeBounds<u8> + 'static + Send + Sync>(
+    config: Res<Config>,
+    mut shapes: Query<
+        (
+            Entity,
+            &mut SidesChangingShape<T>,
+            ChangeTrackers<SidesChangingShape<T>>,
+        ),
+        Changed<SidesChangingShape<T>>,
+    >,
+) {
+    if config.degenerate_sides == SidesPolicy::Allow {
+        return;
+    }
+    for (entity, mut sides, tracker) in shapes.iter_mut() {
+        if tracker.is_added() && (0..MIN_SIDES).any(|n| sides.bounds.contains(&n)) {
+            warn!(
+                "The bounds for {:?} allow fewer than {} sides, which makes for a degenerate shape",
+                entity, MIN_SIDES
+            );
+        }
+        if config.degenerate_sides == SidesPolicy::Clamp && sides.sides < MIN_SIDES {
+            sides.sides = MIN_SIDES;
+            sides.direction = Direction::Increasing;
+        }
+    }
+}
+
+// Debug builds only. Panics on the first malformed path, naming the entity, so corruption is
+// caught on the frame it happens rather than wherever it finally breaks something.
+#[cfg(debug_assertions)]
+pub fn assert_well_formed(shapes: Query<(Entity, &PathComponent), Changed<PathComponent>>) {
+    for (entity, path) in shapes.iter() {
+        if let Some(problem) = malformation(&path.0) {
+            panic!("The path of {:?} is malformed: {}", entity, problem);
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use crate::metrics;
+use crate::{AlignedTarget, LerpingShape, MainCamera};
+use bevy::prelude::*;
+use bevy_prototype_lyon::entity::Path as PathComponent;
+use bevy_prototype_lyon::prelude::*;
+use serde::Deserialize;
+use tess::path::Path;
+
+// What a shape does while it's outside the main camera's view
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Deserialize)]
+pub enum OffscreenPolicy {
+    // Carry on morphing as if it were on screen
+    Morph,
+    // Hold still, and carry on from where it was when it's back in view
+    Pause,
+    // Hold still, and jump straight to its target when it's back in view
+    Snap,
+}
+
+impl Default for OffscreenPolicy {
+    fn default() -> Self {
+        Self::Morph
+    }
+}
+
+impl OffscreenPolicy {
+    pub fn holds(self, on_screen: &OnScreen) -> bool {
+        self != Self::Morph && !on_screen.0
+    }
+}
+
+// Whether the shape's path or its target overlaps the main camera's view, only written when it
+// flips. Either, so a shape morphing into view from off screen or out of it isn't cut off.
+// Stays true without a window to view through.
+#[derive(Component)]
+pub struct OnScreen(pub bool);
+
+impl Default for OnScreen {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+// Curves only need to be roughly in the box for culling and picking a level of detail
+const BOUNDS_TOLERANCE: f32 = 1.0;
+
+// Local-space bounding box of the path, (min, max), to within a pixel or so
+pub fn bounds(path: &Path) -> (Vec2, Vec2) {
+    let (min, max) = metrics::bounding_box(path, BOUNDS_TOLERANCE);
+    (Vec2::new(min.x, min.y), Vec2::new(max.x, max.y))
+}
+
+// Assumes the camera isn't rotated, as nothing here rotates it
+pub fn update_on_screen(
+    windows: Res<Windows>,
+    cameras: Query<(&GlobalTransform, &OrthographicProjection), With<MainCamera>>,
+    mut shapes: Query<(
+        &GlobalTransform,
+        &PathComponent,
+        &AlignedTarget,
+        &mut OnScreen,
+    )>,
+) {
+    let (window, (camera, projection)) = match (windows.get_primary(), cameras.iter().next()) {
+        (Some(window), Some(camera)) => (window, camera),
+        _ => return,
+    };
+    let half_view = Vec2::new(window.width(), window.height()) / 2.0 * projection.scale;
+    let center = camera.translation.truncate();
+    let (view_min, view_max) = (center - half_view, center + half_view);
+    for (transform, path, aligned, mut on_screen) in shapes.iter_mut() {
+        let scale = transform.scale.truncate();
+        let position = transform.translation.truncate();
+        let (path_min, path_max) = bounds(&path.0);
+        let (min, max) = (
+            path_min.min(aligned.bounds.0),
+            path_max.max(aligned.bounds.1),
+        );
+        let (min, max) = (position + min * scale, position + max * scale);
+        let visible = min.x <= view_max.x
+            && max.x >= view_min.x
+            && min.y <= view_max.y
+            && max.y >= view_min.y;
+        if on_screen.0 != visible {
+            on_screen.0 = visible;
+        }
+    }
+}
+
+// Puts shapes with the Snap policy that just came back into view straight onto their target,
+// which the next lerp then finishes off as usual
+pub fn snap_on_reentry(
+    mut shapes: Query<
+        (&mut PathComponent, &LerpingShape, &AlignedTarget, &OnScreen),
+        Changed<OnScreen>,
+    >,
+) {
+    for (mut path, shape, aligned, on_screen) in shapes.iter_mut() {
+        if on_screen.0 && shape.offscreen == OffscreenPolicy::Snap {
+            path.0 = aligned.target(&shape.target).clone();
+        }
+    }
+}
@@ -0,0 +1,210 @@
+// Accepts JSON commands over WebSocket connections, one reply per command:
+//   {"command": "set_target", "shape": 0, "svg": "M 0 0 L 100 0 L 50 80 Z", "width": 100, "height": 80}
+//   {"command": "set_sides", "shape": 0, "sides": 6}
+//   {"command": "set_params", "lerp_t": 0.05, "margin_of_error": 0.5}
+//   {"command": "query_progress"}
+// `shape` is a shape id, leaving it out applies the command to every shape.
+use crate::control::MorphCommand;
+use crate::target::TargetShape;
+use crate::{LerpingShape, ShapeId};
+use bevy::prelude::*;
+use bevy_prototype_lyon::entity::Path as PathComponent;
+use bevy_prototype_lyon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{
+    io,
+    net::{SocketAddr, TcpListener, TcpStream},
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Mutex,
+    },
+    thread,
+};
+use tess::path::Path;
+use tungstenite::Message;
+
+#[derive(Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+enum RemoteCommand {
+    SetTarget {
+        shape: Option<u32>,
+        svg: String,
+        width: f32,
+        height: f32,
+    },
+    SetSides {
+        shape: Option<u32>,
+        sides: u8,
+    },
+    SetParams {
+        shape: Option<u32>,
+        lerp_t: Option<f32>,
+        margin_of_error: Option<f32>,
+    },
+    QueryProgress,
+}
+
+#[derive(Serialize)]
+struct ShapeProgress {
+    shape: u32,
+    // Furthest any point still has to travel to reach the target
+    remaining: f32,
+    snapped: bool,
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+enum Response {
+    Ok { ok: bool },
+    Error { error: String },
+    Progress { shapes: Vec<ShapeProgress> },
+}
+
+impl Response {
+    fn error(error: impl ToString) -> Self {
+        Self::Error {
+            error: error.to_string(),
+        }
+    }
+}
+
+// Commands have to be answered from inside the app, so connections wait on a reply channel
+struct Request {
+    command: RemoteCommand,
+    reply: Sender<Response>,
+}
+
+pub struct RemoteRequests(Mutex<Receiver<Request>>);
+
+impl RemoteRequests {
+    pub fn listen(addr: SocketAddr) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                match stream {
+                    Ok(stream) => {
+                        let sender = sender.clone();
+                        thread::spawn(move || serve(stream, sender));
+                    }
+                    Err(e) => warn!("Failed to accept WebSocket connection: {}", e),
+                }
+            }
+        });
+        Ok(Self(Mutex::new(receiver)))
+    }
+}
+
+fn serve(stream: TcpStream, requests: Sender<Request>) {
+    let mut socket = match tungstenite::accept(stream) {
+        Ok(socket) => socket,
+        Err(e) => {
+            warn!("WebSocket handshake failed: {}", e);
+            return;
+        }
+    };
+    loop {
+        let text = match socket.read_message() {
+            Ok(Message::Text(text)) => text,
+            Ok(Message::Close(_)) | Err(_) => return,
+            Ok(_) => continue,
+        };
+        let response = match serde_json::from_str(&text) {
+            Ok(command) => {
+                let (reply, response) = mpsc::channel();
+                if requests.send(Request { command, reply }).is_err() {
+                    return;
+                }
+                match response.recv() {
+                    Ok(response) => response,
+                    Err(_) => return,
+                }
+            }
+            Err(e) => Response::error(e),
+        };
+        let json = serde_json::to_string(&response).expect("Responses are always serializable");
+        if socket.write_message(Message::Text(json)).is_err() {
+            return;
+        }
+    }
+}
+
+// Unequal paths are padded at the front while lerping, so corresponding events line up from the back
+fn remaining_distance(from: &Path, to: &Path) -> f32 {
+    let from = from.iter().collect::<Vec<_>>();
+    let to = to.iter().collect::<Vec<_>>();
+    from.iter()
+        .rev()
+        .zip(to.iter().rev())
+        .map(|(a, b)| a.to().distance_to(b.to()))
+        .fold(0.0, f32::max)
+}
+
+pub fn handle_remote_requests(
+    requests: Res<RemoteRequests>,
+    shapes: Query<(Entity, &ShapeId, &PathComponent, &LerpingShape)>,
+    mut commands: EventWriter<MorphCommand>,
+) {
+    let find = |shape: Option<u32>| match shape {
+        None => Ok(None),
+        Some(id) => shapes
+            .iter()
+            .find(|(_, shape_id, _, _)| shape_id.0 == id)
+            .map(|(entity, _, _, _)| Some(entity))
+            .ok_or_else(|| Response::error(format!("No shape with id {}", id))),
+    };
+
+    for Request { command, reply } in requests.0.lock().unwrap().try_iter() {
+        let response = match command {
+            RemoteCommand::SetTarget {
+                shape,
+                svg,
+                width,
+                height,
+            } => find(shape).map(|entity| {
+                commands.send(MorphCommand::SetTarget {
+                    entity,
+                    target: TargetShape::Svg {
+                        path: svg,
+                        size: Vec2::new(width, height),
+                    },
+                })
+            }),
+            RemoteCommand::SetSides { shape, sides } => {
+                find(shape).map(|entity| commands.send(MorphCommand::SetSides { entity, sides }))
+            }
+            RemoteCommand::SetParams {
+                shape,
+                lerp_t,
+                margin_of_error,
+            } => find(shape).map(|entity| {
+                if let Some(lerp_t) = lerp_t {
+                    commands.send(MorphCommand::SetLerpT { entity, lerp_t });
+                }
+                if let Some(margin_of_error) = margin_of_error {
+                    commands.send(MorphCommand::SetMarginOfError {
+                        entity,
+                        margin_of_error,
+                    });
+                }
+            }),
+            RemoteCommand::QueryProgress => {
+                let _ = reply.send(Response::Progress {
+                    shapes: shapes
+                        .iter()
+                        .map(|(_, id, path, shape)| {
+                            let remaining = remaining_distance(&path.0, &shape.target);
+                            ShapeProgress {
+                                shape: id.0,
+                                remaining,
+                                snapped: remaining <= shape.margin_of_error,
+                            }
+                        })
+                        .collect(),
+                });
+                continue;
+            }
+        };
+        let _ = reply.send(response.map_or_else(|e| e, |()| Response::Ok { ok: true }));
+    }
+}
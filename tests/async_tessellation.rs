@@ -0,0 +1,85 @@
+mod support;
+
+use bevy::prelude::*;
+use bevy::render::mesh::{Indices, VertexAttributeValues};
+use bevy_prototype_lyon::prelude::*;
+use shape_lerping::async_tessellation::tessellate;
+use support::polygon;
+
+fn positions(mesh: &Mesh) -> &[[f32; 3]] {
+    match mesh.attribute(Mesh::ATTRIBUTE_POSITION) {
+        Some(VertexAttributeValues::Float32x3(positions)) => positions,
+        _ => panic!("Mesh has no positions"),
+    }
+}
+
+fn colors(mesh: &Mesh) -> &[[f32; 4]] {
+    match mesh.attribute(Mesh::ATTRIBUTE_COLOR) {
+        Some(VertexAttributeValues::Float32x4(colors)) => colors,
+        _ => panic!("Mesh has no colors"),
+    }
+}
+
+fn indices(mesh: &Mesh) -> &[u32] {
+    match mesh.indices() {
+        Some(Indices::U32(indices)) => indices,
+        _ => panic!("Mesh has no indices"),
+    }
+}
+
+fn assert_moves_like_full_tessellation(mode: DrawMode) {
+    let from = polygon(&[
+        (0.0, 0.0),
+        (100.0, 0.0),
+        (120.0, 80.0),
+        (40.0, 110.0),
+        (-20.0, 60.0),
+    ]);
+    // Every point moved, but not so far any triangle turns over
+    let to = polygon(&[
+        (10.0, -5.0),
+        (105.0, 8.0),
+        (118.0, 90.0),
+        (35.0, 100.0),
+        (-10.0, 55.0),
+    ]);
+    let (mut mesh, layout) = tessellate(&from, &mode);
+    let layout = layout.expect("Straight fills should have a layout");
+    assert!(layout.move_mesh(&to, &mode, &mut mesh));
+
+    let (full, _) = tessellate(&to, &mode);
+    assert_eq!(positions(&mesh), positions(&full));
+    assert_eq!(colors(&mesh), colors(&full));
+    assert_eq!(indices(&mesh), indices(&full));
+}
+
+#[test]
+fn moved_fill_matches_full_tessellation() {
+    assert_moves_like_full_tessellation(DrawMode::Fill(FillMode::color(Color::WHITE)));
+}
+
+#[test]
+fn moved_outlined_fill_matches_full_tessellation() {
+    assert_moves_like_full_tessellation(DrawMode::Outlined {
+        fill_mode: FillMode::color(Color::WHITE),
+        outline_mode: StrokeMode::new(Color::BLACK, 4.0),
+    });
+}
+
+#[test]
+fn strokes_have_no_layout() {
+    let path = polygon(&[(0.0, 0.0), (100.0, 0.0), (50.0, 80.0)]);
+    let (_, layout) = tessellate(&path, &DrawMode::Stroke(StrokeMode::new(Color::BLACK, 4.0)));
+    assert!(layout.is_none());
+}
+
+#[test]
+fn turned_over_triangles_need_a_full_tessellation() {
+    let mode = DrawMode::Fill(FillMode::color(Color::WHITE));
+    let from = polygon(&[(0.0, 0.0), (100.0, 0.0), (50.0, 80.0)]);
+    let flipped = polygon(&[(0.0, 0.0), (100.0, 0.0), (50.0, -80.0)]);
+    let (mut mesh, layout) = tessellate(&from, &mode);
+    let before = positions(&mesh).to_vec();
+    assert!(!layout.unwrap().move_mesh(&flipped, &mode, &mut mesh));
+    assert_eq!(positions(&mesh), &before[..]);
+}
@@ -0,0 +1,112 @@
+mod support;
+
+use bevy::math::Vec2;
+use bevy_prototype_lyon::prelude::*;
+use shape_lerping::boolean::{combined, BooleanOp};
+use shape_lerping::metrics::signed_area;
+use support::{polygon, square};
+use tess::path::{Event, Path};
+
+const TOLERANCE: f32 = 0.01;
+
+fn area(path: &Path) -> f32 {
+    signed_area(path, TOLERANCE)
+}
+
+fn combined_area(a: &Path, b: &Path, op: BooleanOp) -> f32 {
+    area(&combined(a, b, op, TOLERANCE))
+}
+
+fn subpaths(path: &Path) -> usize {
+    path.iter()
+        .filter(|event| matches!(event, Event::Begin { .. }))
+        .count()
+}
+
+fn assert_close(a: f32, b: f32) {
+    assert!((a - b).abs() < 1e-3, "{} != {}", a, b);
+}
+
+#[test]
+fn overlapping_squares_combine() {
+    let (a, b) = (square(0.0, 0.0, 10.0), square(5.0, 5.0, 10.0));
+    assert_close(combined_area(&a, &b, BooleanOp::Union), 175.0);
+    assert_close(combined_area(&a, &b, BooleanOp::Intersection), 25.0);
+    assert_close(combined_area(&a, &b, BooleanOp::Difference), 75.0);
+    assert_close(combined_area(&b, &a, BooleanOp::Difference), 75.0);
+}
+
+#[test]
+fn disjoint_squares_are_left_as_they_are() {
+    let (a, b) = (square(0.0, 0.0, 10.0), square(20.0, 0.0, 10.0));
+    let union = combined(&a, &b, BooleanOp::Union, TOLERANCE);
+    assert_close(area(&union), 200.0);
+    assert_eq!(subpaths(&union), 2);
+    let intersection = combined(&a, &b, BooleanOp::Intersection, TOLERANCE);
+    assert_eq!(intersection.iter().count(), 0);
+    assert_close(combined_area(&a, &b, BooleanOp::Difference), 100.0);
+}
+
+#[test]
+fn cutting_out_the_middle_leaves_a_hole() {
+    let (outer, inner) = (square(0.0, 0.0, 20.0), square(5.0, 5.0, 10.0));
+    let ring = combined(&outer, &inner, BooleanOp::Difference, TOLERANCE);
+    assert_close(area(&ring), 300.0);
+    assert_eq!(subpaths(&ring), 2);
+    assert_eq!(
+        combined(&inner, &outer, BooleanOp::Difference, TOLERANCE)
+            .iter()
+            .count(),
+        0
+    );
+}
+
+#[test]
+fn squares_sharing_an_edge_merge() {
+    let (a, b) = (square(0.0, 0.0, 10.0), square(10.0, 0.0, 10.0));
+    let union = combined(&a, &b, BooleanOp::Union, TOLERANCE);
+    assert_close(area(&union), 200.0);
+    assert_eq!(subpaths(&union), 1);
+    // In a corner of `a`, along two of its edges
+    let left = square(0.0, 0.0, 5.0);
+    assert_close(combined_area(&a, &left, BooleanOp::Difference), 75.0);
+    assert_close(combined_area(&a, &left, BooleanOp::Intersection), 25.0);
+}
+
+#[test]
+fn identical_shapes_combine_into_themselves() {
+    let a = square(0.0, 0.0, 10.0);
+    assert_close(combined_area(&a, &a, BooleanOp::Union), 100.0);
+    assert_close(combined_area(&a, &a, BooleanOp::Intersection), 100.0);
+    assert_eq!(
+        combined(&a, &a, BooleanOp::Difference, TOLERANCE)
+            .iter()
+            .count(),
+        0
+    );
+}
+
+#[test]
+fn winding_doesnt_matter() {
+    let clockwise = polygon(&[(0.0, 0.0), (0.0, 10.0), (10.0, 10.0), (10.0, 0.0)]);
+    let b = square(5.0, 5.0, 10.0);
+    assert_close(combined_area(&clockwise, &b, BooleanOp::Union), 175.0);
+    assert_close(combined_area(&b, &clockwise, BooleanOp::Difference), 75.0);
+}
+
+#[test]
+fn curves_are_flattened() {
+    let circle = ShapePath::build_as(&shapes::Circle {
+        radius: 5.0,
+        center: Vec2::new(10.0, 10.0),
+    })
+    .0;
+    let ring = combined(
+        &square(0.0, 0.0, 20.0),
+        &circle,
+        BooleanOp::Difference,
+        TOLERANCE,
+    );
+    let expected = 400.0 - std::f32::consts::PI * 25.0;
+    assert!((area(&ring) - expected).abs() < 0.5, "{}", area(&ring));
+}
@@ -0,0 +1,25 @@
+use shape_lerping::config::Config;
+use std::time::Duration;
+
+fn morph_duration(morph_seconds: f32) -> Option<Duration> {
+    Config {
+        morph_seconds,
+        ..Config::default()
+    }
+    .morph_duration()
+}
+
+#[test]
+fn only_positive_seconds_make_timed_morphs() {
+    assert_eq!(morph_duration(0.0), None);
+    assert_eq!(morph_duration(-1.0), None);
+    assert_eq!(morph_duration(f32::NAN), None);
+    assert_eq!(morph_duration(1.5), Some(Duration::from_millis(1500)));
+}
+
+#[test]
+fn huge_seconds_are_capped_instead_of_overflowing() {
+    let capped = morph_duration(f32::INFINITY).unwrap();
+    assert_eq!(morph_duration(f32::MAX), Some(capped));
+    assert!(capped >= Duration::from_secs(60 * 60));
+}
@@ -0,0 +1,41 @@
+use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::*;
+use shape_lerping::crossfade::blended;
+
+fn outlined(mode: DrawMode) -> (FillMode, StrokeMode) {
+    match mode {
+        DrawMode::Outlined {
+            fill_mode,
+            outline_mode,
+        } => (fill_mode, outline_mode),
+        _ => panic!("a cross-fade is always drawn Outlined"),
+    }
+}
+
+#[test]
+fn stroke_to_stroke_keeps_the_fill_transparent() {
+    let from = DrawMode::Stroke(StrokeMode::new(Color::RED, 2.0));
+    let to = DrawMode::Stroke(StrokeMode::new(Color::BLUE, 6.0));
+    for &t in &[0.0, 0.5, 1.0] {
+        let (fill, outline) = outlined(blended(&from, &to, t));
+        assert_eq!(fill.color.a(), 0.0, "t = {}", t);
+        assert_eq!(outline.options.line_width, 2.0 + 4.0 * t, "t = {}", t);
+    }
+    let (_, outline) = outlined(blended(&from, &to, 1.0));
+    assert_eq!(outline.color, Color::BLUE);
+}
+
+#[test]
+fn stroke_to_fill_fades_the_fill_in_and_the_stroke_out() {
+    let from = DrawMode::Stroke(StrokeMode::new(Color::RED, 2.0));
+    let to = DrawMode::Fill(FillMode::color(Color::GREEN));
+    let (fill, outline) = outlined(blended(&from, &to, 0.0));
+    assert_eq!(fill.color.a(), 0.0);
+    assert_eq!(outline.color.a(), 1.0);
+    let (fill, outline) = outlined(blended(&from, &to, 0.5));
+    assert!((fill.color.a() - 0.5).abs() < 1e-6);
+    assert!((outline.color.a() - 0.5).abs() < 1e-6);
+    let (fill, outline) = outlined(blended(&from, &to, 1.0));
+    assert_eq!(fill.color, Color::GREEN);
+    assert_eq!(outline.color.a(), 0.0);
+}
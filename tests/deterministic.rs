@@ -0,0 +1,81 @@
+use std::{env, fs, path::PathBuf, process::Command};
+
+// Runs the demo headless for a while, returning the checksum line it prints on exit
+fn checksum(args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_shape-lerping"))
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .args(&["--headless", "--deterministic", "--frames", "120"])
+        .args(args)
+        .output()
+        .expect("Failed to run the demo");
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    stdout
+        .lines()
+        .find(|line| line.starts_with("Checksum"))
+        .unwrap_or_else(|| panic!("No checksum in {:?}", stdout))
+        .to_string()
+}
+
+// Written where the demo can read it, for runs that need a config or recipe file
+fn fixture(name: &str, contents: &str) -> PathBuf {
+    let path = env::temp_dir().join(format!("shape-lerping-deterministic-{}", name));
+    fs::write(&path, contents).expect("Failed to write fixture");
+    path
+}
+
+fn checksum_with(flag: &str, file: &PathBuf) -> String {
+    checksum(&[flag, file.to_str().unwrap()])
+}
+
+#[test]
+fn two_runs_match() {
+    assert_eq!(checksum(&[]), checksum(&[]));
+}
+
+#[test]
+fn different_morphs_differ() {
+    assert_ne!(checksum(&[]), checksum(&["--lerp-t", "0.2"]));
+}
+
+#[test]
+fn timed_morphs_match() {
+    let timed = checksum(&["--morph-seconds", "0.5"]);
+    assert_eq!(timed, checksum(&["--morph-seconds", "0.5"]));
+    assert_ne!(timed, checksum(&[]));
+}
+
+#[test]
+fn springs_match() {
+    let config = fixture("spring.ron", "(lerp_mode: Spring)");
+    let sprung = checksum_with("--config", &config);
+    assert_eq!(sprung, checksum_with("--config", &config));
+    assert_ne!(sprung, checksum(&[]));
+}
+
+#[test]
+fn recipes_match() {
+    let recipe = fixture(
+        "recipe.ron",
+        "(
+            shapes: [(
+                position: (0, 0),
+                steps: [
+                    (target: Polygon(sides: 3, radius: 200), duration: 0.5),
+                    (target: Ellipse(radii: (200, 100)), duration: 0.5, easing: QuadInOut),
+                ],
+            )],
+            loop_mode: PingPong,
+            slideshow: Some((
+                slides: [
+                    (name: \"Triangle\", target: Polygon(sides: 3, radius: 200), duration: 0.5),
+                    (name: \"Oval\", target: Ellipse(radii: (200, 100)), duration: 1.0),
+                ],
+            )),
+        )",
+    );
+    assert_eq!(
+        checksum_with("--recipe", &recipe),
+        checksum_with("--recipe", &recipe)
+    );
+}
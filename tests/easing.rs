@@ -0,0 +1,66 @@
+use shape_lerping::easing::Easing;
+
+const ALL: &[Easing] = &[
+    Easing::Linear,
+    Easing::QuadIn,
+    Easing::QuadOut,
+    Easing::QuadInOut,
+    Easing::CubicIn,
+    Easing::CubicOut,
+    Easing::CubicInOut,
+    Easing::SineIn,
+    Easing::SineOut,
+    Easing::SineInOut,
+    Easing::ElasticIn,
+    Easing::ElasticOut,
+    Easing::BounceIn,
+    Easing::BounceOut,
+];
+
+#[test]
+fn every_easing_starts_at_0_and_ends_at_1() {
+    for &easing in ALL {
+        assert!(easing.apply(0.0).abs() < 1e-6, "{:?}", easing);
+        assert_eq!(easing.apply(1.0), 1.0, "{:?}", easing);
+    }
+}
+
+#[test]
+fn in_outs_are_halfway_at_halfway() {
+    for &easing in &[Easing::QuadInOut, Easing::CubicInOut, Easing::SineInOut] {
+        assert!((easing.apply(0.5) - 0.5).abs() < 1e-6, "{:?}", easing);
+    }
+}
+
+#[test]
+fn only_elastic_easings_overshoot() {
+    let samples = (0..=100).map(|i| i as f32 / 100.0);
+    for &easing in ALL {
+        let overshoots = samples
+            .clone()
+            .any(|t| !(-1e-6..=1.0 + 1e-6).contains(&easing.apply(t)));
+        let elastic = matches!(easing, Easing::ElasticIn | Easing::ElasticOut);
+        assert_eq!(overshoots, elastic, "{:?}", easing);
+    }
+}
+
+#[test]
+fn cubic_beziers_match_css() {
+    // CSS's `ease` and `ease-in-out`
+    let ease = Easing::CubicBezier(0.25, 0.1, 0.25, 1.0);
+    assert!((ease.apply(0.5) - 0.8024).abs() < 1e-3);
+    let ease_in_out = Easing::CubicBezier(0.42, 0.0, 0.58, 1.0);
+    assert!((ease_in_out.apply(0.5) - 0.5).abs() < 1e-5);
+    let linear = Easing::CubicBezier(0.0, 0.0, 1.0, 1.0);
+    for t in (0..=10).map(|i| i as f32 / 10.0) {
+        assert!((linear.apply(t) - t).abs() < 1e-5, "{}", t);
+    }
+}
+
+#[test]
+fn cubic_beziers_can_overshoot_but_end_exactly() {
+    let back_out = Easing::CubicBezier(0.34, 1.56, 0.64, 1.0);
+    assert!((0..=100).any(|i| back_out.apply(i as f32 / 100.0) > 1.0));
+    assert_eq!(back_out.apply(0.0), 0.0);
+    assert_eq!(back_out.apply(1.0), 1.0);
+}
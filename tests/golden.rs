@@ -0,0 +1,58 @@
+mod support;
+
+use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::*;
+use shape_lerping::path_lerping::Lerp;
+use tess::path::Path;
+
+fn polygon(sides: usize) -> Path {
+    ShapePath::build_as(&shapes::RegularPolygon {
+        sides,
+        feature: shapes::RegularPolygonFeature::Radius(200.0),
+        ..Default::default()
+    })
+    .0
+}
+
+fn ellipse() -> Path {
+    ShapePath::build_as(&shapes::Ellipse {
+        radii: Vec2::new(200.0, 120.0),
+        ..Default::default()
+    })
+    .0
+}
+
+// A zero margin never snaps, so this is the morph at exactly `t`
+fn morph(from: &Path, to: &Path, t: f32) -> Path {
+    from.lerped(to, Vec2::splat(t), 0.0).1
+}
+
+#[test]
+fn triangle_to_octagon() {
+    for &t in &[0.0, 0.25, 0.5, 0.75, 1.0] {
+        support::golden::assert_golden(
+            &format!("triangle_to_octagon_{}", t),
+            &morph(&polygon(3), &polygon(8), t),
+        );
+    }
+}
+
+#[test]
+fn octagon_to_triangle() {
+    for &t in &[0.25, 0.5, 0.75] {
+        support::golden::assert_golden(
+            &format!("octagon_to_triangle_{}", t),
+            &morph(&polygon(8), &polygon(3), t),
+        );
+    }
+}
+
+#[test]
+fn pentagon_to_ellipse() {
+    for &t in &[0.25, 0.5, 0.75] {
+        support::golden::assert_golden(
+            &format!("pentagon_to_ellipse_{}", t),
+            &morph(&polygon(5), &ellipse(), t),
+        );
+    }
+}
@@ -0,0 +1,56 @@
+mod support;
+
+use bevy::math::Vec2;
+use bevy_prototype_lyon::prelude::*;
+use shape_lerping::gradient::{gradient_lerped, LerpGradient};
+use shape_lerping::path_lerping::Lerp;
+use support::{events, points_reached, polyline};
+
+#[test]
+fn scales_between_stops() {
+    let gradient = LerpGradient::new(vec![(0.25, 2.0), (0.75, 0.0)]);
+    assert_eq!(gradient.scale_at(0.0), 2.0);
+    assert_eq!(gradient.scale_at(0.5), 1.0);
+    assert_eq!(gradient.scale_at(1.0), 0.0);
+    assert_eq!(LerpGradient::default().scale_at(0.5), 1.0);
+}
+
+#[test]
+fn faster_where_the_gradient_is_higher() {
+    let path = polyline(&[(0.0, 0.0), (10.0, 0.0), (20.0, 0.0), (30.0, 0.0)], false);
+    let target = polyline(
+        &[(0.0, 10.0), (10.0, 10.0), (20.0, 10.0), (30.0, 10.0)],
+        false,
+    );
+    let gradient = LerpGradient::new(vec![(0.0, 1.0), (1.0, 0.0)]);
+    let (snapped, lerped) = gradient_lerped(
+        &path,
+        &target,
+        &events(&target),
+        Vec2::splat(0.5),
+        &gradient,
+        0.01,
+    );
+    assert!(!snapped);
+    let heights = points_reached(&lerped)[..4]
+        .iter()
+        .map(|point| point.y)
+        .collect::<Vec<_>>();
+    for (height, expected) in heights.iter().zip(&[5.0, 10.0 / 3.0, 5.0 / 3.0, 0.0]) {
+        assert!((height - expected).abs() < 1e-4, "{:?}", heights);
+    }
+}
+
+#[test]
+fn without_stops_it_lerps_as_usual() {
+    let path = polyline(&[(0.0, 0.0), (10.0, 0.0), (10.0, 10.0)], true);
+    let target = polyline(&[(5.0, 5.0), (20.0, 0.0), (20.0, 20.0)], true);
+    let aligned = events(&target);
+    let gradient = LerpGradient::default();
+    let t = Vec2::new(0.25, 0.5);
+    let (_, lerped) = gradient_lerped(&path, &target, &aligned, t, &gradient, 0.01);
+    assert!(lerped.iter().eq((&path).lerped(&target, t, 0.01).1.iter()));
+    let (snapped, lerped) = gradient_lerped(&path, &target, &aligned, Vec2::ONE, &gradient, 0.01);
+    assert!(snapped);
+    assert!(lerped.iter().eq(target.iter()));
+}
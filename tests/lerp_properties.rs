@@ -0,0 +1,141 @@
+use bevy::math::Vec2;
+use bevy_prototype_lyon::prelude::*;
+use proptest::{collection::vec, prelude::*};
+use shape_lerping::path_lerping::{malformation, Lerp};
+use tess::{math::point, path::Path};
+
+type Point = [f32; 2];
+
+#[derive(Clone, Debug)]
+enum Segment {
+    Line(Point),
+    Quadratic(Point, Point),
+    Cubic(Point, Point, Point),
+}
+
+#[derive(Clone, Debug)]
+struct Subpath {
+    start: Point,
+    segments: Vec<Segment>,
+    close: bool,
+}
+
+fn coordinate() -> impl Strategy<Value = Point> {
+    [-1000.0f32..1000.0, -1000.0f32..1000.0]
+}
+
+fn segment() -> impl Strategy<Value = Segment> {
+    prop_oneof![
+        coordinate().prop_map(Segment::Line),
+        (coordinate(), coordinate()).prop_map(|(ctrl, to)| Segment::Quadratic(ctrl, to)),
+        (coordinate(), coordinate(), coordinate())
+            .prop_map(|(ctrl1, ctrl2, to)| Segment::Cubic(ctrl1, ctrl2, to)),
+    ]
+}
+
+fn subpath() -> impl Strategy<Value = Subpath> {
+    (coordinate(), vec(segment(), 0..12), any::<bool>()).prop_map(|(start, segments, close)| {
+        Subpath {
+            start,
+            segments,
+            close,
+        }
+    })
+}
+
+// Any well-formed path, empty included: every subpath begins, has some segments, and ends
+fn subpaths() -> impl Strategy<Value = Vec<Subpath>> {
+    vec(subpath(), 0..4)
+}
+
+fn build(subpaths: &[Subpath]) -> Path {
+    let p = |[x, y]: Point| point(x, y);
+    let mut builder = Path::builder();
+    for subpath in subpaths {
+        builder.begin(p(subpath.start));
+        for segment in &subpath.segments {
+            match *segment {
+                Segment::Line(to) => {
+                    builder.line_to(p(to));
+                }
+                Segment::Quadratic(ctrl, to) => {
+                    builder.quadratic_bezier_to(p(ctrl), p(to));
+                }
+                Segment::Cubic(ctrl1, ctrl2, to) => {
+                    builder.cubic_bezier_to(p(ctrl1), p(ctrl2), p(to));
+                }
+            }
+        }
+        builder.end(subpath.close);
+    }
+    builder.build()
+}
+
+fn count(path: &Path) -> usize {
+    path.iter().count()
+}
+
+proptest! {
+    #[test]
+    fn lerping_never_panics(
+        from in subpaths(),
+        to in subpaths(),
+        t in 0.0f32..=1.0,
+        margin in 0.0f32..10.0,
+    ) {
+        let _ = build(&from).lerped(&build(&to), Vec2::splat(t), margin);
+    }
+
+    #[test]
+    fn lerped_paths_are_well_formed(
+        from in subpaths(),
+        to in subpaths(),
+        t in 0.0f32..=1.0,
+        margin in 0.0f32..10.0,
+    ) {
+        let (_, result) = build(&from).lerped(&build(&to), Vec2::splat(t), margin);
+        prop_assert_eq!(malformation(&result), None);
+    }
+
+    #[test]
+    fn full_step_with_huge_margin_snaps(from in subpaths(), to in subpaths()) {
+        let (snapped, _) = build(&from).lerped(&build(&to), Vec2::ONE, f32::MAX);
+        prop_assert!(snapped);
+    }
+
+    // Float error alone can leave points stuck a hair off their targets,
+    // which a margin of the default snap epsilon has to cover
+    #[test]
+    fn repeated_halving_snaps_within_the_epsilon(from in subpaths(), to in subpaths()) {
+        let (mut path, to) = (build(&from), build(&to));
+        let mut snapped = false;
+        for _ in 0..64 {
+            let (step_snapped, result) = path.lerped(&to, Vec2::splat(0.5), 1e-4);
+            path = result;
+            snapped = step_snapped;
+            if snapped {
+                break;
+            }
+        }
+        prop_assert!(snapped);
+    }
+
+    // Shrinking morphs collapse to the target once they snap, dropping the padding.
+    // Mismatched subpaths are padded one pair at a time instead, see `padding.rs`.
+    #[test]
+    fn output_has_as_many_events_as_the_longer_input(
+        from in subpaths(),
+        to in subpaths(),
+        t in 0.0f32..=1.0,
+        margin in 0.0f32..10.0,
+    ) {
+        prop_assume!(from.len() == to.len() || from.is_empty() || to.is_empty());
+        let (from, to) = (build(&from), build(&to));
+        let (snapped, result) = from.lerped(&to, Vec2::splat(t), margin);
+        if snapped && count(&from) > count(&to) {
+            prop_assert_eq!(count(&result), count(&to));
+        } else {
+            prop_assert_eq!(count(&result), count(&from).max(count(&to)));
+        }
+    }
+}
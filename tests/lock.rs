@@ -0,0 +1,52 @@
+mod support;
+
+use bevy_prototype_lyon::prelude::*;
+use shape_lerping::lock::{locked_target, LockedEvents};
+use support::{points_reached, polygon};
+use tess::math::point;
+
+#[test]
+fn locked_events_stay_where_the_path_has_them() {
+    let path = polygon(&[(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)]);
+    let target = polygon(&[(5.0, 5.0), (20.0, 0.0), (20.0, 20.0), (0.0, 20.0)]);
+    let locked = locked_target(&path, &target, &LockedEvents(vec![1..3]));
+    assert_eq!(
+        points_reached(&locked)[..4],
+        [
+            point(5.0, 5.0),
+            point(10.0, 0.0),
+            point(10.0, 10.0),
+            point(0.0, 20.0)
+        ]
+    );
+}
+
+#[test]
+fn locking_lines_up_with_padding() {
+    // Begin, Line, End, so the target's second event lines up with the path's fourth
+    let path = polygon(&[(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)]);
+    let target = polygon(&[(5.0, 5.0), (20.0, 0.0)]);
+    let locked = locked_target(&path, &target, &LockedEvents(vec![1..2]));
+    assert_eq!(points_reached(&locked)[1], point(0.0, 10.0));
+    // And the other way round, the path's events line up with the end of the target's, and its
+    // Begin can't stand in for a Line
+    let locked = locked_target(&target, &path, &LockedEvents(vec![0..4]));
+    assert_eq!(
+        points_reached(&locked)[..4],
+        [
+            point(0.0, 0.0),
+            point(10.0, 0.0),
+            point(10.0, 10.0),
+            point(20.0, 0.0)
+        ]
+    );
+}
+
+#[test]
+fn locked_ranges_can_come_from_metadata() {
+    let tail = [false, true, true, false, true];
+    assert_eq!(
+        LockedEvents::matching(&tail, |&locked| locked),
+        LockedEvents(vec![1..3, 4..5])
+    );
+}
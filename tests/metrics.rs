@@ -0,0 +1,72 @@
+mod support;
+
+use bevy_prototype_lyon::prelude::*;
+use shape_lerping::metrics::{bounding_box, centroid, length, signed_area};
+use support::{polyline, square_points};
+use tess::{
+    math::{point, Point},
+    path::Path,
+};
+
+const TOLERANCE: f32 = 0.01;
+
+fn square(close: bool) -> Path {
+    polyline(&square_points(0.0, 0.0, 10.0), close)
+}
+
+#[test]
+fn squares_measure_up() {
+    let square = square(true);
+    assert_eq!(length(&square, TOLERANCE), 40.0);
+    assert_eq!(signed_area(&square, TOLERANCE), 100.0);
+    assert_eq!(centroid(&square, TOLERANCE), point(5.0, 5.0));
+    assert_eq!(
+        bounding_box(&square, TOLERANCE),
+        (point(0.0, 0.0), point(10.0, 10.0))
+    );
+}
+
+#[test]
+fn open_paths_have_no_closing_edge_but_still_enclose_an_area() {
+    let square = square(false);
+    assert_eq!(length(&square, TOLERANCE), 30.0);
+    assert_eq!(signed_area(&square, TOLERANCE), 100.0);
+}
+
+#[test]
+fn clockwise_outlines_have_negative_area() {
+    let clockwise = polyline(&[(0.0, 0.0), (0.0, 10.0), (10.0, 10.0), (10.0, 0.0)], true);
+    assert_eq!(signed_area(&clockwise, TOLERANCE), -100.0);
+    assert_eq!(centroid(&clockwise, TOLERANCE), point(5.0, 5.0));
+}
+
+#[test]
+fn lines_fall_back_to_the_mean_of_their_points() {
+    let line = polyline(&[(0.0, 0.0), (10.0, 0.0), (20.0, 0.0)], false);
+    assert_eq!(signed_area(&line, TOLERANCE), 0.0);
+    assert_eq!(centroid(&line, TOLERANCE), point(10.0, 0.0));
+}
+
+#[test]
+fn empty_paths_measure_nothing() {
+    let empty = Path::new();
+    assert_eq!(length(&empty, TOLERANCE), 0.0);
+    assert_eq!(signed_area(&empty, TOLERANCE), 0.0);
+    assert_eq!(centroid(&empty, TOLERANCE), Point::origin());
+    assert_eq!(
+        bounding_box(&empty, TOLERANCE),
+        (Point::origin(), Point::origin())
+    );
+}
+
+#[test]
+fn curves_are_measured_to_within_the_tolerance() {
+    let mut builder = Path::builder();
+    builder.begin(point(0.0, 0.0));
+    builder.quadratic_bezier_to(point(5.0, 10.0), point(10.0, 0.0));
+    builder.end(true);
+    let (min, max) = bounding_box(&builder.build(), TOLERANCE);
+    // The curve peaks halfway to its control point
+    assert_eq!((min, max.x), (point(0.0, 0.0), 10.0));
+    assert!((max.y - 5.0).abs() <= TOLERANCE);
+}
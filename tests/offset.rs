@@ -0,0 +1,83 @@
+mod support;
+
+use bevy_prototype_lyon::prelude::*;
+use shape_lerping::metrics::signed_area;
+use shape_lerping::offset::{offset, Join};
+use support::{polygons, square_points};
+use tess::path::{Event, Path};
+
+const TOLERANCE: f32 = 0.01;
+
+fn offset_area(path: &Path, distance: f32, join: Join) -> f32 {
+    signed_area(&offset(path, distance, join, TOLERANCE), TOLERANCE)
+}
+
+fn assert_close(a: f32, b: f32) {
+    assert!((a - b).abs() < 1e-3, "{} != {}", a, b);
+}
+
+#[test]
+fn squares_grow_and_shrink() {
+    let square = polygons(&[&square_points(0.0, 0.0, 10.0)]);
+    assert_close(offset_area(&square, 1.0, Join::Miter), 144.0);
+    assert_close(offset_area(&square, -1.0, Join::Miter), 64.0);
+}
+
+#[test]
+fn joins_only_change_the_outside_of_corners() {
+    let square = polygons(&[&square_points(0.0, 0.0, 10.0)]);
+    let round = offset_area(&square, 1.0, Join::Round);
+    assert_close(offset_area(&square, 1.0, Join::Bevel), 142.0);
+    assert!(
+        round > 142.0 && round < 100.0 + 40.0 + std::f32::consts::PI,
+        "{}",
+        round
+    );
+    for &join in &[Join::Round, Join::Bevel] {
+        assert_close(offset_area(&square, -1.0, join), 64.0);
+    }
+}
+
+#[test]
+fn insets_past_the_middle_leave_nothing() {
+    let square = polygons(&[&square_points(0.0, 0.0, 10.0)]);
+    assert_eq!(offset(&square, -6.0, Join::Miter).iter().count(), 0);
+    // Only the thick arm of the L is left
+    let l = polygons(&[&[
+        (0.0, 0.0),
+        (30.0, 0.0),
+        (30.0, 4.0),
+        (10.0, 4.0),
+        (10.0, 20.0),
+        (0.0, 20.0),
+    ]]);
+    assert_close(offset_area(&l, -3.0, Join::Miter), 4.0 * 14.0);
+}
+
+#[test]
+fn holes_shrink_as_outlines_grow() {
+    let mut hole = square_points(5.0, 5.0, 10.0);
+    hole.reverse();
+    let ring = polygons(&[&square_points(0.0, 0.0, 20.0), &hole]);
+    assert_close(
+        offset_area(&ring, 1.0, Join::Miter),
+        22.0 * 22.0 - 8.0 * 8.0,
+    );
+}
+
+#[test]
+fn outlines_grown_into_each_other_merge() {
+    let squares = polygons(&[
+        &square_points(0.0, 0.0, 10.0),
+        &square_points(12.0, 0.0, 10.0),
+    ]);
+    let merged = offset(&squares, 2.0, Join::Miter, TOLERANCE);
+    assert_close(signed_area(&merged, TOLERANCE), 26.0 * 14.0);
+    assert_eq!(
+        merged
+            .iter()
+            .filter(|event| matches!(event, Event::Begin { .. }))
+            .count(),
+        1
+    );
+}
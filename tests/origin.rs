@@ -0,0 +1,17 @@
+use shape_lerping::origin::matched_progress;
+
+#[test]
+fn matches_the_first_progress_at_the_speed() {
+    assert!((matched_progress(0.25, |progress| progress) - 0.25).abs() < 1e-4);
+    assert!((matched_progress(0.5, |progress| 1.0 - progress) - 0.5).abs() < 1e-4);
+    // Speeding up, then slowing down again
+    let speed_at = |progress: f32| 1.0 - (2.0 * progress - 1.0).powi(2);
+    assert!((matched_progress(0.75, speed_at) - 0.25).abs() < 1e-4);
+}
+
+#[test]
+fn nearest_when_it_never_gets_there() {
+    assert_eq!(matched_progress(0.0, |progress| progress), 0.0);
+    assert!(matched_progress(2.0, |progress| progress) > 0.95);
+    assert_eq!(matched_progress(2.0, |_| 1.0), 0.0);
+}
@@ -0,0 +1,303 @@
+mod support;
+
+use bevy::math::Vec2;
+use bevy_prototype_lyon::prelude::*;
+use shape_lerping::path_lerping::{
+    aligned_events, carried_metadata, lerped_aligned, EventZipper, Lerp, ZipStrategy,
+};
+use support::{points_reached, polyline};
+use tess::{
+    math::point,
+    path::{Event, Path, PathEvent},
+};
+
+// Begin, Line, End
+fn segment() -> Path {
+    polyline(&[(0.0, 0.0), (10.0, 0.0)], true)
+}
+
+// Begin, Line, Line, Line, End
+fn square() -> Path {
+    support::square(0.0, 0.0, 20.0)
+}
+
+#[test]
+fn growing_pads_the_start_with_the_first_event() {
+    let (from, to) = (segment(), square());
+    let (snapped, result) = from.lerped(&to, Vec2::ZERO, 0.0);
+    assert!(!snapped);
+    assert_eq!(result.iter().count(), to.iter().count());
+    // Nothing has moved yet, so the padding sits on the start of `from` and the rest follows on
+    let reached = points_reached(&result);
+    assert_eq!(reached[..3], [point(0.0, 0.0); 3]);
+    assert_eq!(reached[3], point(10.0, 0.0));
+}
+
+#[test]
+fn growing_snaps_to_exactly_the_target() {
+    let (from, to) = (segment(), square());
+    let (snapped, result) = from.lerped(&to, Vec2::ONE, f32::MAX);
+    assert!(snapped);
+    assert!(result.iter().eq(to.iter()));
+}
+
+#[test]
+fn growing_from_a_bare_begin() {
+    let mut builder = Path::builder();
+    builder.begin(point(5.0, 5.0));
+    builder.end(false);
+    let from = builder.build();
+    let to = square();
+    let (_, result) = from.lerped(&to, Vec2::splat(0.5), 0.0);
+    assert_eq!(result.iter().count(), to.iter().count());
+    assert!(matches!(result.iter().next(), Some(Event::Begin { .. })));
+}
+
+#[test]
+fn shrinking_keeps_the_padding_until_it_snaps() {
+    let (from, to) = (square(), segment());
+    let (snapped, result) = from.lerped(&to, Vec2::splat(0.5), 0.0);
+    assert!(!snapped);
+    assert_eq!(result.iter().count(), from.iter().count());
+    // The padding pulls the first events of `from` toward the start of `to`
+    assert_eq!(points_reached(&result)[1], point(10.0, 0.0));
+}
+
+#[test]
+fn shrinking_keeps_one_begin_per_subpath() {
+    let (from, to) = (square(), segment());
+    for &t in &[0.0, 0.5, 0.9] {
+        let (_, result) = from.lerped(&to, Vec2::splat(t), 0.0);
+        let begins = result
+            .iter()
+            .filter(|event| matches!(event, Event::Begin { .. }))
+            .count();
+        assert_eq!(begins, 1);
+    }
+}
+
+#[test]
+fn shrinking_snaps_to_exactly_the_target() {
+    let (from, to) = (square(), segment());
+    let (snapped, result) = from.lerped(&to, Vec2::ONE, f32::MAX);
+    assert!(snapped);
+    assert!(result.iter().eq(to.iter()));
+}
+
+#[test]
+fn aligned_lerp_matches_the_general_one() {
+    for (from, to) in vec![
+        (square(), segment()),
+        (segment(), square()),
+        (square(), square()),
+    ] {
+        let mut aligned = Vec::new();
+        let padding = aligned_events(&from, &to, &mut aligned);
+        assert_eq!(aligned.len(), from.iter().count().max(to.iter().count()));
+        for &(t, margin) in &[(0.25, 0.0), (1.0, f32::MAX)] {
+            let (snapped, result) = from.lerped(&to, Vec2::splat(t), margin);
+            let (aligned_snapped, aligned_result) = lerped_aligned(
+                &from,
+                &to,
+                &aligned,
+                padding,
+                Vec2::splat(t),
+                margin,
+                &mut Vec::new(),
+            );
+            assert_eq!(snapped, aligned_snapped);
+            assert!(result.iter().eq(aligned_result.iter()));
+        }
+    }
+}
+
+#[test]
+fn growing_from_empty_starts_at_the_targets_centroid() {
+    let to = square();
+    let (snapped, result) = Path::new().lerped(&to, Vec2::ZERO, 0.0);
+    assert!(!snapped);
+    assert_eq!(result.iter().count(), to.iter().count());
+    assert_eq!(points_reached(&result)[0], point(10.0, 10.0));
+}
+
+#[test]
+fn shrinking_to_empty_snaps_to_empty() {
+    let from = square();
+    let (snapped, result) = from.lerped(&Path::new(), Vec2::splat(0.5), 0.0);
+    assert!(!snapped);
+    assert_eq!(result.iter().count(), from.iter().count());
+    let (snapped, result) = from.lerped(&Path::new(), Vec2::ONE, f32::MAX);
+    assert!(snapped);
+    assert_eq!(result.iter().count(), 0);
+}
+
+#[test]
+fn both_empty_is_already_snapped() {
+    let (snapped, result) = Path::new().lerped(&Path::new(), Vec2::splat(0.5), 0.0);
+    assert!(snapped);
+    assert_eq!(result.iter().count(), 0);
+}
+
+// Two squares side by side
+fn two_squares() -> Path {
+    let mut builder = Path::builder();
+    for &x in &[0.0, 40.0] {
+        builder.begin(point(x, 0.0));
+        builder.line_to(point(x + 20.0, 0.0));
+        builder.line_to(point(x + 20.0, 20.0));
+        builder.line_to(point(x, 20.0));
+        builder.end(true);
+    }
+    builder.build()
+}
+
+fn begins(path: &Path) -> usize {
+    path.iter()
+        .filter(|event| matches!(event, Event::Begin { .. }))
+        .count()
+}
+
+#[test]
+fn extra_subpaths_collapse_into_the_targets_centroid() {
+    let (from, to) = (two_squares(), segment());
+    let (snapped, result) = from.lerped(&to, Vec2::ONE, 0.0);
+    assert!(snapped);
+    assert!(result.iter().eq(to.iter()));
+    let (_, halfway) = from.lerped(&to, Vec2::splat(0.5), 0.0);
+    assert_eq!(begins(&halfway), 2);
+    // The second square heads for the middle of the segment, not for the segment's own points
+    assert_eq!(points_reached(&halfway)[5], point(22.5, 0.0));
+}
+
+#[test]
+fn missing_subpaths_grow_from_the_targets_centroid() {
+    let (from, to) = (segment(), two_squares());
+    let (_, halfway) = from.lerped(&to, Vec2::ZERO, 0.0);
+    assert_eq!(begins(&halfway), 2);
+    let centroid = point(30.0, 10.0);
+    assert!(points_reached(&halfway)[5..].iter().all(|&p| p == centroid));
+}
+
+#[test]
+fn mismatched_subpaths_skip_the_aligned_fast_path() {
+    let (from, to) = (two_squares(), segment());
+    let mut aligned = Vec::new();
+    assert_eq!(aligned_events(&from, &to, &mut aligned), 0);
+    assert!(aligned.is_empty());
+    let (snapped, result) = from.lerped(&to, Vec2::splat(0.25), 0.0);
+    let (aligned_snapped, aligned_result) = lerped_aligned(
+        &from,
+        &to,
+        &aligned,
+        0,
+        Vec2::splat(0.25),
+        0.0,
+        &mut Vec::new(),
+    );
+    assert_eq!(snapped, aligned_snapped);
+    assert!(result.iter().eq(aligned_result.iter()));
+}
+
+#[test]
+fn each_axis_closes_in_at_its_own_rate() {
+    let from = square();
+    let to = polyline(
+        &[(40.0, 40.0), (60.0, 40.0), (60.0, 60.0), (40.0, 60.0)],
+        true,
+    );
+    let (snapped, result) = from.lerped(&to, Vec2::new(1.0, 0.5), 0.0);
+    assert!(!snapped);
+    let expected = [
+        (40.0, 20.0),
+        (60.0, 20.0),
+        (60.0, 40.0),
+        (40.0, 40.0),
+        (40.0, 20.0),
+    ];
+    let expected = expected
+        .iter()
+        .map(|&(x, y)| point(x, y))
+        .collect::<Vec<_>>();
+    assert_eq!(points_reached(&result), expected);
+}
+
+#[test]
+fn zipped_pairs_are_what_lerping_lerps() {
+    // Rebuilding the path recomputes the points each event starts from, so only compare where they go
+    let ends = |event: &PathEvent| match event {
+        Event::End { .. } => None,
+        event => Some(event.to()),
+    };
+    for (from, to) in &[(segment(), square()), (square(), segment())] {
+        let (_, result) = from.lerped(to, Vec2::splat(0.5), 0.0);
+        let pairs = EventZipper::new(from, to, ZipStrategy::Padded);
+        let lerped = pairs
+            .map(|(from, to)| from.lerped(to, Vec2::splat(0.5), 0.0).1)
+            .collect::<Vec<_>>();
+        assert_eq!(lerped.len(), result.iter().count());
+        assert!(result
+            .iter()
+            .filter_map(|e| ends(&e))
+            .eq(lerped.iter().filter_map(ends)));
+    }
+}
+
+#[test]
+fn truncated_zipping_leaves_out_the_extras() {
+    let (from, to) = (square(), segment());
+    let pairs = EventZipper::new(&from, &to, ZipStrategy::Truncated).collect::<Vec<_>>();
+    assert_eq!(pairs.len(), to.iter().count());
+    assert!(pairs
+        .iter()
+        .map(|&(from, _)| from)
+        .eq(from.iter().take(pairs.len())));
+    assert!(pairs.iter().map(|&(_, to)| to).eq(to.iter()));
+}
+
+#[test]
+fn metadata_follows_the_target_events() {
+    let (from, to) = (segment(), square());
+    let (_, result) = from.lerped(&to, Vec2::splat(0.5), 0.0);
+    let to_data = ["a", "b", "c", "d", "e"];
+    let carried = carried_metadata(&from, &["x", "y", "z"], &to, &to_data, &result);
+    assert_eq!(carried, to_data);
+}
+
+#[test]
+fn collapsing_events_keep_their_metadata() {
+    let (from, to) = (square(), segment());
+    let from_data = ["a", "b", "c", "d", "e"];
+    let (_, result) = from.lerped(&to, Vec2::splat(0.5), 0.0);
+    let carried = carried_metadata(&from, &from_data, &to, &["x", "y", "z"], &result);
+    assert_eq!(carried, ["x", "b", "c", "y", "z"]);
+    // Until it snaps, when there's nothing left to collapse
+    let (snapped, result) = from.lerped(&to, Vec2::ONE, f32::MAX);
+    assert!(snapped);
+    let carried = carried_metadata(&from, &from_data, &to, &["x", "y", "z"], &result);
+    assert_eq!(carried, ["x", "y", "z"]);
+}
+
+#[test]
+fn collapsing_subpaths_keep_their_metadata() {
+    let mut builder = Path::builder();
+    for path in &[segment(), segment()] {
+        for event in path.iter() {
+            match event {
+                Event::Begin { at } => {
+                    builder.begin(at);
+                }
+                Event::Line { to, .. } => {
+                    builder.line_to(to);
+                }
+                Event::End { close, .. } => builder.end(close),
+                _ => unreachable!(),
+            }
+        }
+    }
+    let (from, to) = (builder.build(), square());
+    let (_, result) = from.lerped(&to, Vec2::splat(0.5), 0.0);
+    let from_data = ["p", "q", "r", "s", "t", "u"];
+    let to_data = ["a", "b", "c", "d", "e"];
+    let carried = carried_metadata(&from, &from_data, &to, &to_data, &result);
+    assert_eq!(carried, ["a", "b", "c", "d", "e", "s", "t", "u"]);
+}
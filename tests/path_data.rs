@@ -0,0 +1,63 @@
+mod support;
+
+use shape_lerping::target::{PathData, Verb};
+use support::{events, square};
+
+fn data(verbs: &[Verb], points: &[[f32; 2]]) -> PathData {
+    PathData {
+        verbs: verbs.to_vec(),
+        points: points.to_vec(),
+    }
+}
+
+#[test]
+fn round_trips_paths() {
+    let path = square(1.0, 2.0, 3.0);
+    let rebuilt = PathData::from(&path).to_path().unwrap();
+    assert_eq!(events(&rebuilt), events(&path));
+}
+
+#[test]
+fn rejects_segments_outside_a_subpath() {
+    let line_first = data(&[Verb::Line, Verb::End], &[[1.0, 1.0]]);
+    assert!(line_first.to_path().is_err());
+    let end_first = data(&[Verb::End], &[]);
+    assert!(end_first.to_path().is_err());
+}
+
+#[test]
+fn rejects_a_second_begin() {
+    let data = data(
+        &[Verb::Begin, Verb::Begin, Verb::End],
+        &[[0.0, 0.0], [1.0, 1.0]],
+    );
+    assert!(data.to_path().is_err());
+}
+
+#[test]
+fn rejects_unended_subpaths() {
+    let data = data(&[Verb::Begin, Verb::Line], &[[0.0, 0.0], [1.0, 1.0]]);
+    assert!(data.to_path().is_err());
+}
+
+#[test]
+fn rejects_missing_and_left_over_points() {
+    let missing = data(
+        &[Verb::Begin, Verb::Cubic, Verb::End],
+        &[[0.0, 0.0], [1.0, 1.0]],
+    );
+    assert!(missing.to_path().is_err());
+    let left_over = data(&[Verb::Begin, Verb::End], &[[0.0, 0.0], [1.0, 1.0]]);
+    assert!(left_over.to_path().is_err());
+}
+
+#[test]
+fn rejects_non_finite_points() {
+    for &bad in &[f32::NAN, f32::INFINITY, f32::NEG_INFINITY] {
+        let data = data(
+            &[Verb::Begin, Verb::Line, Verb::Close],
+            &[[0.0, 0.0], [bad, 1.0]],
+        );
+        assert!(data.to_path().is_err(), "{}", bad);
+    }
+}
@@ -0,0 +1,83 @@
+mod support;
+
+use bevy_prototype_lyon::prelude::*;
+use shape_lerping::path_lerping::{resample, sample_morph, sample_resampled, SampleOptions};
+use support::{points_reached, polygon};
+use tess::math::point;
+
+#[test]
+fn endpoints_are_the_paths_themselves() {
+    let from = polygon(&[(0.0, 0.0), (10.0, 0.0)]);
+    let to = polygon(&[(0.0, 0.0), (20.0, 0.0), (20.0, 20.0)]);
+    let options = SampleOptions::default();
+    assert!(sample_morph(&from, &to, 0.0, options)
+        .iter()
+        .eq(from.iter()));
+    assert!(sample_morph(&from, &to, 1.0, options).iter().eq(to.iter()));
+    assert!(sample_morph(&from, &to, 1.5, options).iter().eq(to.iter()));
+}
+
+#[test]
+fn samples_are_independent_of_each_other() {
+    let from = polygon(&[(0.0, 0.0), (10.0, 0.0)]);
+    let to = polygon(&[(10.0, 10.0), (30.0, 10.0)]);
+    let options = SampleOptions::default();
+    let halfway = sample_morph(&from, &to, 0.5, options);
+    assert_eq!(
+        points_reached(&halfway)[..2],
+        [point(5.0, 5.0), point(20.0, 5.0)]
+    );
+    let _ = sample_morph(&from, &to, 0.25, options);
+    assert!(sample_morph(&from, &to, 0.5, options)
+        .iter()
+        .eq(halfway.iter()));
+}
+
+#[test]
+fn nothing_snaps_however_close() {
+    let from = polygon(&[(0.0, 0.0), (10.0, 0.0)]);
+    let to = polygon(&[(0.0, 0.001), (10.0, 0.001)]);
+    let sample = sample_morph(&from, &to, 0.5, SampleOptions::default());
+    assert_eq!(points_reached(&sample)[0], point(0.0, 0.0005));
+}
+
+#[test]
+fn extrapolates_without_clamping() {
+    let from = polygon(&[(0.0, 0.0), (10.0, 0.0)]);
+    let to = polygon(&[(10.0, 0.0), (20.0, 0.0)]);
+    let options = SampleOptions {
+        clamp_t: false,
+        ..Default::default()
+    };
+    let sample = sample_morph(&from, &to, 2.0, options);
+    assert_eq!(points_reached(&sample)[0], point(20.0, 0.0));
+}
+
+#[test]
+fn resampled_points_are_evenly_spaced_along_the_outline() {
+    let square = polygon(&[(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)]);
+    assert_eq!(
+        resample(&square, 8, 0.1),
+        [
+            point(0.0, 0.0),
+            point(5.0, 0.0),
+            point(10.0, 0.0),
+            point(10.0, 5.0),
+            point(10.0, 10.0),
+            point(5.0, 10.0),
+            point(0.0, 10.0),
+            point(0.0, 5.0),
+        ]
+    );
+}
+
+#[test]
+fn resampled_morphs_end_on_the_resampled_target() {
+    let triangle = polygon(&[(0.0, 0.0), (10.0, 0.0), (5.0, 8.0)]);
+    let square = polygon(&[(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)]);
+    let end = sample_resampled(&triangle, &square, 1.0, 8, 0.1);
+    let end = points_reached(&end);
+    // Closing the polygon ends back on its first point
+    assert_eq!(end[..8], resample(&square, 8, 0.1)[..]);
+    assert_eq!(end[8], end[0]);
+}
@@ -0,0 +1,100 @@
+#![cfg(feature = "simd")]
+
+use bevy::math::Vec2;
+use bevy_prototype_lyon::prelude::*;
+use shape_lerping::path_lerping::{build_path, Lerp};
+use tess::{
+    math::{point, Point},
+    path::{Event, Path, PathEvent},
+};
+
+// Same shape of path either way, lines, quadratics and cubics over a few subpaths, well past
+// the size the SIMD lerp takes over at and not a multiple of its lanes
+fn path(seed: u32) -> Path {
+    let mut state = seed;
+    let mut next = || {
+        state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+        point(
+            (state >> 8) as f32 / (1 << 24) as f32 * 2000.0 - 1000.0,
+            (state & 0xffff) as f32 / 65536.0 * 2000.0 - 1000.0,
+        )
+    };
+    let mut builder = Path::builder();
+    for subpath in 0..3 {
+        builder.begin(next());
+        for i in 0..40 {
+            match (i + subpath) % 3 {
+                0 => {
+                    builder.line_to(next());
+                }
+                1 => {
+                    builder.quadratic_bezier_to(next(), next());
+                }
+                _ => {
+                    builder.cubic_bezier_to(next(), next(), next());
+                }
+            }
+        }
+        builder.end(subpath != 1);
+    }
+    builder.build()
+}
+
+fn bits(event: PathEvent) -> Vec<(u32, u32)> {
+    let points: Vec<Point> = match event {
+        Event::Begin { at } => vec![at],
+        Event::Line { from, to } => vec![from, to],
+        Event::Quadratic { from, ctrl, to } => vec![from, ctrl, to],
+        Event::Cubic {
+            from,
+            ctrl1,
+            ctrl2,
+            to,
+        } => vec![from, ctrl1, ctrl2, to],
+        Event::End { last, first, close } => vec![last, first, point(close as u8 as f32, 0.0)],
+    };
+    points
+        .into_iter()
+        .map(|point| (point.x.to_bits(), point.y.to_bits()))
+        .collect()
+}
+
+// The per-event lerp the SIMD one has to match
+fn scalar_lerped(from: &Path, to: &Path, t: Vec2, p: f32) -> (bool, Path) {
+    let mut all_snapped = true;
+    let events = from
+        .iter()
+        .zip(to.iter())
+        .map(|(from, to)| {
+            let (snapped, event) = from.lerped(to, t, p);
+            all_snapped &= snapped;
+            event
+        })
+        .collect::<Vec<_>>();
+    (all_snapped, build_path(&events))
+}
+
+#[test]
+fn simd_lerp_matches_scalar_bit_for_bit() {
+    let (from, to) = (path(1), path(2));
+    for &t in &[
+        Vec2::ZERO,
+        Vec2::splat(0.1),
+        Vec2::new(0.3, 0.7),
+        Vec2::splat(1.0 / 3.0),
+        Vec2::ONE,
+    ] {
+        // Without snapping, snapping some points and snapping every one
+        for &p in &[0.0, 400.0, f32::INFINITY] {
+            let (simd_snapped, simd) = (&from).lerped(&to, t, p);
+            let (scalar_snapped, scalar) = scalar_lerped(&from, &to, t, p);
+            assert_eq!(simd_snapped, scalar_snapped, "t {:?}, p {}", t, p);
+            assert!(
+                simd.iter().map(bits).eq(scalar.iter().map(bits)),
+                "t {:?}, p {}",
+                t,
+                p
+            );
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use bevy_prototype_lyon::prelude::*;
+use shape_lerping::spring::SpringLerp;
+use tess::math::{point, Point};
+
+const DT: f32 = 1.0 / 60.0;
+
+// Where the point went each frame on its way from the origin to (100, 0), for `seconds`
+fn trajectory(mut spring: SpringLerp, seconds: f32) -> Vec<Point> {
+    let mut points = [point(0.0, 0.0)];
+    let target = [point(100.0, 0.0)];
+    (0..(seconds / DT) as usize)
+        .map(|_| {
+            spring.step(&mut points, &target, DT);
+            points[0]
+        })
+        .collect()
+}
+
+#[test]
+fn underdamped_springs_overshoot_and_settle() {
+    let path = trajectory(SpringLerp::default(), 5.0);
+    assert!(path.iter().any(|p| p.x > 101.0));
+    assert!((path[path.len() - 1].x - 100.0).abs() < 0.01);
+}
+
+#[test]
+fn critically_damped_springs_never_overshoot() {
+    let path = trajectory(SpringLerp::new(100.0, 20.0, 1.0), 5.0);
+    assert!(path.iter().all(|p| p.x <= 100.0 + 1e-3), "{:?}", path);
+    assert!((path[path.len() - 1].x - 100.0).abs() < 0.01);
+}
+
+#[test]
+fn settling_needs_the_point_to_have_stopped_too() {
+    let mut spring = SpringLerp::default();
+    let mut points = [point(0.0, 0.0)];
+    let target = [point(0.0, 0.0)];
+    assert!(spring.settled(&points, &target, 0.1, DT));
+    // Flung past the target from right on it
+    spring.step(&mut points, &[point(1000.0, 0.0)], DT);
+    points[0] = target[0];
+    assert!(!spring.settled(&points, &target, 0.1, DT));
+}
@@ -0,0 +1,77 @@
+mod support;
+
+use bevy_prototype_lyon::prelude::*;
+use shape_lerping::metrics::signed_area;
+use shape_lerping::stroke::stroked;
+use support::polyline;
+use tess::path::{Event, Path};
+
+const TOLERANCE: f32 = 0.01;
+
+fn outline_areas(path: &Path) -> Vec<f32> {
+    let mut areas = Vec::new();
+    let mut builder = Path::builder();
+    for event in path.iter() {
+        match event {
+            Event::Begin { at } => {
+                builder.begin(at);
+            }
+            Event::Line { to, .. } => {
+                builder.line_to(to);
+            }
+            Event::End { close, .. } => {
+                assert!(close, "Outlines are always closed");
+                builder.end(true);
+                let outline = std::mem::replace(&mut builder, Path::builder()).build();
+                areas.push(signed_area(&outline, TOLERANCE));
+            }
+            _ => panic!("Outlines are made of lines"),
+        }
+    }
+    areas
+}
+
+fn assert_areas(path: &Path, expected: &[f32]) {
+    let areas = outline_areas(path);
+    assert_eq!(areas.len(), expected.len(), "{:?}", areas);
+    for (area, expected) in areas.iter().zip(expected) {
+        assert!((area - expected).abs() < 1e-3, "{:?}", areas);
+    }
+}
+
+#[test]
+fn lines_become_rectangles() {
+    let line = polyline(&[(0.0, 0.0), (10.0, 0.0)], false);
+    assert_areas(&stroked(&line, 2.0, TOLERANCE), &[20.0]);
+}
+
+#[test]
+fn corners_are_mitred() {
+    let corner = polyline(&[(0.0, 0.0), (10.0, 0.0), (10.0, 10.0)], false);
+    assert_areas(&stroked(&corner, 2.0, TOLERANCE), &[40.0]);
+}
+
+#[test]
+fn closed_paths_become_an_outline_and_a_hole() {
+    let anticlockwise = polyline(&[(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)], true);
+    assert_areas(&stroked(&anticlockwise, 2.0, TOLERANCE), &[144.0, -64.0]);
+    let clockwise = polyline(&[(0.0, 0.0), (0.0, 10.0), (10.0, 10.0), (10.0, 0.0)], true);
+    assert_areas(&stroked(&clockwise, 2.0, TOLERANCE), &[-64.0, 144.0]);
+}
+
+#[test]
+fn sharp_corners_are_bevelled() {
+    let spike = polyline(&[(0.0, 0.0), (10.0, 0.0), (0.0, 1.0)], false);
+    let outline = stroked(&spike, 2.0, TOLERANCE);
+    let furthest = outline
+        .iter()
+        .map(|event| event.to().x)
+        .fold(f32::MIN, f32::max);
+    assert!(furthest < 12.0, "{}", furthest);
+}
+
+#[test]
+fn zero_width_strokes_cover_nothing() {
+    let line = polyline(&[(0.0, 0.0), (10.0, 0.0)], false);
+    assert_eq!(stroked(&line, 0.0, TOLERANCE).iter().count(), 0);
+}
@@ -0,0 +1,124 @@
+// Renders paths on the CPU and compares them against reference images in tests/golden.
+// A missing reference fails the test, setting BLESS_GOLDEN=1 writes every reference that's
+// checked from the current output instead (commit them).
+use bevy_prototype_lyon::prelude::*;
+use image::{GrayImage, Luma};
+use std::{env, path::PathBuf};
+use tess::{path::Path, BuffersBuilder, FillOptions, FillTessellator, FillVertex, VertexBuffers};
+
+pub const SIZE: u32 = 256;
+// World units covered by the whole image, centred on the origin
+const EXTENT: f32 = 512.0;
+// Subsamples per pixel along each axis, so edges are antialiased
+const SUPERSAMPLING: u32 = 4;
+// A pixel counts as different past this much change in coverage...
+const PIXEL_TOLERANCE: u8 = 32;
+// ...and an image as different once this fraction of pixels do
+const MAX_DIFFERENT_PIXELS: f32 = 0.005;
+
+fn golden_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("golden")
+}
+
+// White where the path is filled, y up like in bevy
+pub fn render(path: &Path) -> GrayImage {
+    let mut geometry: VertexBuffers<[f32; 2], u32> = VertexBuffers::new();
+    FillTessellator::new()
+        .tessellate_path(
+            path,
+            &FillOptions::tolerance(0.05),
+            &mut BuffersBuilder::new(&mut geometry, |vertex: FillVertex| {
+                vertex.position().to_array()
+            }),
+        )
+        .expect("Failed to tessellate path");
+
+    let to_pixels = |[x, y]: [f32; 2]| {
+        [
+            (x / EXTENT + 0.5) * SIZE as f32,
+            (0.5 - y / EXTENT) * SIZE as f32,
+        ]
+    };
+    let samples = SUPERSAMPLING * SUPERSAMPLING;
+    let mut coverage = vec![0u32; (SIZE * SIZE) as usize];
+    for triangle in geometry.indices.chunks(3) {
+        let [a, b, c] = [0, 1, 2].map(|i| to_pixels(geometry.vertices[triangle[i] as usize]));
+        let min_x = a[0].min(b[0]).min(c[0]).floor().max(0.0) as u32;
+        let max_x = (a[0].max(b[0]).max(c[0]).ceil() as u32).min(SIZE);
+        let min_y = a[1].min(b[1]).min(c[1]).floor().max(0.0) as u32;
+        let max_y = (a[1].max(b[1]).max(c[1]).ceil() as u32).min(SIZE);
+        for y in min_y..max_y {
+            for x in min_x..max_x {
+                for s in 0..samples {
+                    let p = [
+                        x as f32 + ((s % SUPERSAMPLING) as f32 + 0.5) / SUPERSAMPLING as f32,
+                        y as f32 + ((s / SUPERSAMPLING) as f32 + 0.5) / SUPERSAMPLING as f32,
+                    ];
+                    if in_triangle(p, a, b, c) {
+                        coverage[(y * SIZE + x) as usize] += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    GrayImage::from_fn(SIZE, SIZE, |x, y| {
+        let covered = coverage[(y * SIZE + x) as usize].min(samples);
+        Luma([(covered * 255 / samples) as u8])
+    })
+}
+
+fn in_triangle(p: [f32; 2], a: [f32; 2], b: [f32; 2], c: [f32; 2]) -> bool {
+    let edge = |from: [f32; 2], to: [f32; 2]| {
+        (to[0] - from[0]) * (p[1] - from[1]) - (to[1] - from[1]) * (p[0] - from[0])
+    };
+    let (ab, bc, ca) = (edge(a, b), edge(b, c), edge(c, a));
+    (ab >= 0.0 && bc >= 0.0 && ca >= 0.0) || (ab <= 0.0 && bc <= 0.0 && ca <= 0.0)
+}
+
+// Fraction of pixels that differ noticeably, so antialiasing noise along edges doesn't fail tests
+pub fn difference(a: &GrayImage, b: &GrayImage) -> f32 {
+    let different = a
+        .pixels()
+        .zip(b.pixels())
+        .filter(|(a, b)| (a.0[0] as i16 - b.0[0] as i16).abs() > PIXEL_TOLERANCE as i16)
+        .count();
+    different as f32 / (SIZE * SIZE) as f32
+}
+
+pub fn assert_golden(name: &str, path: &Path) {
+    let actual = render(path);
+    let reference_path = golden_dir().join(format!("{}.png", name));
+    if env::var_os("BLESS_GOLDEN").is_some() {
+        actual
+            .save(&reference_path)
+            .expect("Failed to write reference image");
+        eprintln!("Wrote reference image {}", reference_path.display());
+        return;
+    }
+    if !reference_path.exists() {
+        panic!(
+            "No reference image {}, run with BLESS_GOLDEN=1 to write it",
+            reference_path.display()
+        );
+    }
+
+    let reference = image::open(&reference_path)
+        .expect("Failed to read reference image")
+        .into_luma8();
+    let difference = difference(&actual, &reference);
+    if difference > MAX_DIFFERENT_PIXELS {
+        let actual_path = golden_dir().join(format!("{}.actual.png", name));
+        actual
+            .save(&actual_path)
+            .expect("Failed to write actual image");
+        panic!(
+            "{} differs from its reference in {:.2}% of pixels, see {}",
+            name,
+            difference * 100.0,
+            actual_path.display()
+        );
+    }
+}
@@ -0,0 +1,51 @@
+// Paths and checks shared between the test files, each of which only uses some of them
+#![allow(dead_code)]
+
+pub mod golden;
+
+use bevy_prototype_lyon::prelude::*;
+use tess::{
+    math::{point, Point},
+    path::{Path, PathEvent},
+};
+
+pub fn polyline(points: &[(f32, f32)], close: bool) -> Path {
+    polylines(&[points], close)
+}
+
+pub fn polylines(lines: &[&[(f32, f32)]], close: bool) -> Path {
+    let mut builder = Path::builder();
+    for points in lines {
+        builder.begin(point(points[0].0, points[0].1));
+        for &(x, y) in &points[1..] {
+            builder.line_to(point(x, y));
+        }
+        builder.end(close);
+    }
+    builder.build()
+}
+
+pub fn polygon(points: &[(f32, f32)]) -> Path {
+    polyline(points, true)
+}
+
+pub fn polygons(rings: &[&[(f32, f32)]]) -> Path {
+    polylines(rings, true)
+}
+
+// Anticlockwise from the corner at (x, y)
+pub fn square_points(x: f32, y: f32, size: f32) -> [(f32, f32); 4] {
+    [(x, y), (x + size, y), (x + size, y + size), (x, y + size)]
+}
+
+pub fn square(x: f32, y: f32, size: f32) -> Path {
+    polygon(&square_points(x, y, size))
+}
+
+pub fn points_reached(path: &Path) -> Vec<Point> {
+    path.iter().map(|event| event.to()).collect()
+}
+
+pub fn events(path: &Path) -> Vec<PathEvent> {
+    path.iter().collect()
+}
@@ -0,0 +1,23 @@
+use shape_lerping::svg::path_data;
+
+#[test]
+fn reads_d_wherever_it_is_in_the_tag() {
+    let document = "<svg>\n\
+        <path d=\"M 0 0 L 1 0\"/>\n\
+        <path\n\td='M 2 2 L 3 2' fill=\"none\"/>\n\
+        <path fill=\"red\"\n    d = \"M 4 4 L 5 4\"></path>\n\
+        </svg>";
+    assert_eq!(path_data(document), "M 0 0 L 1 0 M 2 2 L 3 2 M 4 4 L 5 4 ");
+}
+
+#[test]
+fn skips_attributes_that_only_end_in_d() {
+    let document = "<path id=\"d\" data-d=\"M 9 9\" d=\"M 0 0 L 1 1\"/>";
+    assert_eq!(path_data(document), "M 0 0 L 1 1 ");
+}
+
+#[test]
+fn skips_other_elements_and_paths_without_d() {
+    let document = "<pathLength d=\"M 9 9\"/><path fill=\"red\"/><rect d=\"M 9 9\"/>";
+    assert_eq!(path_data(document), "");
+}
@@ -0,0 +1,56 @@
+mod support;
+
+use bevy_prototype_lyon::prelude::*;
+use shape_lerping::metrics::length;
+use shape_lerping::trim::trimmed;
+use support::{polygons, square, square_points};
+use tess::{
+    math::point,
+    path::{Event, Path},
+};
+
+const TOLERANCE: f32 = 0.01;
+
+fn closes(path: &Path) -> Vec<bool> {
+    path.iter()
+        .filter_map(|event| match event {
+            Event::End { close, .. } => Some(close),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn trims_by_length() {
+    let square = square(0.0, 0.0, 10.0);
+    let half = trimmed(&square, 0.0, 0.5, TOLERANCE);
+    assert!((length(&half, TOLERANCE) - 20.0).abs() < 1e-4);
+    assert_eq!(closes(&half), [false]);
+    let middle = trimmed(&square, 0.125, 0.375, TOLERANCE);
+    assert!((length(&middle, TOLERANCE) - 10.0).abs() < 1e-4);
+    assert_eq!(middle.iter().next().unwrap().to(), point(5.0, 0.0));
+}
+
+#[test]
+fn untrimmed_subpaths_stay_closed() {
+    let squares = polygons(&[
+        &square_points(0.0, 0.0, 10.0),
+        &square_points(20.0, 0.0, 10.0),
+    ]);
+    let whole = trimmed(&squares, 0.0, 1.0, TOLERANCE);
+    assert!((length(&whole, TOLERANCE) - 80.0).abs() < 1e-4);
+    assert_eq!(closes(&whole), [true, true]);
+    // The first square and half the second
+    let most = trimmed(&squares, 0.0, 0.75, TOLERANCE);
+    assert_eq!(closes(&most), [true, false]);
+}
+
+#[test]
+fn trims_either_way_round() {
+    let square = square(0.0, 0.0, 10.0);
+    let forward = trimmed(&square, 0.25, 0.75, TOLERANCE);
+    assert!(forward
+        .iter()
+        .eq(trimmed(&square, 0.75, 0.25, TOLERANCE).iter()));
+    assert_eq!(trimmed(&square, 0.5, 0.5, TOLERANCE).iter().count(), 0);
+}
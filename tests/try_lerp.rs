@@ -0,0 +1,72 @@
+mod support;
+
+use bevy::math::Vec2;
+use bevy_prototype_lyon::prelude::*;
+use shape_lerping::path_lerping::{LerpOptions, MorphError, TryLerp};
+use support::polygon;
+use tess::{math::Transform, path::Path};
+
+fn triangle() -> Path {
+    polygon(&[(0.0, 0.0), (10.0, 0.0), (0.0, 10.0)])
+}
+
+fn square() -> Path {
+    support::square(0.0, 0.0, 20.0)
+}
+
+const HALFWAY: LerpOptions = LerpOptions {
+    t: Vec2::splat(0.5),
+    margin_of_error: 0.0,
+};
+
+#[test]
+fn reports_padding_and_snapped_events() {
+    let outcome = triangle().try_lerped(&square(), HALFWAY).unwrap();
+    assert!(!outcome.snapped);
+    assert_eq!(outcome.events, 5);
+    assert_eq!(outcome.padding, 1);
+    // Only the shared starting point is already there
+    assert_eq!(outcome.snapped_events, 1);
+}
+
+#[test]
+fn every_event_snaps_on_a_full_step() {
+    let outcome = square()
+        .try_lerped(
+            &triangle(),
+            LerpOptions {
+                t: Vec2::ONE,
+                margin_of_error: 0.0,
+            },
+        )
+        .unwrap();
+    assert!(outcome.snapped);
+    assert_eq!(outcome.snapped_events, outcome.events);
+    assert!(outcome.path.iter().eq(triangle().iter()));
+}
+
+// Transformed rather than built, as the builder asserts its points are finite
+fn non_finite() -> Path {
+    triangle().transformed(&Transform::translation(f32::NAN, 0.0))
+}
+
+#[test]
+fn rejects_non_finite_points() {
+    assert_eq!(
+        triangle().try_lerped(&non_finite(), HALFWAY).unwrap_err(),
+        MorphError::NonFinite { event: 0 }
+    );
+    assert_eq!(
+        non_finite().try_lerped(&triangle(), HALFWAY).unwrap_err(),
+        MorphError::NonFinite { event: 0 }
+    );
+}
+
+#[test]
+fn grows_from_an_empty_source_but_rejects_an_empty_target() {
+    assert!(Path::new().try_lerped(&square(), HALFWAY).is_ok());
+    assert_eq!(
+        square().try_lerped(&Path::new(), HALFWAY).unwrap_err(),
+        MorphError::EmptyPath
+    );
+}
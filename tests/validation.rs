@@ -0,0 +1,53 @@
+mod support;
+
+use bevy::prelude::*;
+use bevy_prototype_lyon::entity::Path as PathComponent;
+use bevy_prototype_lyon::prelude::*;
+use shape_lerping::config::Config;
+use shape_lerping::path_lerping::{check, MorphError};
+use shape_lerping::validation::{check_targets, InvalidTarget, NonFinitePolicy};
+use shape_lerping::LerpingShape;
+use support::{events, polygon, square};
+use tess::math::Transform as PathTransform;
+
+fn triangle() -> tess::path::Path {
+    polygon(&[(0.0, 0.0), (10.0, 0.0), (0.0, 10.0)])
+}
+
+// Runs `check_targets` once on a square given a target of NaNs, returning the target it's left
+// with and the errors sent
+fn checked(policy: NonFinitePolicy) -> (tess::path::Path, Vec<MorphError>) {
+    let mut world = World::new();
+    world.insert_resource(Config {
+        non_finite: policy,
+        ..Config::default()
+    });
+    world.insert_resource(Events::<InvalidTarget>::default());
+    // Transformed rather than built, as the builder asserts its points are finite
+    let bad = triangle().transformed(&PathTransform::translation(f32::NAN, 0.0));
+    let entity = world
+        .spawn()
+        .insert(PathComponent(square(0.0, 0.0, 20.0)))
+        .insert(LerpingShape::lerping(bad, Vec2::splat(0.5), 1.0))
+        .id();
+    SystemStage::single(check_targets).run(&mut world);
+    let target = world.get::<LerpingShape>(entity).unwrap().target().clone();
+    let sent = world.get_resource::<Events<InvalidTarget>>().unwrap();
+    let errors = sent.get_reader().iter(sent).map(|e| e.error).collect();
+    (target, errors)
+}
+
+#[test]
+fn clamp_replaces_non_finite_points() {
+    let (target, errors) = checked(NonFinitePolicy::Clamp);
+    assert_eq!(errors, vec![MorphError::NonFinite { event: 0 }]);
+    assert!(!matches!(check(&target), Err(MorphError::NonFinite { .. })));
+    assert_eq!(events(&target).len(), events(&triangle()).len());
+}
+
+#[test]
+fn reject_keeps_the_shape_where_it_is() {
+    let (target, errors) = checked(NonFinitePolicy::Reject);
+    assert_eq!(errors, vec![MorphError::NonFinite { event: 0 }]);
+    assert_eq!(events(&target), events(&square(0.0, 0.0, 20.0)));
+}